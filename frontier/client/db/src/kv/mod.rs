@@ -358,4 +358,76 @@ impl<Block: BlockT> MappingDb<Block> {
 
 		Ok(())
 	}
+
+	/// Undo [`Self::write_none`] for the substrate block `block_hash`.
+	pub fn remove_none(&self, block_hash: Block::Hash) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		let mut transaction = sp_database::Transaction::new();
+
+		transaction.remove(crate::columns::SYNCED_MAPPING, &block_hash.encode());
+
+		self.db
+			.commit(transaction)
+			.map_err(|e| format!("{:?}", e))?;
+
+		Ok(())
+	}
+
+	/// Undo [`Self::write_hashes`] for the substrate block of `commitment`.
+	pub fn remove_hashes(&self, commitment: MappingCommitment<Block>) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		let mut transaction = sp_database::Transaction::new();
+
+		let substrate_hashes: Vec<Block::Hash> = self
+			.block_hash(&commitment.ethereum_block_hash)?
+			.unwrap_or_default()
+			.into_iter()
+			.filter(|hash| hash != &commitment.block_hash)
+			.collect();
+		if substrate_hashes.is_empty() {
+			transaction.remove(
+				crate::columns::BLOCK_MAPPING,
+				&commitment.ethereum_block_hash.encode(),
+			);
+		} else {
+			transaction.set(
+				crate::columns::BLOCK_MAPPING,
+				&commitment.ethereum_block_hash.encode(),
+				&substrate_hashes.encode(),
+			);
+		}
+
+		for ethereum_transaction_hash in commitment.ethereum_transaction_hashes {
+			let metadata: Vec<TransactionMetadata<Block>> = self
+				.transaction_metadata(&ethereum_transaction_hash)?
+				.into_iter()
+				.filter(|metadata| metadata.block_hash != commitment.block_hash)
+				.collect();
+			if metadata.is_empty() {
+				transaction.remove(
+					crate::columns::TRANSACTION_MAPPING,
+					&ethereum_transaction_hash.encode(),
+				);
+			} else {
+				transaction.set(
+					crate::columns::TRANSACTION_MAPPING,
+					&ethereum_transaction_hash.encode(),
+					&metadata.encode(),
+				);
+			}
+		}
+
+		transaction.remove(
+			crate::columns::SYNCED_MAPPING,
+			&commitment.block_hash.encode(),
+		);
+
+		self.db
+			.commit(transaction)
+			.map_err(|e| format!("{:?}", e))?;
+
+		Ok(())
+	}
 }
@@ -195,6 +195,26 @@ where
 		tx.commit().await
 	}
 
+	/// Remove everything indexed for the provided substrate blocks, which are being reverted.
+	pub async fn revert(&self, substrate_block_hashes: &[H256]) -> Result<(), Error> {
+		let mut tx = self.pool().begin().await?;
+
+		for table in ["logs", "transactions", "blocks", "sync_status"] {
+			let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+				"DELETE FROM {table} WHERE substrate_block_hash IN ("
+			));
+			let mut hashes = builder.separated(", ");
+			for hash in substrate_block_hashes.iter() {
+				hashes.push_bind(hash.as_bytes());
+			}
+			hashes.push_unseparated(")");
+			let query = builder.build();
+			query.execute(&mut *tx).await?;
+		}
+
+		tx.commit().await
+	}
+
 	/// Index the block metadata for the genesis block.
 	pub async fn insert_genesis_block_metadata<Client, BE>(
 		&self,
@@ -1844,4 +1864,50 @@ LIMIT 10001";
 			super::build_query(&mut qb, from_block, to_block, addresses, topics).sql();
 		assert_eq!(expected_query_sql, actual_query_sql);
 	}
+
+	#[tokio::test]
+	async fn revert_removes_reverted_blocks() {
+		let TestData {
+			backend,
+			substrate_hash_2,
+			substrate_hash_3,
+			ethereum_hash_2,
+			ethereum_hash_3,
+			log_1_abcd_0_0_alice,
+			log_1_dcba_1_0_alice,
+			log_1_badc_2_0_alice,
+			log_2_abcd_0_0_bob,
+			log_2_dcba_1_0_bob,
+			log_2_badc_2_0_bob,
+			..
+		} = prepare().await;
+
+		backend
+			.revert(&[substrate_hash_3])
+			.await
+			.expect("revert must succeed");
+
+		// The reverted block is no longer visible through the eth RPC reader.
+		assert_eq!(backend.block_hash(&ethereum_hash_3).await, Ok(Some(vec![])));
+		assert_eq!(
+			backend.block_hash(&ethereum_hash_2).await,
+			Ok(Some(vec![substrate_hash_2]))
+		);
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 3,
+			addresses: vec![],
+			topics: vec![],
+			expected_result: vec![
+				log_1_abcd_0_0_alice.into(),
+				log_1_dcba_1_0_alice.into(),
+				log_1_badc_2_0_alice.into(),
+				log_2_abcd_0_0_bob.into(),
+				log_2_dcba_1_0_bob.into(),
+				log_2_badc_2_0_bob.into(),
+			],
+		};
+		let result = run_test_case(backend, &filter).await.expect("must succeed");
+		assert_eq!(result, filter.expected_result);
+	}
 }
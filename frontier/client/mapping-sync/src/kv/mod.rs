@@ -37,12 +37,12 @@ use fp_rpc::EthereumRuntimeRPCApi;
 
 use crate::{EthereumBlockNotification, EthereumBlockNotificationSinks, SyncStrategy};
 
-pub fn sync_block<Block: BlockT, C, BE>(
+/// The mapping the digest of `header` commits to, `None` for a block without Ethereum data.
+fn mapping_commitment<Block: BlockT, C, BE>(
 	client: &C,
 	overrides: Arc<OverrideHandle<Block>>,
-	backend: &fc_db::kv::Backend<Block>,
 	header: &Block::Header,
-) -> Result<(), String>
+) -> Result<Option<fc_db::kv::MappingCommitment<Block>>, String>
 where
 	C: HeaderBackend<Block> + StorageProvider<Block, BE>,
 	BE: Backend<Block>,
@@ -63,19 +63,10 @@ where
 			};
 
 			match log {
-				Log::Pre(PreLog::Block(block)) => {
-					let mapping_commitment = gen_from_block(block);
-					backend.mapping().write_hashes(mapping_commitment)
-				}
+				Log::Pre(PreLog::Block(block)) => Ok(Some(gen_from_block(block))),
 				Log::Post(post_log) => match post_log {
-					PostLog::Hashes(hashes) => {
-						let mapping_commitment = gen_from_hashes(hashes);
-						backend.mapping().write_hashes(mapping_commitment)
-					}
-					PostLog::Block(block) => {
-						let mapping_commitment = gen_from_block(block);
-						backend.mapping().write_hashes(mapping_commitment)
-					}
+					PostLog::Hashes(hashes) => Ok(Some(gen_from_hashes(hashes))),
+					PostLog::Block(block) => Ok(Some(gen_from_block(block))),
 					PostLog::BlockHash(expect_eth_block_hash) => {
 						let schema =
 							fc_storage::onchain_storage_schema(client, substrate_block_hash);
@@ -94,21 +85,55 @@ where
 										db state ({got_eth_block_hash:?})"
 									))
 								} else {
-									let mapping_commitment = gen_from_block(block);
-									backend.mapping().write_hashes(mapping_commitment)
+									Ok(Some(gen_from_block(block)))
 								}
 							}
-							None => backend.mapping().write_none(substrate_block_hash),
+							None => Ok(None),
 						}
 					}
 				},
 			}
 		}
-		Err(FindLogError::NotFound) => backend.mapping().write_none(substrate_block_hash),
+		Err(FindLogError::NotFound) => Ok(None),
 		Err(FindLogError::MultipleLogs) => Err("Multiple logs found".to_string()),
 	}
 }
 
+pub fn sync_block<Block: BlockT, C, BE>(
+	client: &C,
+	overrides: Arc<OverrideHandle<Block>>,
+	backend: &fc_db::kv::Backend<Block>,
+	header: &Block::Header,
+) -> Result<(), String>
+where
+	C: HeaderBackend<Block> + StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	match mapping_commitment(client, overrides, header)? {
+		Some(mapping_commitment) => backend.mapping().write_hashes(mapping_commitment),
+		None => backend.mapping().write_none(header.hash()),
+	}
+}
+
+/// Undo [`sync_block`], removing the mapping of a block that is being reverted.
+///
+/// The block state must still be available, so this has to run before the block is reverted.
+pub fn revert_block<Block: BlockT, C, BE>(
+	client: &C,
+	overrides: Arc<OverrideHandle<Block>>,
+	backend: &fc_db::kv::Backend<Block>,
+	header: &Block::Header,
+) -> Result<(), String>
+where
+	C: HeaderBackend<Block> + StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	match mapping_commitment(client, overrides, header)? {
+		Some(mapping_commitment) => backend.mapping().remove_hashes(mapping_commitment),
+		None => backend.mapping().remove_none(header.hash()),
+	}
+}
+
 pub fn sync_genesis_block<Block: BlockT, C>(
 	client: &C,
 	backend: &fc_db::kv::Backend<Block>,
@@ -465,4 +465,84 @@ mod tests {
 			assert_eq!(sinks.len(), 0);
 		}
 	}
+
+	#[tokio::test]
+	async fn revert_block_removes_mapping() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		// Client
+		let (client, _) =
+			builder.build_with_native_executor::<frontier_template_runtime::RuntimeApi, _>(None);
+		let mut client = Arc::new(client);
+		// Overrides
+		let mut overrides_map = BTreeMap::new();
+		overrides_map.insert(
+			EthereumStorageSchema::V3,
+			Box::new(SchemaV3Override::new(client.clone())) as Box<dyn StorageOverride<_>>,
+		);
+		let overrides = Arc::new(OverrideHandle {
+			schemas: overrides_map,
+			fallback: Box::new(SchemaV3Override::new(client.clone())),
+		});
+
+		let frontier_backend = fc_db::kv::Backend::<OpaqueBlock>::new(
+			client.clone(),
+			&fc_db::kv::DatabaseSettings {
+				source: sc_client_db::DatabaseSource::RocksDb {
+					path: tmp.path().to_path_buf(),
+					cache_size: 0,
+				},
+			},
+		)
+		.expect("frontier backend");
+
+		// Import and sync three blocks.
+		let mut synced = Vec::new();
+		for _ in 0..3 {
+			let builder = client.new_block(ethereum_digest()).unwrap();
+			let block = builder.build().unwrap().block;
+			let header = block.header.clone();
+			client.import(BlockOrigin::Own, block).await.unwrap();
+			crate::kv::sync_block(&*client, overrides.clone(), &frontier_backend, &header)
+				.expect("sync block");
+
+			let ethereum_block_hash = match fp_consensus::find_log(header.digest()) {
+				Ok(fp_consensus::Log::Post(fp_consensus::PostLog::Hashes(hashes))) => {
+					hashes.block_hash
+				}
+				_ => panic!("the block has a frontier digest"),
+			};
+			synced.push((header, ethereum_block_hash));
+		}
+
+		// Revert the last two of them.
+		for (header, _) in synced[1..].iter().rev() {
+			crate::kv::revert_block(&*client, overrides.clone(), &frontier_backend, header)
+				.expect("revert block");
+		}
+
+		let (kept, kept_ethereum_block_hash) = &synced[0];
+		assert_eq!(
+			frontier_backend
+				.mapping()
+				.block_hash(kept_ethereum_block_hash),
+			Ok(Some(vec![kept.hash()]))
+		);
+		assert_eq!(frontier_backend.mapping().is_synced(&kept.hash()), Ok(true));
+		for (reverted, reverted_ethereum_block_hash) in &synced[1..] {
+			assert_eq!(
+				frontier_backend
+					.mapping()
+					.block_hash(reverted_ethereum_block_hash),
+				Ok(None)
+			);
+			assert_eq!(
+				frontier_backend.mapping().is_synced(&reverted.hash()),
+				Ok(false)
+			);
+		}
+	}
 }
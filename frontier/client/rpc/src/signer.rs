@@ -53,6 +53,11 @@ impl EthDevSigner {
 			.expect("Test key is valid; qed")],
 		}
 	}
+
+	/// Create a signer holding the given secret keys.
+	pub fn with_keys(keys: Vec<libsecp256k1::SecretKey>) -> Self {
+		Self { keys }
+	}
 }
 
 fn secret_key_address(secret: &libsecp256k1::SecretKey) -> H160 {
@@ -75,8 +75,8 @@ pub fn gpu_testnet_config() -> Result<ChainSpec, String> {
 /// Returns the properties for the [`gpuChainSpec`].
 pub fn gpu_chain_spec_properties() -> serde_json::map::Map<String, serde_json::Value> {
 	serde_json::json!({
-		"tokenDecimals": 18,
-		"tokenSymbol": "GP"
+		"tokenDecimals": TOKEN_DECIMALS,
+		"tokenSymbol": TOKEN_SYMBOL
 	})
 	.as_object()
 	.expect("Map given; qed")
@@ -43,6 +43,38 @@ pub struct Cli {
 
 	#[command(flatten)]
 	pub eth: EthConfiguration,
+
+	/// Seal blocks with manual seal instead of BABE, for development: `instant` seals a block
+	/// for every transaction, `manual` waits for `engine_createBlock` and a number seals a
+	/// block every given number of milliseconds.
+	#[arg(long)]
+	pub sealing: Option<Sealing>,
+}
+
+/// How a development node seals its blocks.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Sealing {
+	/// Seal and finalize a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal blocks on `engine_createBlock` calls only.
+	Manual,
+	/// Seal a block every given number of milliseconds.
+	Interval(u64),
+}
+
+impl std::str::FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Self::Instant),
+			"manual" => Ok(Self::Manual),
+			interval => interval
+				.parse()
+				.map(Self::Interval)
+				.map_err(|_| format!("expected `instant`, `manual` or milliseconds, got `{}`", s)),
+		}
+	}
 }
 
 /// Possible subcommands of the main binary.
@@ -97,6 +129,50 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Frontier database maintenance.
+	#[command(subcommand)]
+	FrontierDb(FrontierDbCmd),
+}
+
+/// Operations on the Frontier database selected by `--frontier-backend-type`.
+#[derive(Debug, clap::Subcommand)]
+pub enum FrontierDbCmd {
+	/// Create, read, update or delete a meta or mapping entry. KeyValue backend only.
+	Query(fc_cli::FrontierDbCmd),
+
+	/// Re-run the mapping sync for a range of blocks.
+	Reindex(FrontierDbReindexCmd),
+}
+
+/// Re-runs the Frontier mapping sync for a range of canonical blocks.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct FrontierDbReindexCmd {
+	/// First block to re-index.
+	#[arg(long)]
+	pub from: u32,
+
+	/// Last block to re-index, inclusive.
+	#[arg(long)]
+	pub to: u32,
+
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+}
+
+impl sc_cli::CliConfiguration for FrontierDbReindexCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
 }
 
 /// Avalailable frontier backend types.
@@ -109,6 +185,15 @@ pub enum BackendType {
 	Sql,
 }
 
+/// Optional Ethereum RPC modules.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, clap::ValueEnum)]
+pub enum EthApi {
+	/// `debug_traceTransaction`, `debug_traceBlockByNumber` and `debug_traceBlockByHash`.
+	Debug,
+	/// `trace_filter`.
+	Trace,
+}
+
 /// The ethereum-compatibility configuration used to run a node.
 #[derive(Clone, Debug, clap::Parser)]
 pub struct EthConfiguration {
@@ -116,13 +201,29 @@ pub struct EthConfiguration {
 	#[arg(long, default_value = "10000")]
 	pub max_past_logs: u32,
 
+	/// Optional Ethereum RPC modules to enable, e.g. `--ethapi=debug,trace`.
+	///
+	/// They replay blocks with a runtime built with the `evm-tracing` feature, which the on-chain
+	/// runtime is not: pass one with `--wasm-runtime-overrides`.
+	#[arg(long, value_enum, value_delimiter = ',')]
+	pub ethapi: Vec<EthApi>,
+
+	/// Maximum number of traces returned by a single `trace_filter` call.
+	#[arg(long, default_value = "500")]
+	pub ethapi_trace_max_count: u32,
+
 	/// Maximum fee history cache size.
 	#[arg(long, default_value = "2048")]
 	pub fee_history_limit: u64,
 
+	/// Serve `eth_accounts` and `eth_sendTransaction` with the well-known development keys.
 	#[arg(long)]
 	pub enable_dev_signer: bool,
 
+	/// File of additional hex-encoded secret keys for the dev signer, one per line.
+	#[arg(long, value_name = "PATH", requires = "enable_dev_signer")]
+	pub eth_dev_signer_key: Option<std::path::PathBuf>,
+
 	/// The dynamic-fee pallet target gas price set by block author
 	#[arg(long, default_value = "1")]
 	pub target_gas_price: u64,
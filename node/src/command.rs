@@ -18,9 +18,9 @@
 
 use super::benchmarking::{inherent_benchmark_data, RemarkBuilder, TransferKeepAliveBuilder};
 use crate::{
-	chain_spec, service,
+	chain_spec, eth, service,
 	service::{new_partial, FullClient},
-	Cli, Subcommand,
+	Cli, FrontierDbCmd, Subcommand,
 };
 use frame_benchmarking_cli::*;
 use sc_cli::{Result, SubstrateCli};
@@ -117,7 +117,7 @@ pub fn run() -> Result<()> {
 					},
 					BenchmarkCmd::Block(cmd) => {
 						// ensure that we keep the task manager alive
-						let partial = new_partial(&config, &cli.eth)?;
+						let partial = new_partial(&config, &cli.eth, cli.sealing)?;
 						cmd.run(partial.client)
 					},
 					#[cfg(not(feature = "runtime-benchmarks"))]
@@ -136,7 +136,7 @@ pub fn run() -> Result<()> {
 					},
 					BenchmarkCmd::Overhead(cmd) => {
 						// ensure that we keep the task manager alive
-						let partial = new_partial(&config, &cli.eth)?;
+						let partial = new_partial(&config, &cli.eth, cli.sealing)?;
 						let ext_builder = RemarkBuilder::new(partial.client.clone());
 
 						cmd.run(
@@ -149,7 +149,7 @@ pub fn run() -> Result<()> {
 					},
 					BenchmarkCmd::Extrinsic(cmd) => {
 						// ensure that we keep the task manager alive
-						let partial = service::new_partial(&config, &cli.eth)?;
+						let partial = service::new_partial(&config, &cli.eth, cli.sealing)?;
 						// Register the *Remark* and *TKA* builders.
 						let ext_factory = ExtrinsicFactory(vec![
 							Box::new(RemarkBuilder::new(partial.client.clone())),
@@ -184,7 +184,7 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, import_queue, .. } =
-					new_partial(&config, &cli.eth)?;
+					new_partial(&config, &cli.eth, cli.sealing)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
@@ -192,7 +192,7 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, .. } =
-					new_partial(&config, &cli.eth)?;
+					new_partial(&config, &cli.eth, cli.sealing)?;
 				Ok((cmd.run(client, config.database), task_manager))
 			})
 		},
@@ -200,7 +200,7 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, .. } =
-					new_partial(&config, &cli.eth)?;
+					new_partial(&config, &cli.eth, cli.sealing)?;
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
 		},
@@ -208,7 +208,7 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, import_queue, .. } =
-					new_partial(&config, &cli.eth)?;
+					new_partial(&config, &cli.eth, cli.sealing)?;
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
@@ -219,9 +219,11 @@ pub fn run() -> Result<()> {
 		Some(Subcommand::Revert(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
-				let PartialComponents { client, task_manager, backend, .. } =
-					new_partial(&config, &cli.eth)?;
-				let aux_revert = Box::new(|client: Arc<FullClient>, backend, blocks| {
+				let PartialComponents { client, task_manager, backend, other, .. } =
+					new_partial(&config, &cli.eth, cli.sealing)?;
+				let (_, _, _, _, _, frontier_backend, overrides) = other;
+				let aux_revert = Box::new(move |client: Arc<FullClient>, backend, blocks| {
+					eth::revert(client.clone(), &frontier_backend, overrides, blocks)?;
 					sc_consensus_babe::revert(client.clone(), backend, blocks)?;
 					grandpa::revert(client, blocks)?;
 					Ok(())
@@ -260,5 +262,36 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run::<Block>(&config))
 		},
+		Some(Subcommand::FrontierDb(FrontierDbCmd::Query(cmd))) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, other, .. } =
+					new_partial(&config, &cli.eth, cli.sealing)?;
+				let (_, _, _, _, _, frontier_backend, _) = other;
+				match frontier_backend {
+					fc_db::Backend::KeyValue(backend) => cmd.run(client, Arc::new(backend)),
+					fc_db::Backend::Sql(_) =>
+						Err("The Sql Frontier backend can't be queried, use `reindex` instead."
+							.into()),
+				}
+			})
+		},
+		Some(Subcommand::FrontierDb(FrontierDbCmd::Reindex(cmd))) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let PartialComponents { client, task_manager, other, .. } =
+					new_partial(&config, &cli.eth, cli.sealing)?;
+				let (_, _, _, _, _, frontier_backend, overrides) = other;
+				let (from, to) = (cmd.from, cmd.to);
+				Ok((
+					async move {
+						eth::reindex(client, &frontier_backend, overrides, from, to)
+							.await
+							.map_err(Into::into)
+					},
+					task_manager,
+				))
+			})
+		},
 	}
 }
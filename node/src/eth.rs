@@ -2,15 +2,22 @@ use crate::cli::EthConfiguration;
 
 use std::{
 	collections::BTreeMap,
+	fs,
 	path::PathBuf,
 	sync::{Arc, Mutex},
 };
 
 // Substrate
+use sc_client_api::{HeaderBackend, StateBackend, StorageProvider};
 use sc_service::{error::Error as ServiceError, Configuration};
+use sp_api::ProvideRuntimeApi;
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Block as BlockT, NumberFor, One, Zero};
 // Frontier
 pub use fc_consensus::FrontierBlockImport;
 pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use fc_storage::OverrideHandle;
+use fp_rpc::EthereumRuntimeRPCApi;
 // Local
 use gpu_primitives::Block;
 
@@ -36,3 +43,417 @@ pub fn new_frontier_partial(
 		fee_history_cache_limit: config.fee_history_limit,
 	})
 }
+
+/// Loads the additional dev signer keys from `--eth-dev-signer-key`, if given.
+pub fn dev_signer_keys(config: &EthConfiguration) -> Result<Vec<[u8; 32]>, ServiceError> {
+	let Some(path) = &config.eth_dev_signer_key else { return Ok(Vec::new()) };
+	let keys = fs::read_to_string(path).map_err(|e| {
+		ServiceError::Other(format!("Failed to read dev signer keys {}: {}", path.display(), e))
+	})?;
+
+	keys.lines()
+		.map(str::trim)
+		.filter(|line| !line.is_empty())
+		.map(|line| {
+			array_bytes::hex2array::<_, 32>(line).map_err(|e| {
+				ServiceError::Other(format!(
+					"Invalid dev signer key in {}: {:?}",
+					path.display(),
+					e
+				))
+			})
+		})
+		.collect()
+}
+
+/// Re-runs the mapping sync for the canonical blocks `from..=to`.
+///
+/// KeyValue mappings are rewritten from the block digests. The Sql backend drops the rows of each
+/// block before indexing it again, so stale or corrupted rows get replaced rather than skipped.
+pub async fn reindex<B, C, BE>(
+	client: Arc<C>,
+	frontier_backend: &fc_db::Backend<B>,
+	overrides: Arc<OverrideHandle<B>>,
+	from: NumberFor<B>,
+	to: NumberFor<B>,
+) -> Result<(), String>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + StorageProvider<B, BE> + Send + Sync + 'static,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	BE: sc_client_api::Backend<B> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	if from > to {
+		return Err(format!("Invalid block range {}..={}", from, to))
+	}
+
+	let mut number = from;
+	while number <= to {
+		let hash = client
+			.hash(number)
+			.map_err(|e| format!("{:?}", e))?
+			.ok_or_else(|| format!("Block #{} not found", number))?;
+
+		match frontier_backend {
+			fc_db::Backend::KeyValue(backend) => {
+				let header = client
+					.header(hash)
+					.map_err(|e| format!("{:?}", e))?
+					.ok_or_else(|| format!("Header of block #{} not found", number))?;
+				if number.is_zero() {
+					fc_mapping_sync::kv::sync_genesis_block(&*client, backend, &header)?;
+				} else {
+					fc_mapping_sync::kv::sync_block(&*client, overrides.clone(), backend, &header)?;
+				}
+			},
+			fc_db::Backend::Sql(backend) => {
+				backend.revert(&[hash]).await.map_err(|e| format!("{:?}", e))?;
+				if number.is_zero() {
+					backend
+						.insert_genesis_block_metadata(client.clone())
+						.await
+						.map_err(|e| format!("{:?}", e))?;
+				} else {
+					backend
+						.insert_block_metadata(client.clone(), hash)
+						.await
+						.map_err(|e| format!("{:?}", e))?;
+				}
+				backend.index_block_logs(client.clone(), hash).await;
+			},
+		}
+
+		number += One::one();
+	}
+
+	log::info!(target: "frontier-db", "Re-indexed blocks #{}..=#{}", from, to);
+	Ok(())
+}
+
+/// Removes what the mapping sync recorded for the blocks `revert` is about to drop, so the eth
+/// RPC stops serving them. Like the chain itself, finalized blocks are left untouched.
+pub fn revert<B, C, BE>(
+	client: Arc<C>,
+	frontier_backend: &fc_db::Backend<B>,
+	overrides: Arc<OverrideHandle<B>>,
+	blocks: NumberFor<B>,
+) -> Result<(), String>
+where
+	B: BlockT<Hash = H256>,
+	C: HeaderBackend<B> + StorageProvider<B, BE>,
+	BE: sc_client_api::Backend<B>,
+{
+	let info = client.info();
+	let blocks = blocks.min(info.best_number - info.finalized_number);
+	let mut reverted = Vec::new();
+	let mut number = info.best_number - blocks;
+	while number < info.best_number {
+		number += One::one();
+		reverted.push(
+			client
+				.hash(number)
+				.map_err(|e| format!("{:?}", e))?
+				.ok_or_else(|| format!("Block #{} not found", number))?,
+		);
+	}
+
+	match frontier_backend {
+		fc_db::Backend::KeyValue(backend) => {
+			for hash in reverted.iter().rev() {
+				let header = client
+					.header(*hash)
+					.map_err(|e| format!("{:?}", e))?
+					.ok_or_else(|| format!("Header of block {} not found", hash))?;
+				fc_mapping_sync::kv::revert_block(&*client, overrides.clone(), backend, &header)?;
+			}
+			// The mapping sync worker resumes from these tips, which must still exist.
+			let tips = backend
+				.meta()
+				.current_syncing_tips()?
+				.into_iter()
+				.filter(|tip| !reverted.contains(tip))
+				.collect();
+			backend.meta().write_current_syncing_tips(tips)?;
+		},
+		fc_db::Backend::Sql(backend) => futures::executor::block_on(backend.revert(&reverted))
+			.map_err(|e| format!("{:?}", e))?,
+	}
+
+	log::info!(target: "frontier-db", "Reverted {} blocks", reverted.len());
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::path::Path;
+
+	use codec::Encode;
+	use tempfile::tempdir;
+	// Substrate
+	use sc_block_builder::BlockBuilderProvider;
+	use sp_consensus::BlockOrigin;
+	use sp_core::{H160, U256};
+	use sp_io::hashing::twox_128;
+	use sp_runtime::generic::DigestItem;
+	use substrate_test_runtime_client::{
+		client::Client, prelude::*, runtime::Block as TestBlock, DefaultTestClientBuilderExt,
+		TestClientBuilder, TestClientBuilderExt,
+	};
+	// Frontier
+	use fc_storage::{SchemaV3Override, StorageOverride};
+	use fp_storage::{
+		EthereumStorageSchema, ETHEREUM_CURRENT_RECEIPTS, PALLET_ETHEREUM, PALLET_ETHEREUM_SCHEMA,
+	};
+
+	type EthTestClient = Client<Backend, ExecutorDispatch, TestBlock, gpu_runtime::RuntimeApi>;
+
+	fn storage_prefix_build(module: &[u8], storage: &[u8]) -> Vec<u8> {
+		[twox_128(module), twox_128(storage)].concat().to_vec()
+	}
+
+	/// The digest of an empty ethereum block, along with its hash.
+	fn ethereum_digest(number: u64) -> (DigestItem, H256) {
+		let partial_header = ethereum::PartialHeader {
+			parent_hash: H256::default(),
+			beneficiary: H160::default(),
+			state_root: H256::default(),
+			receipts_root: H256::default(),
+			logs_bloom: Default::default(),
+			difficulty: U256::zero(),
+			number: U256::from(number),
+			gas_limit: U256::zero(),
+			gas_used: U256::zero(),
+			timestamp: 0u64,
+			extra_data: Vec::new(),
+			mix_hash: H256::default(),
+			nonce: Default::default(),
+		};
+		let transactions: Vec<ethereum::TransactionV2> = vec![];
+		let hashes = fp_consensus::Hashes::from_block(ethereum::Block::new(
+			partial_header,
+			transactions,
+			vec![],
+		));
+		let hash = hashes.block_hash;
+		let digest = DigestItem::Consensus(
+			fp_consensus::FRONTIER_ENGINE_ID,
+			fp_consensus::PostLog::Hashes(hashes).encode(),
+		);
+		(digest, hash)
+	}
+
+	/// A log emitted by the only transaction of block `number`.
+	fn ethereum_log(number: u64) -> ethereum::Log {
+		ethereum::Log {
+			address: H160::from_low_u64_be(number),
+			topics: vec![H256::from_low_u64_be(number)],
+			data: vec![],
+		}
+	}
+
+	/// Imports `blocks` blocks carrying an ethereum block and a receipt with one log each, and
+	/// returns the client along with the hashes of the ethereum blocks.
+	async fn test_chain(
+		blocks: u64,
+	) -> (Arc<EthTestClient>, Arc<OverrideHandle<TestBlock>>, Vec<H256>) {
+		// Initialize storage with schema V3
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		let (client, _) = builder.build_with_native_executor::<gpu_runtime::RuntimeApi, _>(None);
+		let mut client = Arc::new(client);
+
+		let mut ethereum_hashes = vec![];
+		for number in 1..=blocks {
+			let (digest, ethereum_hash) = ethereum_digest(number);
+			let receipts =
+				Encode::encode(&vec![ethereum::ReceiptV3::EIP1559(ethereum::EIP1559ReceiptData {
+					status_code: 1u8,
+					used_gas: U256::zero(),
+					logs_bloom: Default::default(),
+					logs: vec![ethereum_log(number)],
+				})]);
+
+			let mut builder = client.new_block(Default::default()).unwrap();
+			builder.push_deposit_log_digest_item(digest).unwrap();
+			builder
+				.push_storage_change(
+					storage_prefix_build(PALLET_ETHEREUM, ETHEREUM_CURRENT_RECEIPTS),
+					Some(receipts),
+				)
+				.unwrap();
+			let block = builder.build().unwrap().block;
+			client.import(BlockOrigin::Own, block).await.unwrap();
+			ethereum_hashes.push(ethereum_hash);
+		}
+
+		let overrides = Arc::new(OverrideHandle {
+			schemas: BTreeMap::from([(
+				EthereumStorageSchema::V3,
+				Box::new(SchemaV3Override::new(client.clone())) as Box<dyn StorageOverride<_>>,
+			)]),
+			fallback: Box::new(SchemaV3Override::new(client.clone())),
+		});
+
+		(client, overrides, ethereum_hashes)
+	}
+
+	fn kv_backend(client: Arc<EthTestClient>, path: &Path) -> fc_db::Backend<TestBlock> {
+		let backend = fc_db::kv::Backend::new(
+			client,
+			&fc_db::kv::DatabaseSettings {
+				source: sc_client_db::DatabaseSource::RocksDb {
+					path: path.join("kv"),
+					cache_size: 0,
+				},
+			},
+		)
+		.expect("frontier backend");
+		fc_db::Backend::KeyValue(backend)
+	}
+
+	async fn sql_backend(
+		path: &Path,
+		overrides: Arc<OverrideHandle<TestBlock>>,
+	) -> fc_db::Backend<TestBlock> {
+		let backend = fc_db::sql::Backend::new(
+			fc_db::sql::BackendConfig::Sqlite(fc_db::sql::SqliteBackendConfig {
+				path: Path::new("sqlite:///").join(path).join("test.db3").to_str().unwrap(),
+				create_if_missing: true,
+				cache_size: 204800,
+				thread_count: 4,
+			}),
+			100,
+			None,
+			overrides,
+		)
+		.await
+		.expect("indexer pool to be created");
+		fc_db::Backend::Sql(backend)
+	}
+
+	/// The `(block_number, ethereum_block_hash)` rows of the blocks table and the
+	/// `(substrate_block_hash, address, topic_1)` rows of the logs table.
+	async fn sql_rows(
+		backend: &fc_db::Backend<TestBlock>,
+	) -> (Vec<(i32, Vec<u8>)>, Vec<(Vec<u8>, Vec<u8>, Option<Vec<u8>>)>) {
+		let fc_db::Backend::Sql(backend) = backend else { unreachable!() };
+		let blocks = sqlx::query_as(
+			"SELECT block_number, ethereum_block_hash FROM blocks ORDER BY block_number",
+		)
+		.fetch_all(backend.pool())
+		.await
+		.unwrap();
+		let logs = sqlx::query_as(
+			"SELECT a.substrate_block_hash, address, topic_1 FROM logs AS a
+			INNER JOIN blocks AS b ON a.substrate_block_hash = b.substrate_block_hash
+			ORDER BY b.block_number",
+		)
+		.fetch_all(backend.pool())
+		.await
+		.unwrap();
+		(blocks, logs)
+	}
+
+	/// The substrate blocks the eth RPC resolves `ethereum_hashes` to, as `eth_getBlockByHash`
+	/// does.
+	async fn served_blocks(
+		client: &EthTestClient,
+		backend: &fc_db::Backend<TestBlock>,
+		ethereum_hashes: &[H256],
+	) -> Vec<Option<H256>> {
+		let reader: &(dyn fc_db::BackendReader<TestBlock> + Send + Sync) = match backend {
+			fc_db::Backend::KeyValue(backend) => backend,
+			fc_db::Backend::Sql(backend) => backend,
+		};
+		let mut served = vec![];
+		for hash in ethereum_hashes {
+			served.push(
+				fc_rpc::frontier_backend_client::load_hash::<TestBlock, _>(client, reader, *hash)
+					.await
+					.unwrap(),
+			);
+		}
+		served
+	}
+
+	// `revert` blocks on the Sql backend, whose queries other workers have to drive meanwhile.
+	#[tokio::test(flavor = "multi_thread")]
+	async fn revert_stops_serving_the_reverted_blocks() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let (client, overrides, ethereum_hashes) = test_chain(3).await;
+		let hashes: Vec<_> = (1..=3).map(|number| client.hash(number).unwrap()).collect();
+
+		for frontier_backend in [
+			kv_backend(client.clone(), tmp.path()),
+			sql_backend(tmp.path(), overrides.clone()).await,
+		] {
+			reindex(client.clone(), &frontier_backend, overrides.clone(), 1, 3)
+				.await
+				.unwrap();
+			assert_eq!(
+				served_blocks(&client, &frontier_backend, &ethereum_hashes).await,
+				hashes.clone(),
+			);
+
+			revert(client.clone(), &frontier_backend, overrides.clone(), 2).unwrap();
+			assert_eq!(
+				served_blocks(&client, &frontier_backend, &ethereum_hashes).await,
+				vec![hashes[0], None, None],
+			);
+			if let fc_db::Backend::Sql(backend) = &frontier_backend {
+				let logs =
+					fc_db::BackendReader::filter_logs(backend, 0, 3, vec![], vec![]).await.unwrap();
+				assert_eq!(
+					logs.iter().map(|log| log.substrate_block_hash).collect::<Vec<_>>(),
+					vec![hashes[0].unwrap()],
+				);
+			}
+		}
+	}
+
+	#[tokio::test]
+	async fn reindex_rewrites_corrupted_sql_rows() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let (client, overrides, ethereum_hashes) = test_chain(3).await;
+		let frontier_backend = sql_backend(tmp.path(), overrides.clone()).await;
+
+		reindex(client.clone(), &frontier_backend, overrides.clone(), 1, 3)
+			.await
+			.unwrap();
+		let indexed = sql_rows(&frontier_backend).await;
+		assert_eq!(
+			indexed.0,
+			(1..=3)
+				.zip(&ethereum_hashes)
+				.map(|(n, h)| (n, h.as_bytes().to_vec()))
+				.collect::<Vec<_>>(),
+		);
+		assert_eq!(indexed.1.len(), 3);
+		assert_eq!(indexed.1[1].1, ethereum_log(2).address.as_bytes().to_vec());
+
+		// Corrupt what was indexed for block #2.
+		let fc_db::Backend::Sql(backend) = &frontier_backend else { unreachable!() };
+		let hash = client.hash(2).unwrap().unwrap();
+		sqlx::query("UPDATE blocks SET ethereum_block_hash = ? WHERE substrate_block_hash = ?")
+			.bind(H256::repeat_byte(0xff).as_bytes())
+			.bind(hash.as_bytes())
+			.execute(backend.pool())
+			.await
+			.unwrap();
+		sqlx::query("UPDATE logs SET address = ? WHERE substrate_block_hash = ?")
+			.bind(H160::repeat_byte(0xff).as_bytes())
+			.bind(hash.as_bytes())
+			.execute(backend.pool())
+			.await
+			.unwrap();
+		assert_ne!(sql_rows(&frontier_backend).await, indexed);
+
+		reindex(client, &frontier_backend, overrides, 2, 2).await.unwrap();
+		assert_eq!(sql_rows(&frontier_backend).await, indexed);
+	}
+}
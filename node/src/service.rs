@@ -21,8 +21,11 @@
 //! Service implementation. Specialized wrapper over substrate service.
 
 use crate::{
-	eth::{db_config_dir, new_frontier_partial, FrontierBackend, FrontierPartialComponents},
-	BackendType, Cli, EthConfiguration,
+	eth::{
+		db_config_dir, dev_signer_keys, new_frontier_partial, FrontierBackend,
+		FrontierPartialComponents,
+	},
+	BackendType, Cli, EthApi, EthConfiguration, Sealing,
 };
 use codec::Encode;
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
@@ -31,6 +34,10 @@ use futures::prelude::*;
 use fc_rpc::{EthTask};
 use sc_client_api::{Backend, BlockBackend};
 use sc_consensus_babe::{self, SlotProportion};
+use sc_consensus_manual_seal::{
+	consensus::{babe::BabeConsensusDataProvider, timestamp::SlotTimestampProvider},
+	EngineCommand, ManualSealParams,
+};
 use sc_executor::NativeElseWasmExecutor;
 use sc_network::{event::Event, NetworkEventStream, NetworkService};
 use sc_network_common::sync::warp::WarpSyncParams;
@@ -38,7 +45,7 @@ use sc_network_sync::SyncingService;
 use sc_rpc::SubscriptionTaskExecutor;
 use sc_service::{config::Configuration, error::Error as ServiceError, RpcHandlers, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
-use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sc_transaction_pool_api::{OffchainTransactionPoolFactory, TransactionPool as _};
 use gpu_primitives::Block;
 use gpu_runtime::{RuntimeApi, TransactionConverter};
 use sp_api::ProvideRuntimeApi;
@@ -156,6 +163,7 @@ pub fn create_extrinsic(
 pub fn new_partial(
 	config: &Configuration,
 	eth_config: &EthConfiguration,
+	sealing: Option<Sealing>,
 ) -> Result<
 	sc_service::PartialComponents<
 		FullClient,
@@ -259,7 +267,7 @@ pub fn new_partial(
 		},
 	};
 
-	let (import_queue, babe_worker_handle) =
+	let (babe_import_queue, babe_worker_handle) =
 		sc_consensus_babe::import_queue(sc_consensus_babe::ImportQueueParams {
 			link: babe_link.clone(),
 			block_import: babe_block_import.clone(),
@@ -283,6 +291,17 @@ pub fn new_partial(
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool.clone()),
 		})?;
 
+	// Manually sealed blocks carry no BABE seal, so they can't go through the BABE verifier.
+	// They still go through the BABE block import, which keeps track of the epochs.
+	let import_queue = match sealing {
+		Some(_) => sc_consensus_manual_seal::import_queue(
+			Box::new(babe_block_import.clone()),
+			&task_manager.spawn_essential_handle(),
+			config.prometheus_registry(),
+		),
+		None => babe_import_queue,
+	};
+
 	Ok(sc_service::PartialComponents {
 		client,
 		backend,
@@ -324,6 +343,7 @@ pub struct NewFullBase {
 pub fn new_full_base(
 	mut config: Configuration,
 	eth_config: EthConfiguration,
+	sealing: Option<Sealing>,
 	disable_hardware_benchmarks: bool,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<Block, FullClient, FullGrandpaBlockImport>,
@@ -355,7 +375,7 @@ pub fn new_full_base(
 				frontier_backend,
 				overrides,
 			),
-	} = new_partial(&config, &eth_config)?;
+	} = new_partial(&config, &eth_config, sealing)?;
 
 	let auth_disc_publish_non_global_ips = config.network.allow_non_globals_in_dht;
 	let mut net_config = sc_network::config::FullNetworkConfiguration::new(&config.network);
@@ -409,6 +429,15 @@ pub fn new_full_base(
 
 	let pubsub_notification = pubsub_notification_sinks.clone();
 
+	// Channel of the `engine_*` RPC commands, when sealing blocks manually.
+	let (command_sink, commands_stream) = match sealing {
+		Some(_) => {
+			let (sink, stream) = futures::channel::mpsc::channel(1000);
+			(Some(sink), Some(stream))
+		},
+		None => (None, None),
+	};
+
 	// for ethereum-compatibility rpc.
 	config.rpc_id_provider = Some(Box::new(fc_rpc::EthereumSubIdProvider));
 	let eth_rpc_params = gpu_rpc::EthDeps {
@@ -437,6 +466,11 @@ pub fn new_full_base(
 		fee_history_cache_limit,
 		execute_gas_limit_multiplier: eth_config.execute_gas_limit_multiplier,
 		forced_parent_hashes: None,
+		enable_debug: eth_config.ethapi.contains(&EthApi::Debug),
+		enable_trace: eth_config.ethapi.contains(&EthApi::Trace),
+		trace_max_count: eth_config.ethapi_trace_max_count,
+		enable_dev_signer: eth_config.enable_dev_signer,
+		dev_signer_keys: dev_signer_keys(&eth_config)?,
 	};
 
 	let (rpc_extensions_builder, rpc_setup) = {
@@ -478,6 +512,7 @@ pub fn new_full_base(
 					},
 					backend: rpc_backend.clone(),
 					eth: eth_rpc_params.clone(),
+					command_sink: command_sink.clone(),
 				};
 
 				gpu_rpc::create_full(
@@ -528,7 +563,77 @@ pub fn new_full_base(
 
 	(with_startup_data)(&block_import, &babe_link);
 
-	if let sc_service::config::Role::Authority { .. } = &role {
+	if let Some(sealing) = sealing {
+		let proposer = sc_basic_authorship::ProposerFactory::new(
+			task_manager.spawn_handle(),
+			client.clone(),
+			transaction_pool.clone(),
+			prometheus_registry.as_ref(),
+			telemetry.as_ref().map(|x| x.handle()),
+		);
+
+		// Sealed blocks are claimed with the local BABE keys, the same way BABE would.
+		let babe_consensus_data_provider = BabeConsensusDataProvider::new(
+			client.clone(),
+			keystore_container.keystore(),
+			babe_link.epoch_changes().clone(),
+			babe_link.config().authorities.clone(),
+		)
+		.map_err(|err| ServiceError::Other(err.to_string()))?;
+
+		let commands_stream = commands_stream.expect("Created when sealing is set; qed");
+		// Without GRANDPA, blocks sealed automatically are finalized right away.
+		let seal_command = |create_empty| EngineCommand::SealNewBlock {
+			create_empty,
+			finalize: true,
+			parent_hash: None,
+			sender: None,
+		};
+		let commands_stream = match sealing {
+			Sealing::Manual => commands_stream.boxed(),
+			Sealing::Instant => stream::select(
+				commands_stream,
+				transaction_pool.import_notification_stream().map(move |_| seal_command(false)),
+			)
+			.boxed(),
+			Sealing::Interval(millis) => stream::select(
+				commands_stream,
+				stream::unfold((), move |()| async move {
+					futures_timer::Delay::new(Duration::from_millis(millis)).await;
+					Some((seal_command(true), ()))
+				}),
+			)
+			.boxed(),
+		};
+
+		let client_clone = client.clone();
+		let manual_seal = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+			block_import,
+			env: proposer,
+			client: client.clone(),
+			pool: transaction_pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: Some(Box::new(babe_consensus_data_provider)),
+			create_inherent_data_providers: move |_, ()| {
+				let client_clone = client_clone.clone();
+				async move {
+					// Timestamps advance by one slot per block, matching the BABE pre-digest.
+					let timestamp = SlotTimestampProvider::new_babe(client_clone)?;
+					let slot =
+						sp_consensus_babe::inherents::InherentDataProvider::new(timestamp.slot());
+
+					Ok((slot, timestamp))
+				}
+			},
+		});
+
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"manual-seal",
+			Some("block-authoring"),
+			manual_seal,
+		);
+	} else if let sc_service::config::Role::Authority { .. } = &role {
 		let proposer = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
@@ -691,7 +796,7 @@ pub fn new_full_base(
 		protocol_name: grandpa_protocol_name,
 	};
 
-	if enable_grandpa {
+	if enable_grandpa && sealing.is_none() {
 		// start the full GRANDPA voter
 		// NOTE: non-authorities could run the GRANDPA observer protocol, but at
 		// this point the full voter should provide better guarantees of block
@@ -754,7 +859,8 @@ pub fn new_full_base(
 /// Builds a new service for a full client.
 pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceError> {
 	let database_source = config.database.clone();
-	let task_manager = new_full_base(config, cli.eth, cli.no_hardware_benchmarks, |_, _| ())
+	let task_manager =
+		new_full_base(config, cli.eth, cli.sealing, cli.no_hardware_benchmarks, |_, _| ())
 		.map(|NewFullBase { task_manager, .. }| task_manager)?;
 
 	sc_storage_monitor::StorageMonitorService::try_spawn(
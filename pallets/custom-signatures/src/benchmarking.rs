@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of the wrapper overhead, dispatching an empty `remark`.
+
+use super::*;
+use crate::ethereum::signable_message;
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::{Currency, Get};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use parity_scale_codec::Encode;
+use sp_core::{ecdsa, testing::ECDSA};
+use sp_io::{
+	crypto::{ecdsa_generate, ecdsa_sign_prehashed},
+	hashing::keccak_256,
+};
+use sp_runtime::traits::{Bounded, IdentifyAccount};
+use sp_std::prelude::*;
+
+/// A funded account of a new key in the keystore.
+fn funded_account<T: Config>() -> (ecdsa::Public, T::AccountId) {
+	let public = ecdsa_generate(ECDSA, None);
+	let account = T::Signer::from(public).into_account();
+	T::Currency::make_free_balance_be(&account, BalanceOf::<T>::max_value() / 2u32.into());
+	(public, account)
+}
+
+/// `eth_sign` of `payload` by `public`.
+fn eth_sign(public: &ecdsa::Public, payload: &[u8]) -> Vec<u8> {
+	let hash = keccak_256(&signable_message(payload));
+	sign_prehashed(public, &hash)
+}
+
+fn sign_prehashed(public: &ecdsa::Public, hash: &[u8; 32]) -> Vec<u8> {
+	let signature = ecdsa_sign_prehashed(ECDSA, public, hash).expect("key is in the keystore");
+	AsRef::<[u8]>::as_ref(&signature).to_vec()
+}
+
+fn remark<T: Config>() -> <T as Config>::RuntimeCall
+where
+	<T as Config>::RuntimeCall: From<frame_system::Call<T>>,
+{
+	frame_system::Call::<T>::remark { remark: Vec::new() }.into()
+}
+
+#[benchmarks(where <T as Config>::RuntimeCall: From<frame_system::Call<T>>)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn call() {
+		let (public, signer) = funded_account::<T>();
+		let call = remark::<T>();
+		let nonce = frame_system::Pallet::<T>::account_nonce(&signer);
+		let valid_until = frame_system::Pallet::<T>::block_number();
+		let payload = (T::CallMagicNumber::get(), nonce, valid_until, call.clone());
+		let signature = eth_sign(&public, &payload.encode());
+
+		#[extrinsic_call]
+		_(RawOrigin::None, Box::new(call), signer.clone(), signature, nonce, valid_until);
+
+		assert_eq!(frame_system::Pallet::<T>::account_nonce(&signer), nonce + 1u32.into());
+	}
+
+	#[benchmark]
+	fn typed_call() {
+		let (public, signer) = funded_account::<T>();
+		let call = remark::<T>();
+		let nonce = frame_system::Pallet::<T>::account_nonce(&signer);
+		let valid_until: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+		let digest = Pallet::<T>::typed_call_digest(&call, &nonce, &valid_until);
+		let signature = sign_prehashed(&public, &digest);
+
+		#[extrinsic_call]
+		_(RawOrigin::None, Box::new(call), signer.clone(), signature, nonce, valid_until);
+
+		assert_eq!(frame_system::Pallet::<T>::account_nonce(&signer), nonce + 1u32.into());
+	}
+
+	#[benchmark]
+	fn call_sponsored() {
+		let (public, signer) = funded_account::<T>();
+		let (sponsor_public, sponsor) = funded_account::<T>();
+		let call = remark::<T>();
+		let nonce = frame_system::Pallet::<T>::account_nonce(&signer);
+		let valid_until = frame_system::Pallet::<T>::block_number();
+		let payload = (T::CallMagicNumber::get(), nonce, valid_until, call.clone());
+		let signature = eth_sign(&public, &payload.encode());
+		let approval =
+			(T::CallMagicNumber::get(), SPONSOR_PREFIX, &signer, nonce, valid_until, call.clone());
+		let sponsor_signature = eth_sign(&sponsor_public, &approval.encode());
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::None,
+			Box::new(call),
+			signer.clone(),
+			signature,
+			nonce,
+			valid_until,
+			sponsor,
+			sponsor_signature,
+		);
+
+		assert_eq!(frame_system::Pallet::<T>::account_nonce(&signer), nonce + 1u32.into());
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::tests::Runtime);
+}
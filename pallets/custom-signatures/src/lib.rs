@@ -20,19 +20,43 @@ pub use pallet::*;
 /// Ethereum-compatible signatures (eth_sign API call).
 pub mod ethereum;
 
-// #[cfg(test)]
-// mod tests;
+/// Ethereum-compatible typed data signatures (eth_signTypedData_v4 API call).
+pub mod eip712;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::{
-		dispatch::{Dispatchable, GetDispatchInfo},
+		dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
 		pallet_prelude::*,
-		traits::{Currency, ExistenceRequirement, Get, OnUnbalanced, WithdrawReasons},
+		traits::{
+			fungible,
+			tokens::{Fortitude, Preservation},
+			CallMetadata, Currency, ExistenceRequirement, Get, GetCallMetadata, Imbalance,
+			OnUnbalanced, WithdrawReasons,
+		},
+		weights::WeightToFee,
 	};
 	use frame_system::{ensure_none, pallet_prelude::*};
-	use sp_runtime::traits::{IdentifyAccount, Verify};
-	use sp_std::{convert::TryFrom, prelude::*};
+	use sp_core::ecdsa;
+	use sp_runtime::{
+		traits::{IdentifyAccount, UniqueSaturatedInto, Verify, Zero},
+		FixedPointNumber, SaturatedConversion,
+	};
+	use sp_std::{
+		convert::{TryFrom, TryInto},
+		prelude::*,
+	};
+
+	use crate::{eip712, WeightInfo};
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -42,42 +66,49 @@ pub mod pallet {
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: frame_system::Config + pallet_transaction_payment::Config {
 		/// The overarching event type.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
 		/// A signable call.
 		type RuntimeCall: Parameter
-			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin>
-			+ GetDispatchInfo;
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ GetCallMetadata;
 
 		/// User defined signature type.
 		type Signature: Parameter + Verify<Signer = Self::Signer> + TryFrom<Vec<u8>>;
 
-		/// User defined signer type.
-		type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+		/// User defined signer type, also used for the accounts of typed data signers.
+		type Signer: IdentifyAccount<AccountId = Self::AccountId> + From<ecdsa::Public>;
 
 		/// The currency trait.
-		type Currency: Currency<Self::AccountId>;
+		type Currency: Currency<Self::AccountId> + fungible::Inspect<Self::AccountId>;
 
 		/// The call fee destination.
+		///
+		/// The fee is computed as for a signed extrinsic by `pallet_transaction_payment`, from the
+		/// weight of the wrapped call and the length of the call and its signatures.
 		type OnChargeTransaction: OnUnbalanced<
 			<Self::Currency as Currency<Self::AccountId>>::NegativeImbalance,
 		>;
 
-		/// The call processing fee amount.
-		#[pallet::constant]
-		type CallFee: Get<BalanceOf<Self>>;
-
 		/// The call magic number.
 		#[pallet::constant]
 		type CallMagicNumber: Get<u16>;
 
+		/// The EVM chain id, part of the typed data signing domain.
+		#[pallet::constant]
+		type ChainId: Get<u64>;
+
 		/// A configuration for base priority of unsigned transactions.
 		///
 		/// This is exposed so that it can be tuned for particular runtime, when
 		/// multiple pallets send unsigned transactions.
 		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Weight information for the extrinsics of this pallet, without the wrapped call.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
@@ -88,6 +119,10 @@ pub mod pallet {
 		InvalidSignature,
 		/// Bad nonce parameter.
 		BadNonce,
+		/// The signed call is past its `valid_until` block.
+		Expired,
+		/// Sponsor signature and account mismatched.
+		InvalidSponsorSignature,
 	}
 
 	#[pallet::event]
@@ -99,26 +134,47 @@ pub mod pallet {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// # <weight>
-		/// - O(1).
-		/// - Limited storage reads.
-		/// - One DB write (event).
-		/// - Weight of derivative `call` execution + read/write + 10_000.
-		/// # </weight>
+		/// Dispatch `call` on behalf of `signer`, who signed it along with `nonce` and the last
+		/// block `valid_until` it can be included in.
+		///
+		/// The fee is charged from `signer` for the weight of `call` plus
+		/// [`WeightInfo::call`], the unused weight of `call` is refunded after dispatch.
 		#[pallet::call_index(0)]
 		#[pallet::weight({
-            let dispatch_info = call.get_dispatch_info();
-            (dispatch_info.weight.saturating_add(T::DbWeight::get().reads(1))
-                                 .saturating_add(T::DbWeight::get().writes(1))
-                                 .saturating_add(Weight::from_parts(10_000, 0)),
-             dispatch_info.class)
-        })]
+			let dispatch_info = call.get_dispatch_info();
+			(T::WeightInfo::call().saturating_add(dispatch_info.weight), dispatch_info.class)
+		})]
 		pub fn call(
 			origin: OriginFor<T>,
 			call: Box<<T as Config>::RuntimeCall>,
 			signer: T::AccountId,
 			signature: Vec<u8>,
 			#[pallet::compact] nonce: T::Nonce,
+			valid_until: BlockNumberFor<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			let len = Self::fee_len(&call, &[&signature]);
+			Self::ensure_signed_call(&call, &signer, signature, &nonce, &valid_until)?;
+
+			Self::dispatch_signed(call, signer.clone(), &signer, T::WeightInfo::call(), len)
+		}
+
+		/// Dispatch `call` on behalf of `signer`, who signed it as EIP-712 typed data.
+		///
+		/// Same as [`Pallet::call`], with the signed payload described in [`eip712`].
+		#[pallet::call_index(1)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(T::WeightInfo::typed_call().saturating_add(dispatch_info.weight), dispatch_info.class)
+		})]
+		pub fn typed_call(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+			signer: T::AccountId,
+			signature: Vec<u8>,
+			#[pallet::compact] nonce: T::Nonce,
+			valid_until: BlockNumberFor<T>,
 		) -> DispatchResultWithPostInfo {
 			ensure_none(origin)?;
 
@@ -127,86 +183,412 @@ pub mod pallet {
 				nonce == frame_system::Pallet::<T>::account_nonce(signer.clone()),
 				Error::<T>::BadNonce,
 			);
+			ensure!(valid_until >= frame_system::Pallet::<T>::block_number(), Error::<T>::Expired);
 
-			let signature = <T as Config>::Signature::try_from(signature)
-				.map_err(|_| Error::<T>::DecodeFailure)?;
+			let len = Self::fee_len(&call, &[&signature]);
+			let signature: [u8; 65] =
+				signature.as_slice().try_into().map_err(|_| Error::<T>::DecodeFailure)?;
 
 			// Ensure that transaction signature is valid
 			ensure!(
-				Self::valid_signature(&call, &signer, &signature, &nonce),
+				Self::valid_typed_signature(&call, &signer, &signature, &nonce, &valid_until),
 				Error::<T>::InvalidSignature
 			);
 
+			Self::dispatch_signed(call, signer.clone(), &signer, T::WeightInfo::typed_call(), len)
+		}
+
+		/// Dispatch `call` on behalf of `signer` with the call fee paid by `sponsor`.
+		///
+		/// `signature` is the signer's signature of the same payload as for [`Pallet::call`],
+		/// `sponsor_signature` is the sponsor's approval of that call, see
+		/// [`Pallet::valid_sponsor_signature`].
+		#[pallet::call_index(2)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(
+				T::WeightInfo::call_sponsored().saturating_add(dispatch_info.weight),
+				dispatch_info.class,
+			)
+		})]
+		#[allow(clippy::too_many_arguments)]
+		pub fn call_sponsored(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+			signer: T::AccountId,
+			signature: Vec<u8>,
+			#[pallet::compact] nonce: T::Nonce,
+			valid_until: BlockNumberFor<T>,
+			sponsor: T::AccountId,
+			sponsor_signature: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			let len = Self::fee_len(&call, &[&signature, &sponsor_signature]);
+			Self::ensure_signed_call(&call, &signer, signature, &nonce, &valid_until)?;
+
+			let sponsor_signature = <T as Config>::Signature::try_from(sponsor_signature)
+				.map_err(|_| Error::<T>::DecodeFailure)?;
+
+			// Ensure that the sponsor approved this call
+			ensure!(
+				Self::valid_sponsor_signature(
+					&call,
+					&signer,
+					&nonce,
+					&valid_until,
+					&sponsor,
+					&sponsor_signature
+				),
+				Error::<T>::InvalidSponsorSignature
+			);
+
+			Self::dispatch_signed(call, signer, &sponsor, T::WeightInfo::call_sponsored(), len)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Length charged for `call`: the wrapped call and the signatures over it.
+		fn fee_len(call: &<T as Config>::RuntimeCall, signatures: &[&[u8]]) -> u32 {
+			signatures
+				.iter()
+				.fold(call.encoded_size(), |len, signature| len.saturating_add(signature.len()))
+				.saturated_into()
+		}
+
+		/// The fee for a wrapped call of `weight` and `len`, computed as `pallet_transaction_payment`
+		/// would for a signed extrinsic: base fee, length fee and the weight fee adjusted by the
+		/// current fee multiplier.
+		pub fn call_fee(len: u32, weight: Weight, class: DispatchClass) -> BalanceOf<T> {
+			let weight_to_fee = |weight: &Weight| -> u128 {
+				<T as pallet_transaction_payment::Config>::WeightToFee::weight_to_fee(weight)
+					.saturated_into()
+			};
+			let base_fee = weight_to_fee(&T::BlockWeights::get().get(class).base_extrinsic);
+			let len_fee = <T as pallet_transaction_payment::Config>::LengthToFee::weight_to_fee(
+				&Weight::from_parts(len.into(), 0),
+			)
+			.saturated_into::<u128>();
+			let adjusted_weight_fee =
+				pallet_transaction_payment::Pallet::<T>::next_fee_multiplier()
+					.saturating_mul_int(weight_to_fee(&weight));
+
+			base_fee
+				.saturating_add(len_fee)
+				.saturating_add(adjusted_weight_fee)
+				.saturated_into()
+		}
+
+		/// Charge the call fee from `fee_payer` and dispatch `call` with `signer` as origin.
+		///
+		/// The fee for the unused weight of `call` is refunded to `fee_payer` after dispatch.
+		fn dispatch_signed(
+			call: Box<<T as Config>::RuntimeCall>,
+			signer: T::AccountId,
+			fee_payer: &T::AccountId,
+			overhead: Weight,
+			len: u32,
+		) -> DispatchResultWithPostInfo {
 			// Increment account nonce
 			frame_system::Pallet::<T>::inc_account_nonce(signer.clone());
 
-			// Processing fee
-			let tx_fee = T::Currency::withdraw(
-				&signer,
-				T::CallFee::get(),
+			// Processing fee, for the whole weight of `call`
+			let info = call.get_dispatch_info();
+			let paid = T::Currency::withdraw(
+				fee_payer,
+				Self::call_fee(len, overhead.saturating_add(info.weight), info.class),
 				WithdrawReasons::FEE,
 				ExistenceRequirement::AllowDeath,
 			)?;
-			T::OnChargeTransaction::on_unbalanced(tx_fee);
 
 			// Dispatch call
 			let new_origin = frame_system::RawOrigin::Signed(signer.clone()).into();
-			let res = call.dispatch(new_origin).map(|_| ());
-			Self::deposit_event(Event::Executed(signer, res.map_err(|e| e.error)));
+			let (res, post_info) = match call.dispatch(new_origin) {
+				Ok(post_info) => (Ok(()), post_info),
+				Err(e) => (Err(e.error), e.post_info),
+			};
+			let actual_weight = overhead.saturating_add(post_info.calc_actual_weight(&info));
+
+			// Refund the fee for unused weight, the rest goes to the fee destination
+			let (fee, refund) = paid.split(Self::call_fee(len, actual_weight, info.class));
+			T::Currency::resolve_creating(fee_payer, refund);
+			<T as Config>::OnChargeTransaction::on_unbalanceds(Some(fee).into_iter());
+
+			Self::deposit_event(Event::Executed(signer, res));
 
 			// Fee already charged
-			Ok(Pays::No.into())
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::No })
+		}
+
+		/// Check nonce, expiry and signature of a [`Pallet::call`] payload.
+		fn ensure_signed_call(
+			call: &Box<<T as Config>::RuntimeCall>,
+			signer: &T::AccountId,
+			signature: Vec<u8>,
+			nonce: &T::Nonce,
+			valid_until: &BlockNumberFor<T>,
+		) -> DispatchResult {
+			// Ensure that transaction isn't stale
+			ensure!(
+				*nonce == frame_system::Pallet::<T>::account_nonce(signer.clone()),
+				Error::<T>::BadNonce,
+			);
+			ensure!(*valid_until >= frame_system::Pallet::<T>::block_number(), Error::<T>::Expired);
+
+			let signature = <T as Config>::Signature::try_from(signature)
+				.map_err(|_| Error::<T>::DecodeFailure)?;
+
+			// Ensure that transaction signature is valid
+			ensure!(
+				Self::valid_signature(call, signer, &signature, nonce, valid_until),
+				Error::<T>::InvalidSignature
+			);
+
+			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
 		/// Verify custom signature and returns `true` if correct.
 		pub fn valid_signature(
 			call: &Box<<T as Config>::RuntimeCall>,
 			signer: &T::AccountId,
 			signature: &T::Signature,
 			nonce: &T::Nonce,
+			valid_until: &BlockNumberFor<T>,
 		) -> bool {
-			let payload = (T::CallMagicNumber::get(), *nonce, call.clone());
+			let payload = (T::CallMagicNumber::get(), *nonce, *valid_until, call.clone());
 			signature.verify(&payload.encode()[..], signer)
 		}
-	}
 
-	pub(crate) const SIGNATURE_DECODE_FAILURE: u8 = 1;
+		/// Verify the sponsor approval of a [`Pallet::call_sponsored`] and returns `true` if
+		/// correct.
+		///
+		/// The sponsor signs the same fields as the signer, prefixed with [`SPONSOR_PREFIX`] and
+		/// the signer account.
+		pub fn valid_sponsor_signature(
+			call: &Box<<T as Config>::RuntimeCall>,
+			signer: &T::AccountId,
+			nonce: &T::Nonce,
+			valid_until: &BlockNumberFor<T>,
+			sponsor: &T::AccountId,
+			sponsor_signature: &T::Signature,
+		) -> bool {
+			let payload = (
+				T::CallMagicNumber::get(),
+				SPONSOR_PREFIX,
+				signer,
+				*nonce,
+				*valid_until,
+				call.clone(),
+			);
+			sponsor_signature.verify(&payload.encode()[..], sponsor)
+		}
 
-	#[pallet::validate_unsigned]
-	impl<T: Config> frame_support::unsigned::ValidateUnsigned for Pallet<T> {
-		type Call = Call<T>;
+		/// Transaction longevity for a call signed up to `valid_until`, or `Stale` if it already
+		/// expired.
+		fn longevity(valid_until: &BlockNumberFor<T>) -> Result<u64, TransactionValidityError> {
+			let now = frame_system::Pallet::<T>::block_number();
+			if *valid_until < now {
+				return Err(InvalidTransaction::Stale.into())
+			}
 
-		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-			// Call decomposition (we have only one possible value here)
-			let (call, signer, signature, nonce) = match call {
-				Call::call { call, signer, signature, nonce } => (call, signer, signature, nonce),
-				_ => return InvalidTransaction::Call.into(),
-			};
+			// The pool drops the transaction once it can no longer be included.
+			Ok((*valid_until - now).saturated_into::<u64>().saturating_add(1))
+		}
 
+		/// Check that `fee_payer` can afford the fee of `call`.
+		fn validate_fee(
+			call: &<T as Config>::RuntimeCall,
+			fee_payer: &T::AccountId,
+			overhead: Weight,
+			len: u32,
+		) -> Result<(), TransactionValidityError> {
+			let info = call.get_dispatch_info();
+			let fee: u128 = Self::call_fee(len, overhead.saturating_add(info.weight), info.class)
+				.saturated_into();
+			// Locked and held funds cannot pay the fee.
+			let usable: u128 = <T::Currency as fungible::Inspect<T::AccountId>>::reducible_balance(
+				fee_payer,
+				Preservation::Expendable,
+				Fortitude::Polite,
+			)
+			.saturated_into();
+			if usable < fee {
+				return Err(InvalidTransaction::Payment.into())
+			}
+			Ok(())
+		}
+
+		fn validate_call(
+			call: &Box<<T as Config>::RuntimeCall>,
+			signer: &T::AccountId,
+			signature: &[u8],
+			nonce: &T::Nonce,
+			valid_until: &BlockNumberFor<T>,
+			sponsor: Option<(&T::AccountId, &[u8])>,
+		) -> TransactionValidity {
 			// Check that tx isn't stale
 			if *nonce != frame_system::Pallet::<T>::account_nonce(signer.clone()) {
 				return InvalidTransaction::Stale.into()
 			}
+			let longevity = Self::longevity(valid_until)?;
+
+			// Check that the fee can be paid
+			match sponsor {
+				Some((sponsor, sponsor_signature)) => Self::validate_fee(
+					call,
+					sponsor,
+					T::WeightInfo::call_sponsored(),
+					Self::fee_len(call, &[signature, sponsor_signature]),
+				)?,
+				None => Self::validate_fee(
+					call,
+					signer,
+					T::WeightInfo::call(),
+					Self::fee_len(call, &[signature]),
+				)?,
+			}
 
 			// Check signature encoding
-			if let Ok(signature) = <T as Config>::Signature::try_from(signature.clone()) {
-				// Verify signature
-				if Self::valid_signature(call, signer, &signature, nonce) {
-					ValidTransaction::with_tag_prefix("CustomSignatures")
-						.priority(T::UnsignedPriority::get())
-						.and_provides((call, signer, nonce))
-						.longevity(64_u64)
-						.propagate(true)
-						.build()
-				} else {
-					// Signature mismatched to given signer
-					InvalidTransaction::BadProof.into()
+			let Ok(signature) = <T as Config>::Signature::try_from(signature.to_vec()) else {
+				return InvalidTransaction::Custom(SIGNATURE_DECODE_FAILURE).into()
+			};
+			if !Self::valid_signature(call, signer, &signature, nonce, valid_until) {
+				// Signature mismatched to given signer
+				return InvalidTransaction::BadProof.into()
+			}
+
+			if let Some((sponsor, sponsor_signature)) = sponsor {
+				let Ok(sponsor_signature) =
+					<T as Config>::Signature::try_from(sponsor_signature.to_vec())
+				else {
+					return InvalidTransaction::Custom(SIGNATURE_DECODE_FAILURE).into()
+				};
+				if !Self::valid_sponsor_signature(
+					call,
+					signer,
+					nonce,
+					valid_until,
+					sponsor,
+					&sponsor_signature,
+				) {
+					// Approval mismatched to given sponsor
+					return InvalidTransaction::BadProof.into()
 				}
-			} else {
-				// Signature encoding broken
-				InvalidTransaction::Custom(SIGNATURE_DECODE_FAILURE).into()
+			}
+
+			// Sponsored or not, the signer's call may only be included once
+			ValidTransaction::with_tag_prefix("CustomSignatures")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((call, signer, nonce))
+				.longevity(longevity)
+				.propagate(true)
+				.build()
+		}
+
+		/// The EIP-712 digest signed for a [`Pallet::typed_call`].
+		pub fn typed_call_digest(
+			call: &<T as Config>::RuntimeCall,
+			nonce: &T::Nonce,
+			valid_until: &BlockNumberFor<T>,
+		) -> [u8; 32] {
+			let CallMetadata { pallet_name, function_name } = call.get_call_metadata();
+			let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+			let salt = <[u8; 32]>::try_from(genesis_hash.as_ref()).unwrap_or_default();
+
+			eip712::digest(
+				eip712::domain_separator(T::ChainId::get(), salt),
+				eip712::call_hash(
+					pallet_name,
+					function_name,
+					&call.encode(),
+					(*nonce).unique_saturated_into(),
+					(*valid_until).unique_saturated_into(),
+				),
+			)
+		}
+
+		/// Verify EIP-712 typed data signature and returns `true` if correct.
+		pub fn valid_typed_signature(
+			call: &<T as Config>::RuntimeCall,
+			signer: &T::AccountId,
+			signature: &[u8; 65],
+			nonce: &T::Nonce,
+			valid_until: &BlockNumberFor<T>,
+		) -> bool {
+			let digest = Self::typed_call_digest(call, nonce, valid_until);
+			eip712::recover(signature, &digest)
+				.map_or(false, |public| T::Signer::from(public).into_account() == *signer)
+		}
+
+		fn validate_typed_call(
+			call: &<T as Config>::RuntimeCall,
+			signer: &T::AccountId,
+			signature: &[u8],
+			nonce: &T::Nonce,
+			valid_until: &BlockNumberFor<T>,
+		) -> TransactionValidity {
+			// Check that tx isn't stale
+			if *nonce != frame_system::Pallet::<T>::account_nonce(signer.clone()) {
+				return InvalidTransaction::Stale.into()
+			}
+			let longevity = Self::longevity(valid_until)?;
+			Self::validate_fee(
+				call,
+				signer,
+				T::WeightInfo::typed_call(),
+				Self::fee_len(call, &[signature]),
+			)?;
+
+			// Check signature encoding
+			let Ok(signature) = <[u8; 65]>::try_from(signature) else {
+				return InvalidTransaction::Custom(SIGNATURE_DECODE_FAILURE).into()
+			};
+			if !Self::valid_typed_signature(call, signer, &signature, nonce, valid_until) {
+				// Signature mismatched to given signer
+				return InvalidTransaction::BadProof.into()
+			}
+
+			ValidTransaction::with_tag_prefix("CustomSignaturesEip712")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((call, signer, nonce))
+				.longevity(longevity)
+				.propagate(true)
+				.build()
+		}
+	}
+
+	pub(crate) const SIGNATURE_DECODE_FAILURE: u8 = 1;
+
+	/// Prefix of the payload a sponsor signs to approve a [`Pallet::call_sponsored`].
+	pub const SPONSOR_PREFIX: [u8; 7] = *b"sponsor";
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> frame_support::unsigned::ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			match call {
+				Call::call { call, signer, signature, nonce, valid_until } =>
+					Self::validate_call(call, signer, signature, nonce, valid_until, None),
+				Call::call_sponsored {
+					call,
+					signer,
+					signature,
+					nonce,
+					valid_until,
+					sponsor,
+					sponsor_signature,
+				} => Self::validate_call(
+					call,
+					signer,
+					signature,
+					nonce,
+					valid_until,
+					Some((sponsor, sponsor_signature.as_slice())),
+				),
+				Call::typed_call { call, signer, signature, nonce, valid_until } =>
+					Self::validate_typed_call(call, signer, signature, nonce, valid_until),
+				_ => InvalidTransaction::Call.into(),
 			}
 		}
 	}
@@ -16,7 +16,9 @@ use crate as custom_signatures;
 use assert_matches::assert_matches;
 use custom_signatures::*;
 use frame_support::{
-    traits::{ConstU32, Contains},
+    dispatch::GetDispatchInfo,
+    traits::{ConstU32, ConstU64, ConstU8, Contains, Currency, OnUnbalanced},
+    weights::{IdentityFee, Weight},
     {assert_err, assert_ok, parameter_types},
 };
 use hex_literal::hex;
@@ -24,28 +26,35 @@ use parity_scale_codec::Encode;
 use sp_core::{ecdsa, Pair};
 use sp_io::{hashing::keccak_256, TestExternalities};
 use sp_keyring::AccountKeyring as Keyring;
+use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
 use sp_runtime::{
-    testing::{H256, Header},
+    testing::H256,
     traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
-    transaction_validity::TransactionPriority,
-    MultiSignature, MultiSigner,
+    transaction_validity::{
+        InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidityError,
+        ValidTransaction,
+    },
+    BuildStorage, MultiSignature, MultiSigner,
 };
 
 pub const ECDSA_SEED: [u8; 32] =
     hex_literal::hex!["7e9c7ad85df5cdc88659f53e06fb2eb9bab3ebc59083a3190eaf2c730332529c"];
 
+pub const SPONSOR_SEED: [u8; 32] =
+    hex_literal::hex!["ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"];
+
 type Balance = u128;
-type BlockNumber = u64;
 type Signature = MultiSignature;
 type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
 type Block = frame_system::mocking::MockBlock<Runtime>;
-type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 
 frame_support::construct_runtime! {
 	pub enum Runtime
 	{
         Balances: pallet_balances,
         System: frame_system,
+        TransactionPayment: pallet_transaction_payment,
+        Utility: pallet_utility,
         CustomSignatures: custom_signatures,
     }
 }
@@ -71,7 +80,7 @@ impl frame_system::Config for Runtime {
     type RuntimeOrigin = RuntimeOrigin;
     type BaseCallFilter = NoRemarkFilter;
     type Nonce = u32;
-    type Block = BlockNumber;
+    type Block = Block;
     type RuntimeCall = RuntimeCall;
     type Hash = H256;
     type Hashing = BlakeTwo256;
@@ -108,14 +117,42 @@ impl pallet_balances::Config for Runtime {
     type MaxReserves = ();
     type ReserveIdentifier = ();
     type FreezeIdentifier = ();
+    type RuntimeHoldReason = ();
     type MaxHolds = ConstU32<0>;
     type MaxFreezes = ConstU32<0>;
 }
 
+impl pallet_transaction_payment::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>;
+    type OperationalFeeMultiplier = ConstU8<5>;
+    type WeightToFee = IdentityFee<Balance>;
+    type LengthToFee = IdentityFee<Balance>;
+    type FeeMultiplierUpdate = ();
+}
+
+impl pallet_utility::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
+    type PalletsOrigin = OriginCaller;
+    type WeightInfo = ();
+}
+
+/// Collects the call fees
+fn fee_collector() -> AccountId {
+    Keyring::Ferdie.into()
+}
+
+pub struct ToFeeCollector;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Runtime>> for ToFeeCollector {
+    fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Runtime>) {
+        Balances::resolve_creating(&fee_collector(), amount);
+    }
+}
+
 const MAGIC_NUMBER: u16 = 0xff50;
 parameter_types! {
     pub const Priority: TransactionPriority = TransactionPriority::MAX;
-    pub const CallFee: Balance = 42;
     pub const CallMagicNumber: u16 = MAGIC_NUMBER;
 }
 
@@ -125,29 +162,42 @@ impl Config for Runtime {
     type Signature = ethereum::EthereumSignature;
     type Signer = <Signature as Verify>::Signer;
     type CallMagicNumber = CallMagicNumber;
+    type ChainId = ConstU64<1234>;
     type Currency = Balances;
-    type CallFee = CallFee;
-    type OnChargeTransaction = ();
+    type OnChargeTransaction = ToFeeCollector;
     type UnsignedPriority = Priority;
+    type WeightInfo = ();
 }
 
-fn new_test_ext() -> TestExternalities {
-    let mut storage = frame_system::GenesisConfig::default()
-        .build_storage::<Runtime>()
+const INITIAL_BALANCE: Balance = 1_000_000_000_000_000;
+
+pub fn new_test_ext() -> TestExternalities {
+    let mut storage = frame_system::GenesisConfig::<Runtime>::default()
+        .build_storage()
         .unwrap();
 
     let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
     let account = MultiSigner::from(pair.public()).into_account();
+    let sponsor = MultiSigner::from(ecdsa::Pair::from_seed(&SPONSOR_SEED).public()).into_account();
     let _ = pallet_balances::GenesisConfig::<Runtime> {
-        balances: vec![(account, 1_000_000_000)],
+        balances: vec![(account, INITIAL_BALANCE), (sponsor, INITIAL_BALANCE)],
     }
     .assimilate_storage(&mut storage);
 
     let mut ext = TestExternalities::from(storage);
+    ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
     ext.execute_with(|| System::set_block_number(1));
     ext
 }
 
+/// Fee of `call` wrapped with `overhead` and signatures of `signatures_len` bytes, when all of
+/// its weight is used
+fn full_fee(call: &RuntimeCall, overhead: Weight, signatures_len: usize) -> Balance {
+    let info = call.get_dispatch_info();
+    let len = (call.encoded_size() + signatures_len) as u32;
+    CustomSignatures::call_fee(len, overhead + info.weight, info.class)
+}
+
 /// Simple `eth_sign` implementation, should be equal to exported by RPC
 fn eth_sign(seed: &[u8; 32], data: &[u8]) -> Vec<u8> {
     let call_msg = ethereum::signable_message(data);
@@ -173,7 +223,7 @@ fn eth_sign_works() {
 fn invalid_signature() {
     let bob: <Runtime as frame_system::Config>::AccountId = Keyring::Bob.into();
     let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
-    let call = pallet_balances::Call::<Runtime>::transfer {
+    let call = pallet_balances::Call::<Runtime>::transfer_allow_death {
         dest: alice.clone(),
         value: 1_000,
     }
@@ -181,7 +231,7 @@ fn invalid_signature() {
     let signature = Vec::from(&hex!["dd0992d40e5cdf99db76bed162808508ac65acd7ae2fdc8573594f03ed9c939773e813181788fc02c3c68f3fdc592759b35f6354484343e18cb5317d34dab6c61b"][..]);
     new_test_ext().execute_with(|| {
         assert_err!(
-            CustomSignatures::call(RuntimeOrigin::none(), Box::new(call), bob, signature, 0),
+            CustomSignatures::call(RuntimeOrigin::none(), Box::new(call), bob, signature, 0, 10),
             Error::<Runtime>::InvalidSignature,
         );
     });
@@ -196,12 +246,12 @@ fn balance_transfer() {
         let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
         assert_eq!(System::account(alice.clone()).data.free, 0);
 
-        let call: RuntimeCall = pallet_balances::Call::<Runtime>::transfer {
+        let call: RuntimeCall = pallet_balances::Call::<Runtime>::transfer_allow_death {
             dest: alice.clone(),
             value: 1_000,
         }
         .into();
-        let payload = (MAGIC_NUMBER, 0u32, call.clone());
+        let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
         let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref()).into();
 
         assert_eq!(System::account(account.clone()).nonce, 0);
@@ -211,10 +261,16 @@ fn balance_transfer() {
             account.clone(),
             signature,
             0,
+            10,
         ));
+        let fee = full_fee(&call, <() as WeightInfo>::call(), 65);
         assert_eq!(System::account(alice.clone()).data.free, 1_000);
         assert_eq!(System::account(account.clone()).nonce, 1);
-        assert_eq!(System::account(account.clone()).data.free, 999_998_958);
+        assert_eq!(
+            System::account(account.clone()).data.free,
+            INITIAL_BALANCE - 1_000 - fee
+        );
+        assert_eq!(System::account(fee_collector()).data.free, fee);
         assert_matches!(
             System::events()
                 .last()
@@ -233,11 +289,12 @@ fn balance_transfer() {
                 account.clone(),
                 signature,
                 0,
+                10,
             ),
             Error::<Runtime>::BadNonce,
         );
 
-        let payload = (MAGIC_NUMBER, 1u32, call.clone());
+        let payload = (MAGIC_NUMBER, 1u32, 10u64, call.clone());
         let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref()).into();
         assert_eq!(System::account(account.clone()).nonce, 1);
         assert_ok!(CustomSignatures::call(
@@ -246,10 +303,14 @@ fn balance_transfer() {
             account.clone(),
             signature,
             1,
+            10,
         ));
         assert_eq!(System::account(alice).data.free, 2_000);
         assert_eq!(System::account(account.clone()).nonce, 2);
-        assert_eq!(System::account(account.clone()).data.free, 999_997_916);
+        assert_eq!(
+            System::account(account.clone()).data.free,
+            INITIAL_BALANCE - 2_000 - 2 * fee
+        );
     })
 }
 
@@ -275,15 +336,13 @@ fn call_fixtures() {
         hex!["0000c4305fb88b6ccb43d6552dc11d18e7b0ee3185247adcc6e885eb284adf6c563da10f"],
     );
 
-    let payload = (MAGIC_NUMBER, 0u32, call.clone());
+    let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
     assert_eq!(
         payload.encode(),
-        hex![
-            "50ff000000000000c4305fb88b6ccb43d6552dc11d18e7b0ee3185247adcc6e885eb284adf6c563da10f"
-        ],
+        hex!["50ff000000000a000000000000000000c4305fb88b6ccb43d6552dc11d18e7b0ee3185247adcc6e885eb284adf6c563da10f"],
     );
 
-    let signature = hex!["6ecb474240df46ee5cde8f51cf5ccf4c75d15ac3c1772aea6c8189604263c98b16350883438c4eaa447ebcb6889d516f70351fd704bb3521072cd2fccc7c99dc1c"];
+    let signature = hex!["5bcc4c76052a9c5bc98c6768c62e9cde8c8f82ff614092f18e89ecc63f91969762007476222bf0cdbee1597b605f181ce0e9307df14ab55d4ae5152ee55eb4c81c"];
     assert_eq!(eth_sign(&seed, payload.encode().as_ref()), signature)
 }
 
@@ -303,7 +362,7 @@ fn not_allowed_call_filtered() {
         // sanity check, call should be filtered out
         assert!(!<Runtime as frame_system::Config>::BaseCallFilter::contains(&call));
 
-        let payload = (MAGIC_NUMBER, 0u32, call.clone());
+        let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
         let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref()).into();
 
         assert_eq!(System::account(account.clone()).nonce, 0);
@@ -313,6 +372,7 @@ fn not_allowed_call_filtered() {
             account.clone(),
             signature,
             0,
+            10,
         ));
         assert_eq!(System::account(account.clone()).nonce, 1);
 
@@ -327,3 +387,562 @@ fn not_allowed_call_filtered() {
         );
     })
 }
+
+#[test]
+fn call_expired() {
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+
+        let call = transfer_to_alice();
+        let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+
+        // Expiry is part of the signed payload
+        assert_err!(
+            CustomSignatures::call(
+                RuntimeOrigin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                signature.clone(),
+                0,
+                20,
+            ),
+            Error::<Runtime>::InvalidSignature,
+        );
+
+        System::set_block_number(11);
+        assert_err!(
+            CustomSignatures::call(
+                RuntimeOrigin::none(),
+                Box::new(call),
+                account,
+                signature,
+                0,
+                10,
+            ),
+            Error::<Runtime>::Expired,
+        );
+    })
+}
+
+/// Sponsor approval of `call` signed by `signer`
+fn sponsor_sign(call: &RuntimeCall, signer: &AccountId, nonce: u32, valid_until: u64) -> Vec<u8> {
+    let payload = (
+        MAGIC_NUMBER,
+        SPONSOR_PREFIX,
+        signer,
+        nonce,
+        valid_until,
+        call,
+    );
+    eth_sign(&SPONSOR_SEED, payload.encode().as_ref())
+}
+
+#[test]
+fn call_sponsored_works() {
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let sponsor =
+            MultiSigner::from(ecdsa::Pair::from_seed(&SPONSOR_SEED).public()).into_account();
+        let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
+
+        let call = transfer_to_alice();
+        let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        let sponsor_signature = sponsor_sign(&call, &account, 0, 10);
+
+        assert_ok!(CustomSignatures::call_sponsored(
+            RuntimeOrigin::none(),
+            Box::new(call.clone()),
+            account.clone(),
+            signature.clone(),
+            0,
+            10,
+            sponsor.clone(),
+            sponsor_signature.clone(),
+        ));
+        assert_eq!(System::account(alice).data.free, 1_000);
+        // Signer pays only for the transfer, the sponsor pays the call fee
+        let fee = full_fee(&call, <() as WeightInfo>::call_sponsored(), 2 * 65);
+        assert_eq!(System::account(account.clone()).nonce, 1);
+        assert_eq!(
+            System::account(account.clone()).data.free,
+            INITIAL_BALANCE - 1_000
+        );
+        assert_eq!(System::account(sponsor.clone()).nonce, 0);
+        assert_eq!(
+            System::account(sponsor.clone()).data.free,
+            INITIAL_BALANCE - fee
+        );
+        assert_matches!(
+            System::events()
+                .last()
+                .expect("events expected")
+                .event
+                .clone(),
+            RuntimeEvent::CustomSignatures(Event::Executed(used_account, Ok(..),))
+            if used_account == account
+        );
+
+        // Replay
+        assert_err!(
+            CustomSignatures::call_sponsored(
+                RuntimeOrigin::none(),
+                Box::new(call),
+                account,
+                signature,
+                0,
+                10,
+                sponsor,
+                sponsor_signature,
+            ),
+            Error::<Runtime>::BadNonce,
+        );
+    })
+}
+
+#[test]
+fn call_sponsored_invalid_sponsor_signature() {
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let sponsor =
+            MultiSigner::from(ecdsa::Pair::from_seed(&SPONSOR_SEED).public()).into_account();
+
+        let call = transfer_to_alice();
+        let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+
+        // The signer's own signature is not an approval
+        assert_err!(
+            CustomSignatures::call_sponsored(
+                RuntimeOrigin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                signature.clone(),
+                0,
+                10,
+                sponsor.clone(),
+                signature.clone(),
+            ),
+            Error::<Runtime>::InvalidSponsorSignature,
+        );
+
+        // An approval of another call
+        let other: RuntimeCall = frame_system::Call::<Runtime>::remark {
+            remark: Vec::<_>::new(),
+        }
+        .into();
+        assert_err!(
+            CustomSignatures::call_sponsored(
+                RuntimeOrigin::none(),
+                Box::new(call),
+                account.clone(),
+                signature,
+                0,
+                10,
+                sponsor.clone(),
+                sponsor_sign(&other, &account, 0, 10),
+            ),
+            Error::<Runtime>::InvalidSponsorSignature,
+        );
+        assert_eq!(System::account(account).nonce, 0);
+        assert_eq!(System::account(sponsor).data.free, INITIAL_BALANCE);
+    })
+}
+
+#[test]
+fn validate_unsigned_longevity() {
+    use frame_support::unsigned::ValidateUnsigned;
+
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let sponsor =
+            MultiSigner::from(ecdsa::Pair::from_seed(&SPONSOR_SEED).public()).into_account();
+        let call = transfer_to_alice();
+
+        let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        let unsponsored = custom_signatures::Call::<Runtime>::call {
+            call: Box::new(call.clone()),
+            signer: account.clone(),
+            signature: signature.clone(),
+            nonce: 0,
+            valid_until: 10,
+        };
+        let sponsored = custom_signatures::Call::<Runtime>::call_sponsored {
+            call: Box::new(call.clone()),
+            signer: account.clone(),
+            signature,
+            nonce: 0,
+            valid_until: 10,
+            sponsor,
+            sponsor_signature: sponsor_sign(&call, &account, 0, 10),
+        };
+
+        let unsponsored_tx: ValidTransaction =
+            CustomSignatures::validate_unsigned(TransactionSource::External, &unsponsored)
+                .expect("valid call");
+        let sponsored_tx: ValidTransaction =
+            CustomSignatures::validate_unsigned(TransactionSource::External, &sponsored)
+                .expect("valid sponsored call");
+
+        // Valid from block 1 up to and including block 10
+        assert_eq!(unsponsored_tx.longevity, 10);
+        assert_eq!(sponsored_tx.longevity, 10);
+        // Both spend the same signed call
+        assert_eq!(unsponsored_tx.provides, sponsored_tx.provides);
+
+        System::set_block_number(11);
+        assert_matches!(
+            CustomSignatures::validate_unsigned(TransactionSource::External, &unsponsored),
+            Err(TransactionValidityError::Invalid(InvalidTransaction::Stale))
+        );
+        assert_matches!(
+            CustomSignatures::validate_unsigned(TransactionSource::External, &sponsored),
+            Err(TransactionValidityError::Invalid(InvalidTransaction::Stale))
+        );
+    })
+}
+
+#[test]
+fn fee_depends_on_call_weight() {
+    new_test_ext().execute_with(|| {
+        let transfer = transfer_to_alice();
+        let batch: RuntimeCall = pallet_utility::Call::<Runtime>::batch {
+            calls: vec![transfer.clone(); 10],
+        }
+        .into();
+        let overhead = <() as WeightInfo>::call();
+        // One weight unit costs one balance unit in the mock
+        let transfer_weight = Balance::from(transfer.get_dispatch_info().weight.ref_time());
+        assert!(
+            full_fee(&batch, overhead, 65) - full_fee(&transfer, overhead, 65)
+                >= 9 * transfer_weight
+        );
+    })
+}
+
+#[test]
+fn unused_weight_refunded() {
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+
+        // The filtered remark interrupts the batch before the transfer
+        let remark: RuntimeCall = frame_system::Call::<Runtime>::remark {
+            remark: Vec::<_>::new(),
+        }
+        .into();
+        let call: RuntimeCall = pallet_utility::Call::<Runtime>::batch {
+            calls: vec![remark, transfer_to_alice()],
+        }
+        .into();
+        let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+
+        let post_info = CustomSignatures::call(
+            RuntimeOrigin::none(),
+            Box::new(call.clone()),
+            account.clone(),
+            signature,
+            0,
+            10,
+        )
+        .expect("call dispatched");
+
+        let info = call.get_dispatch_info();
+        let overhead = <() as WeightInfo>::call();
+        let actual_weight = post_info.actual_weight.expect("actual weight reported");
+        assert!(actual_weight.ref_time() < (overhead + info.weight).ref_time());
+
+        let len = (call.encoded_size() + 65) as u32;
+        let fee = CustomSignatures::call_fee(len, actual_weight, info.class);
+        assert!(fee < full_fee(&call, overhead, 65));
+        assert_eq!(System::account(account).data.free, INITIAL_BALANCE - fee);
+        assert_eq!(System::account(fee_collector()).data.free, fee);
+    })
+}
+
+#[test]
+fn validate_unsigned_checks_fee_payer_balance() {
+    use frame_support::unsigned::ValidateUnsigned;
+
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let sponsor =
+            MultiSigner::from(ecdsa::Pair::from_seed(&SPONSOR_SEED).public()).into_account();
+        let call = transfer_to_alice();
+
+        let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        let unsponsored = custom_signatures::Call::<Runtime>::call {
+            call: Box::new(call.clone()),
+            signer: account.clone(),
+            signature: signature.clone(),
+            nonce: 0,
+            valid_until: 10,
+        };
+        let sponsored = custom_signatures::Call::<Runtime>::call_sponsored {
+            call: Box::new(call.clone()),
+            signer: account.clone(),
+            signature,
+            nonce: 0,
+            valid_until: 10,
+            sponsor: sponsor.clone(),
+            sponsor_signature: sponsor_sign(&call, &account, 0, 10),
+        };
+
+        // The signer can no longer pay, the sponsor still can
+        Balances::make_free_balance_be(&account, 1);
+        assert_matches!(
+            CustomSignatures::validate_unsigned(TransactionSource::External, &unsponsored),
+            Err(TransactionValidityError::Invalid(
+                InvalidTransaction::Payment
+            ))
+        );
+        assert_ok!(CustomSignatures::validate_unsigned(
+            TransactionSource::External,
+            &sponsored
+        ));
+
+        Balances::make_free_balance_be(&sponsor, 1);
+        assert_matches!(
+            CustomSignatures::validate_unsigned(TransactionSource::External, &sponsored),
+            Err(TransactionValidityError::Invalid(
+                InvalidTransaction::Payment
+            ))
+        );
+    })
+}
+
+#[test]
+fn validate_unsigned_ignores_locked_balance() {
+    use frame_support::{
+        traits::{LockableCurrency, WithdrawReasons},
+        unsigned::ValidateUnsigned,
+    };
+
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let call = transfer_to_alice();
+
+        let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        let call = custom_signatures::Call::<Runtime>::call {
+            call: Box::new(call),
+            signer: account.clone(),
+            signature,
+            nonce: 0,
+            valid_until: 10,
+        };
+        assert_ok!(CustomSignatures::validate_unsigned(
+            TransactionSource::External,
+            &call
+        ));
+
+        // The whole balance is still free, but locked
+        Balances::set_lock(*b"testlock", &account, INITIAL_BALANCE, WithdrawReasons::all());
+        assert_eq!(Balances::free_balance(&account), INITIAL_BALANCE);
+        assert_matches!(
+            CustomSignatures::validate_unsigned(TransactionSource::External, &call),
+            Err(TransactionValidityError::Invalid(
+                InvalidTransaction::Payment
+            ))
+        );
+    })
+}
+
+/// Simple `eth_signTypedData_v4` implementation, signing the final EIP-712 digest
+fn eip712_sign(seed: &[u8; 32], digest: &[u8; 32]) -> Vec<u8> {
+    let ecdsa_msg = libsecp256k1::Message::parse(digest);
+    let secret = libsecp256k1::SecretKey::parse(&seed).expect("valid seed");
+    let (signature, recovery_id) = libsecp256k1::sign(&ecdsa_msg, &secret);
+    let mut out = Vec::new();
+    out.extend_from_slice(&signature.serialize()[..]);
+    out.push(recovery_id.serialize() + 27);
+    out
+}
+
+fn transfer_to_alice() -> RuntimeCall {
+    let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
+    pallet_balances::Call::<Runtime>::transfer_allow_death {
+        dest: alice,
+        value: 1_000,
+    }
+    .into()
+}
+
+#[test]
+fn typed_call_digest_fixture() {
+    new_test_ext().execute_with(|| {
+        let call = transfer_to_alice();
+        assert_eq!(
+            call.encode(),
+            hex!["0000d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27da10f"],
+        );
+        assert_eq!(
+            CustomSignatures::typed_call_digest(&call, &0, &10),
+            hex!["0b23dce55455ca89a7c547b211e6f4000abb69912595d5b676579f2a5abb1def"],
+        );
+    })
+}
+
+#[test]
+fn typed_call_works() {
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
+
+        let call = transfer_to_alice();
+        let digest = CustomSignatures::typed_call_digest(&call, &0, &10);
+        let signature = eip712_sign(&ECDSA_SEED, &digest);
+
+        assert_ok!(CustomSignatures::typed_call(
+            RuntimeOrigin::none(),
+            Box::new(call.clone()),
+            account.clone(),
+            signature.clone(),
+            0,
+            10,
+        ));
+        assert_eq!(System::account(alice).data.free, 1_000);
+        assert_eq!(System::account(account.clone()).nonce, 1);
+        let fee = full_fee(&call, <() as WeightInfo>::typed_call(), 65);
+        assert_eq!(
+            System::account(account.clone()).data.free,
+            INITIAL_BALANCE - 1_000 - fee
+        );
+        assert_matches!(
+            System::events()
+                .last()
+                .expect("events expected")
+                .event
+                .clone(),
+            RuntimeEvent::CustomSignatures(Event::Executed(used_account, Ok(..),))
+            if used_account == account
+        );
+
+        // Replay
+        assert_err!(
+            CustomSignatures::typed_call(
+                RuntimeOrigin::none(),
+                Box::new(call),
+                account,
+                signature,
+                0,
+                10,
+            ),
+            Error::<Runtime>::BadNonce,
+        );
+    })
+}
+
+#[test]
+fn typed_call_expired() {
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+
+        let call = transfer_to_alice();
+        let digest = CustomSignatures::typed_call_digest(&call, &0, &10);
+        let signature = eip712_sign(&ECDSA_SEED, &digest);
+
+        System::set_block_number(11);
+        assert_err!(
+            CustomSignatures::typed_call(
+                RuntimeOrigin::none(),
+                Box::new(call),
+                account,
+                signature,
+                0,
+                10,
+            ),
+            Error::<Runtime>::Expired,
+        );
+    })
+}
+
+#[test]
+fn typed_call_rejects_other_schemes() {
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+
+        let call = transfer_to_alice();
+        // A personal_sign signature of the same call
+        let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        assert_err!(
+            CustomSignatures::typed_call(
+                RuntimeOrigin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                signature,
+                0,
+                10,
+            ),
+            Error::<Runtime>::InvalidSignature,
+        );
+
+        // A typed data signature used with the personal_sign scheme
+        let digest = CustomSignatures::typed_call_digest(&call, &0, &10);
+        let signature = eip712_sign(&ECDSA_SEED, &digest);
+        assert_err!(
+            CustomSignatures::call(
+                RuntimeOrigin::none(),
+                Box::new(call),
+                account,
+                signature,
+                0,
+                10,
+            ),
+            Error::<Runtime>::InvalidSignature,
+        );
+    })
+}
+
+#[test]
+fn validate_unsigned_tags_are_distinct() {
+    use frame_support::unsigned::ValidateUnsigned;
+
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let call = transfer_to_alice();
+
+        let payload = (MAGIC_NUMBER, 0u32, 10u64, call.clone());
+        let personal_sign = custom_signatures::Call::<Runtime>::call {
+            call: Box::new(call.clone()),
+            signer: account.clone(),
+            signature: eth_sign(&ECDSA_SEED, payload.encode().as_ref()),
+            nonce: 0,
+            valid_until: 10,
+        };
+        let digest = CustomSignatures::typed_call_digest(&call, &0, &10);
+        let typed_data = custom_signatures::Call::<Runtime>::typed_call {
+            call: Box::new(call),
+            signer: account,
+            signature: eip712_sign(&ECDSA_SEED, &digest),
+            nonce: 0,
+            valid_until: 10,
+        };
+
+        let personal_sign: ValidTransaction =
+            CustomSignatures::validate_unsigned(TransactionSource::External, &personal_sign)
+                .expect("valid personal_sign call");
+        let typed_data: ValidTransaction =
+            CustomSignatures::validate_unsigned(TransactionSource::External, &typed_data)
+                .expect("valid typed data call");
+
+        assert_ne!(personal_sign.provides, typed_data.provides);
+        // Valid from block 1 up to and including block 10
+        assert_eq!(typed_data.longevity, 10);
+    })
+}
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for `pallet_custom_signatures`, the overhead of the wrapper extrinsics without the
+//! weight of the wrapped call.
+//!
+//! These are estimates, dominated by the ECDSA public key recovery of each signature, and were not
+//! produced by the benchmarking CLI. Replace them with its output on the reference machine:
+//!
+//! ```text
+//! ./target/production/gpu benchmark pallet --chain=gpu-dev --steps=50 --repeat=20 \
+//!     --pallet=pallet_custom_signatures --extrinsic=* --execution=wasm \
+//!     --wasm-execution=compiled --heap-pages=4096 \
+//!     --output=./pallets/custom-signatures/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_custom_signatures.
+pub trait WeightInfo {
+	fn call() -> Weight;
+	fn typed_call() -> Weight;
+	fn call_sponsored() -> Weight;
+}
+
+/// Estimated weights for pallet_custom_signatures.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn call() -> Weight {
+		Weight::from_parts(52_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn typed_call() -> Weight {
+		Weight::from_parts(56_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn call_sponsored() -> Weight {
+		Weight::from_parts(94_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn call() -> Weight {
+		Weight::from_parts(52_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn typed_call() -> Weight {
+		Weight::from_parts(56_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn call_sponsored() -> Weight {
+		Weight::from_parts(94_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -139,3 +139,135 @@
     function burn(address who, uint256 amount) external returns (bool);
 }
 
+
+    /**
+     * @title Extension for ERC20 interface
+     * @dev EIP-2612 permit, allowing approvals to be given through a signed message.
+     * The EIP-712 domain uses the asset name, version "1", the chain id and the asset address.
+     */
+    interface IERC20Permit is IERC20 {
+
+    /**
+     * @dev Sets `value` as the allowance of `spender` over `owner`'s tokens,
+     * given `owner`'s signed approval.
+     * Selector: d505accf
+     * @param owner address Owner of the tokens, signer of the permit.
+     * @param spender address Allowed spender.
+     * @param value uint256 Amount of tokens approved.
+     * @param deadline uint256 Timestamp, in seconds, after which the permit is invalid.
+     * @param v uint8 Recovery id of the signature.
+     * @param r bytes32 First half of the signature.
+     * @param s bytes32 Second half of the signature.
+     */
+    function permit(
+        address owner,
+        address spender,
+        uint256 value,
+        uint256 deadline,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external;
+
+    /**
+     * @dev Returns the current nonce of `owner`, to be included in its next permit.
+     * Selector: 7ecebe00
+     */
+    function nonces(address owner) external view returns (uint256);
+
+    /**
+     * @dev Returns the EIP-712 domain separator used in the permit signature.
+     * Selector: 3644e515
+     */
+    // solhint-disable-next-line func-name-mixedcase
+    function DOMAIN_SEPARATOR() external view returns (bytes32);
+}
+
+
+    /**
+     * @title Extension for ERC20 interface
+     * @dev Asset management, mirroring the privileged calls of pallet-assets.
+     * Each function is only usable by the matching role of the asset (owner, admin or freezer).
+     */
+    interface IERC20Management is IERC20 {
+
+    /**
+     * @dev Sets the metadata of the asset.
+     * Only usable by asset owner.
+     * Selector: 37d2c2f4
+     */
+    function setMetadata(string memory name, string memory symbol, uint8 decimals)
+        external
+        returns (bool);
+
+    /**
+     * @dev Clears the metadata of the asset.
+     * Only usable by asset owner.
+     * Selector: efb6d432
+     */
+    function clearMetadata() external returns (bool);
+
+    /**
+     * @dev Prevents `who` from transferring the asset.
+     * Only usable by asset freezer.
+     * Selector: 8d1fdf2f
+     */
+    function freeze(address who) external returns (bool);
+
+    /**
+     * @dev Allows a frozen `who` to transfer the asset again.
+     * Only usable by asset admin.
+     * Selector: 5ea20216
+     */
+    function thaw(address who) external returns (bool);
+
+    /**
+     * @dev Prevents all transfers of the asset.
+     * Only usable by asset freezer.
+     * Selector: d4937f51
+     */
+    function freezeAsset() external returns (bool);
+
+    /**
+     * @dev Allows transfers of a frozen asset again.
+     * Only usable by asset admin.
+     * Selector: 51ec2ad7
+     */
+    function thawAsset() external returns (bool);
+
+    /**
+     * @dev Hands the ownership of the asset over to `owner`.
+     * Only usable by asset owner.
+     * Selector: f2fde38b
+     */
+    function transferOwnership(address owner) external returns (bool);
+
+    /**
+     * @dev Changes the issuer, admin and freezer of the asset.
+     * Only usable by asset owner.
+     * Selector: c7d93c59
+     */
+    function setTeam(address issuer, address admin, address freezer)
+        external
+        returns (bool);
+}
+
+
+    /**
+     * @title Asset factory
+     * @dev Predeployed at the address 0x000000000000000000000000000000000000500A
+     */
+    interface IERC20Factory {
+
+    /**
+     * @dev Creates asset `assetId`, owned by the caller which pays the deposit.
+     * Selector: d0ef1a2f
+     * @param assetId uint128 Id of the new asset.
+     * @param admin address Initial issuer, admin and freezer of the asset.
+     * @param minBalance uint256 Minimum balance an account must have to exist.
+     * @return address under which the asset precompile is served.
+     */
+    function create(uint128 assetId, address admin, uint256 minBalance)
+        external
+        returns (address);
+}
@@ -0,0 +1,331 @@
+// This file is part of gpu.
+
+// Copyright (C) gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Benchmarks of the functions of the assets precompile reading the state of an asset.
+//!
+//! The asset read has a supply, an approval, a permit nonce and metadata of the longest name and
+//! symbol, so that every storage item read by the functions is there.
+//!
+//! `permit` is measured with the approval it dispatches.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::traits::Currency;
+use frame_system::RawOrigin;
+use pallet_assets::BenchmarkHelper;
+use precompile_utils::benchmarking::BenchmarkHandle;
+use sp_core::{testing::ECDSA, H256};
+use sp_io::{
+	crypto::{ecdsa_generate, ecdsa_sign_prehashed, secp256k1_ecdsa_recover},
+	hashing::keccak_256,
+};
+
+pub struct Pallet<T: Config>(PhantomData<T>);
+
+pub trait Config:
+	pallet_assets::Config + pallet_evm::Config + AddressToAssetId<AssetIdOf<Self>>
+{
+}
+
+const OWNER: H160 = H160::repeat_byte(0x11);
+const SPENDER: H160 = H160::repeat_byte(0x22);
+const AMOUNT: u32 = 1_000;
+
+/// Creates the asset read by the benchmarks, returning the address of its precompile.
+fn create_asset<T: Config>() -> Result<H160, BenchmarkError> {
+	let id = <T as pallet_assets::Config>::BenchmarkHelper::create_asset_id_parameter(0);
+	let owner = T::AddressMapping::into_account_id(OWNER);
+	let spender = T::AddressMapping::into_account_id(SPENDER);
+	let string_limit = <T as pallet_assets::Config>::StringLimit::get() as usize;
+	<T as pallet_assets::Config>::Currency::make_free_balance_be(&owner, Bounded::max_value());
+
+	pallet_assets::Pallet::<T>::force_create(
+		RawOrigin::Root.into(),
+		id,
+		T::Lookup::unlookup(owner.clone()),
+		true,
+		1u32.into(),
+	)?;
+	pallet_assets::Pallet::<T>::force_set_metadata(
+		RawOrigin::Root.into(),
+		id,
+		sp_std::vec![b'n'; string_limit],
+		sp_std::vec![b's'; string_limit],
+		18,
+		false,
+	)?;
+	pallet_assets::Pallet::<T>::mint(
+		RawOrigin::Signed(owner.clone()).into(),
+		id,
+		T::Lookup::unlookup(owner.clone()),
+		AMOUNT.into(),
+	)?;
+	pallet_assets::Pallet::<T>::approve_transfer(
+		RawOrigin::Signed(owner).into(),
+		id,
+		T::Lookup::unlookup(spender),
+		AMOUNT.into(),
+	)?;
+
+	let address = T::asset_id_to_address(id.into());
+	NoncesStorage::insert(address, OWNER, U256::one());
+	Ok(address)
+}
+
+/// A new key in the keystore and its EVM address.
+fn generate_key() -> (sp_core::ecdsa::Public, H160) {
+	let public = ecdsa_generate(ECDSA, None);
+	// The uncompressed key, hashed to the address, is only given back by a recovery.
+	let hash = keccak_256(b"address");
+	let signature = ecdsa_sign_prehashed(ECDSA, &public, &hash).expect("key is in the keystore");
+	let uncompressed =
+		secp256k1_ecdsa_recover(&signature.0, &hash).expect("signature is well formed");
+	(public, H160::from(H256::from(keccak_256(&uncompressed))))
+}
+
+#[benchmarks(
+	where
+		<T as frame_system::Config>::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<pallet_assets::Call<T>>,
+		<<T as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
+			From<Option<T::AccountId>> + OriginTrait,
+		BalanceOf<T>: TryFrom<U256> + Into<U256> + EvmData
+)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn total_supply() -> Result<(), BenchmarkError> {
+		let address = create_asset::<T>()?;
+		let input = EvmDataWriter::new_with_selector(Action::TotalSupply).build();
+		let mut handle = BenchmarkHandle::new(address, OWNER, input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute_in(&Erc20AssetsPrecompileSet::<T>::new());
+		}
+
+		assert_eq!(
+			result.map(|output| output.output),
+			Ok(EvmDataWriter::new().write(U256::from(AMOUNT)).build())
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn balance_of() -> Result<(), BenchmarkError> {
+		let address = create_asset::<T>()?;
+		let input = EvmDataWriter::new_with_selector(Action::BalanceOf)
+			.write(Address(OWNER))
+			.build();
+		let mut handle = BenchmarkHandle::new(address, OWNER, input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute_in(&Erc20AssetsPrecompileSet::<T>::new());
+		}
+
+		assert_eq!(
+			result.map(|output| output.output),
+			Ok(EvmDataWriter::new().write(U256::from(AMOUNT)).build())
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn allowance() -> Result<(), BenchmarkError> {
+		let address = create_asset::<T>()?;
+		let input = EvmDataWriter::new_with_selector(Action::Allowance)
+			.write(Address(OWNER))
+			.write(Address(SPENDER))
+			.build();
+		let mut handle = BenchmarkHandle::new(address, OWNER, input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute_in(&Erc20AssetsPrecompileSet::<T>::new());
+		}
+
+		assert_eq!(
+			result.map(|output| output.output),
+			Ok(EvmDataWriter::new().write(U256::from(AMOUNT)).build())
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn name() -> Result<(), BenchmarkError> {
+		let address = create_asset::<T>()?;
+		let input = EvmDataWriter::new_with_selector(Action::Name).build();
+		let mut handle = BenchmarkHandle::new(address, OWNER, input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute_in(&Erc20AssetsPrecompileSet::<T>::new());
+		}
+
+		assert!(result.is_ok());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn symbol() -> Result<(), BenchmarkError> {
+		let address = create_asset::<T>()?;
+		let input = EvmDataWriter::new_with_selector(Action::Symbol).build();
+		let mut handle = BenchmarkHandle::new(address, OWNER, input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute_in(&Erc20AssetsPrecompileSet::<T>::new());
+		}
+
+		assert!(result.is_ok());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn decimals() -> Result<(), BenchmarkError> {
+		let address = create_asset::<T>()?;
+		let input = EvmDataWriter::new_with_selector(Action::Decimals).build();
+		let mut handle = BenchmarkHandle::new(address, OWNER, input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute_in(&Erc20AssetsPrecompileSet::<T>::new());
+		}
+
+		assert_eq!(
+			result.map(|output| output.output),
+			Ok(EvmDataWriter::new().write(18u8).build())
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn minimum_balance() -> Result<(), BenchmarkError> {
+		let address = create_asset::<T>()?;
+		let input = EvmDataWriter::new_with_selector(Action::MinimumBalance).build();
+		let mut handle = BenchmarkHandle::new(address, OWNER, input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute_in(&Erc20AssetsPrecompileSet::<T>::new());
+		}
+
+		assert_eq!(
+			result.map(|output| output.output),
+			Ok(EvmDataWriter::new().write(U256::one()).build())
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn nonces() -> Result<(), BenchmarkError> {
+		let address = create_asset::<T>()?;
+		let input = EvmDataWriter::new_with_selector(Action::Nonces).write(Address(OWNER)).build();
+		let mut handle = BenchmarkHandle::new(address, OWNER, input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute_in(&Erc20AssetsPrecompileSet::<T>::new());
+		}
+
+		assert_eq!(
+			result.map(|output| output.output),
+			Ok(EvmDataWriter::new().write(U256::one()).build())
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn domain_separator() -> Result<(), BenchmarkError> {
+		let address = create_asset::<T>()?;
+		let input = EvmDataWriter::new_with_selector(Action::DomainSeparator).build();
+		let mut handle = BenchmarkHandle::new(address, OWNER, input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute_in(&Erc20AssetsPrecompileSet::<T>::new());
+		}
+
+		assert!(result.is_ok());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn permit() -> Result<(), BenchmarkError> {
+		let address = create_asset::<T>()?;
+		let asset_id = T::address_to_asset_id(address).expect("address of the created asset");
+		let (public, owner) = generate_key();
+		<T as pallet_assets::Config>::Currency::make_free_balance_be(
+			&T::AddressMapping::into_account_id(owner),
+			Bounded::max_value(),
+		);
+		NoncesStorage::insert(address, owner, U256::one());
+
+		let value = U256::from(AMOUNT);
+		let deadline = U256::MAX;
+		let permit = Eip2612::<T>::generate_permit(
+			address,
+			asset_id,
+			owner,
+			SPENDER,
+			value,
+			U256::one(),
+			deadline,
+		);
+		let signature =
+			ecdsa_sign_prehashed(ECDSA, &public, &permit).expect("key is in the keystore");
+		let input = EvmDataWriter::new_with_selector(Action::Permit)
+			.write(Address(owner))
+			.write(Address(SPENDER))
+			.write(value)
+			.write(deadline)
+			.write(signature.0[64])
+			.write(H256::from_slice(&signature.0[0..32]))
+			.write(H256::from_slice(&signature.0[32..64]))
+			.build();
+		let mut handle = BenchmarkHandle::new(address, owner, input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute_in(&Erc20AssetsPrecompileSet::<T>::new());
+		}
+
+		assert!(result.is_ok());
+		assert_eq!(NoncesStorage::get(address, owner), U256::from(2));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::ExtBuilder::default().build(),
+		crate::mock::Runtime
+	);
+}
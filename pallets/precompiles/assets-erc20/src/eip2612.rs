@@ -0,0 +1,224 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! EIP-2612 `permit` for the assets ERC20 precompile.
+//!
+//! The EIP-712 domain of an asset is built from its metadata name, version "1", the EVM chain id
+//! and the asset precompile address. Nonces are kept per asset and per owner.
+
+use super::*;
+use frame_support::{
+	storage::types::{StorageDoubleMap, ValueQuery},
+	traits::{StorageInstance, Time},
+	Blake2_128Concat,
+};
+use precompile_utils::revert;
+use sp_core::H256;
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::vec::Vec;
+
+/// EIP-712 TypeHash of the permit message.
+pub const PERMIT_TYPEHASH: [u8; 32] = keccak256!(
+	"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+);
+
+/// EIP-712 TypeHash of the domain.
+pub const PERMIT_DOMAIN: [u8; 32] = keccak256!(
+	"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+);
+
+/// Storage prefix of the permit nonces, placed under a pallet prefix no pallet uses.
+pub struct NoncesPrefix;
+
+impl StorageInstance for NoncesPrefix {
+	const STORAGE_PREFIX: &'static str = "Nonces";
+
+	fn pallet_prefix() -> &'static str {
+		"AssetsErc20"
+	}
+}
+
+/// Next permit nonce of an owner, keyed by asset precompile address then owner address.
+pub type NoncesStorage = StorageDoubleMap<
+	NoncesPrefix,
+	Blake2_128Concat,
+	H160,
+	Blake2_128Concat,
+	H160,
+	U256,
+	ValueQuery,
+>;
+
+pub struct Eip2612<Runtime, Instance: 'static = (), Weights = ()>(
+	PhantomData<(Runtime, Instance, Weights)>,
+);
+
+impl<Runtime, Instance, Weights> Eip2612<Runtime, Instance, Weights>
+where
+	Instance: 'static,
+	Weights: WeightInfo,
+	Runtime: pallet_assets::Config<Instance> + pallet_evm::Config + frame_system::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<pallet_assets::Call<Runtime, Instance>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime, Instance>: TryFrom<U256> + Into<U256> + EvmData,
+	Runtime: AddressToAssetId<AssetIdOf<Runtime, Instance>>,
+	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin: OriginTrait,
+{
+	pub fn compute_domain_separator(
+		address: H160,
+		asset_id: AssetIdOf<Runtime, Instance>,
+	) -> [u8; 32] {
+		let name: H256 =
+			keccak_256(&pallet_assets::Pallet::<Runtime, Instance>::name(asset_id)).into();
+		let version: H256 = keccak256!("1").into();
+		let chain_id: U256 = <Runtime as pallet_evm::Config>::ChainId::get().into();
+
+		let domain_separator_inner = EvmDataWriter::new()
+			.write(H256::from(PERMIT_DOMAIN))
+			.write(name)
+			.write(version)
+			.write(chain_id)
+			.write(Address(address))
+			.build();
+
+		keccak_256(&domain_separator_inner)
+	}
+
+	pub fn generate_permit(
+		address: H160,
+		asset_id: AssetIdOf<Runtime, Instance>,
+		owner: H160,
+		spender: H160,
+		value: U256,
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		let domain_separator = Self::compute_domain_separator(address, asset_id);
+
+		let permit_content = EvmDataWriter::new()
+			.write(H256::from(PERMIT_TYPEHASH))
+			.write(Address(owner))
+			.write(Address(spender))
+			.write(value)
+			.write(nonce)
+			.write(deadline)
+			.build();
+		let permit_content = keccak_256(&permit_content);
+
+		let mut pre_digest = Vec::with_capacity(2 + 32 + 32);
+		pre_digest.extend_from_slice(b"\x19\x01");
+		pre_digest.extend_from_slice(&domain_separator);
+		pre_digest.extend_from_slice(&permit_content);
+		keccak_256(&pre_digest)
+	}
+
+	pub(crate) fn permit(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		handle.record_weight::<Runtime>(Weights::permit())?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(7)?;
+
+		let owner: H160 = input.read::<Address>()?.into();
+		let spender: H160 = input.read::<Address>()?.into();
+		let value: U256 = input.read()?;
+		let deadline: U256 = input.read()?;
+		let v: u8 = input.read()?;
+		let r: H256 = input.read()?;
+		let s: H256 = input.read()?;
+
+		let address = handle.code_address();
+		let nonce = NoncesStorage::get(address, owner);
+
+		// Deadline is expressed in seconds, like `block.timestamp`.
+		let now: u128 = <Runtime as pallet_evm::Config>::Timestamp::now().unique_saturated_into();
+		if deadline < U256::from(now / 1000) {
+			return Err(revert("Permit expired"))
+		}
+
+		let permit = Self::generate_permit(
+			address,
+			asset_id.clone(),
+			owner,
+			spender,
+			value,
+			nonce,
+			deadline,
+		);
+
+		let mut sig = [0u8; 65];
+		sig[0..32].copy_from_slice(r.as_bytes());
+		sig[32..64].copy_from_slice(s.as_bytes());
+		sig[64] = v;
+
+		let signer = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &permit)
+			.map_err(|_| revert("Invalid permit"))?;
+		let signer = H160::from(H256::from_slice(keccak_256(&signer).as_slice()));
+
+		if signer != owner {
+			return Err(revert("Invalid permit"))
+		}
+
+		NoncesStorage::insert(address, owner, nonce + U256::one());
+
+		{
+			let owner = Runtime::AddressMapping::into_account_id(owner);
+			let spender: Runtime::AccountId = Runtime::AddressMapping::into_account_id(spender);
+			// Amount saturate if too high.
+			let amount: BalanceOf<Runtime, Instance> =
+				value.try_into().unwrap_or_else(|_| Bounded::max_value());
+
+			Erc20AssetsPrecompileSet::<Runtime, Instance, Weights>::approve_inner(
+				asset_id, handle, owner, spender, amount,
+			)?;
+		}
+
+		LogsBuilder::new(address)
+			.log3(SELECTOR_LOG_APPROVAL, owner, spender, EvmDataWriter::new().write(value).build())
+			.record(handle)?;
+
+		Ok(succeed(EvmDataWriter::new().build()))
+	}
+
+	pub(crate) fn nonces(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_weight::<Runtime>(Weights::nonces())?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let owner: H160 = input.read::<Address>()?.into();
+
+		let nonce = NoncesStorage::get(handle.code_address(), owner);
+
+		Ok(succeed(EvmDataWriter::new().write(nonce).build()))
+	}
+
+	pub(crate) fn domain_separator(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		handle.record_weight::<Runtime>(Weights::domain_separator())?;
+
+		let domain_separator = Self::compute_domain_separator(handle.code_address(), asset_id);
+
+		Ok(succeed(EvmDataWriter::new().write(H256::from(domain_separator)).build()))
+	}
+}
@@ -0,0 +1,97 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Factory precompile creating new assets from the EVM.
+//!
+//! The caller becomes the owner and pays the asset deposit, as with `pallet_assets::create`. The
+//! returned address is the one `Erc20AssetsPrecompileSet` serves the new asset under. It is expected
+//! to be given the revert bytecode by the `CallbackHandle` of `pallet_assets`, so that contracts see
+//! it as an account with code.
+
+use super::*;
+
+#[precompile_utils::generate_function_selector]
+#[derive(Debug, PartialEq)]
+pub enum FactoryAction {
+	#[returns("address")]
+	Create = "create(uint128,address,uint256)",
+}
+
+/// Precompile creating assets through `pallet_assets::create`.
+pub struct Erc20AssetsFactoryPrecompile<Runtime, Instance: 'static = ()>(
+	PhantomData<(Runtime, Instance)>,
+);
+
+impl<Runtime, Instance> Precompile for Erc20AssetsFactoryPrecompile<Runtime, Instance>
+where
+	Instance: 'static,
+	Runtime: pallet_assets::Config<Instance> + pallet_evm::Config + frame_system::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<pallet_assets::Call<Runtime, Instance>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime, Instance>: TryFrom<U256> + Into<U256> + EvmData,
+	AssetIdOf<Runtime, Instance>: From<u128>,
+	Runtime: AddressToAssetId<AssetIdOf<Runtime, Instance>>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let selector = handle.read_selector()?;
+
+		handle.check_function_modifier(FunctionModifier::NonPayable)?;
+
+		match selector {
+			FactoryAction::Create => Self::create(handle),
+		}
+	}
+}
+
+impl<Runtime, Instance> Erc20AssetsFactoryPrecompile<Runtime, Instance>
+where
+	Instance: 'static,
+	Runtime: pallet_assets::Config<Instance> + pallet_evm::Config + frame_system::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<pallet_assets::Call<Runtime, Instance>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime, Instance>: TryFrom<U256> + Into<U256> + EvmData,
+	AssetIdOf<Runtime, Instance>: From<u128>,
+	Runtime: AddressToAssetId<AssetIdOf<Runtime, Instance>>,
+{
+	fn create(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+
+		let asset_id: AssetIdOf<Runtime, Instance> = input.read::<u128>()?.into();
+		let admin: H160 = input.read::<Address>()?.into();
+		let min_balance = input.read::<BalanceOf<Runtime, Instance>>()?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let admin = Runtime::AddressMapping::into_account_id(admin);
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_assets::Call::<Runtime, Instance>::create {
+				id: asset_id.clone().into(),
+				admin: Runtime::Lookup::unlookup(admin),
+				min_balance,
+			},
+		)?;
+
+		let address = Runtime::asset_id_to_address(asset_id);
+
+		Ok(succeed(EvmDataWriter::new().write(Address(address)).build()))
+	}
+}
@@ -47,23 +47,35 @@ use frame_support::{
 		OriginTrait,
 	},
 };
-use pallet_evm::{AddressMapping, PrecompileSet};
+use pallet_evm::{AddressMapping, Precompile, PrecompileSet};
 use precompile_utils::{
-	keccak256, succeed, Address, Bytes, EvmData, EvmDataWriter, EvmResult, FunctionModifier,
-	LogExt, LogsBuilder, PrecompileHandleExt, RuntimeHelper,
+	keccak256, succeed, Address, Bytes, CustomError, EvmData, EvmDataWriter, EvmResult,
+	FunctionModifier, LogExt, LogsBuilder, PrecompileHandleExt, RuntimeHelper,
 };
-use sp_runtime::traits::{Bounded, Zero};
+use sp_runtime::traits::Bounded;
 
 use sp_core::{Get, MaxEncodedLen, H160, U256};
 use sp_std::{
 	convert::{TryFrom, TryInto},
 	marker::PhantomData,
+	vec::Vec,
 };
 
-// #[cfg(test)]
-// mod mock;
-// #[cfg(test)]
-// mod tests;
+mod eip2612;
+pub use eip2612::{Eip2612, NoncesStorage, PERMIT_DOMAIN, PERMIT_TYPEHASH};
+
+mod factory;
+pub use factory::{Erc20AssetsFactoryPrecompile, FactoryAction};
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
 
 /// Solidity selector of the Transfer log, which is the Keccak of the Log signature.
 pub const SELECTOR_LOG_TRANSFER: [u8; 32] = keccak256!("Transfer(address,address,uint256)");
@@ -71,6 +83,20 @@ pub const SELECTOR_LOG_TRANSFER: [u8; 32] = keccak256!("Transfer(address,address
 /// Solidity selector of the Approval log, which is the Keccak of the Log signature.
 pub const SELECTOR_LOG_APPROVAL: [u8; 32] = keccak256!("Approval(address,address,uint256)");
 
+/// Revert opcodes (`PUSH1 0 PUSH1 0 REVERT`) stored as the code of asset precompile addresses, so
+/// that contracts calling them see an account with code.
+pub const EVM_REVERT_CODE: &[u8] = &[0x60, 0x00, 0x60, 0x00, 0xfd];
+
+/// Solidity selector of the ERC-6093 error reverting transfers above the balance of the sender,
+/// which is the Keccak of the error signature.
+pub const SELECTOR_ERROR_INSUFFICIENT_BALANCE: [u8; 32] =
+	keccak256!("ERC20InsufficientBalance(address,uint256,uint256)");
+
+/// Solidity selector of the ERC-6093 error reverting transfers above the allowance of the
+/// spender, which is the Keccak of the error signature.
+pub const SELECTOR_ERROR_INSUFFICIENT_ALLOWANCE: [u8; 32] =
+	keccak256!("ERC20InsufficientAllowance(address,uint256,uint256)");
+
 /// Alias for the Balance type for the provided Runtime and Instance.
 pub type BalanceOf<Runtime, Instance = ()> = <Runtime as pallet_assets::Config<Instance>>::Balance;
 
@@ -78,20 +104,66 @@ pub type BalanceOf<Runtime, Instance = ()> = <Runtime as pallet_assets::Config<I
 pub type AssetIdOf<Runtime, Instance = ()> = <Runtime as pallet_assets::Config<Instance>>::AssetId;
 
 #[precompile_utils::generate_function_selector]
+#[event("Transfer(address indexed,address indexed,uint256)")]
+#[event("Approval(address indexed,address indexed,uint256)")]
+#[error("ERC20InsufficientBalance(address,uint256,uint256)")]
+#[error("ERC20InsufficientAllowance(address,uint256,uint256)")]
 #[derive(Debug, PartialEq)]
 pub enum Action {
+	#[view]
+	#[returns("uint256")]
 	TotalSupply = "totalSupply()",
+	#[view]
+	#[returns("uint256")]
 	BalanceOf = "balanceOf(address)",
+	#[view]
+	#[returns("uint256")]
 	Allowance = "allowance(address,address)",
+	#[returns("bool")]
 	Transfer = "transfer(address,uint256)",
+	#[returns("bool")]
 	Approve = "approve(address,uint256)",
+	#[returns("bool")]
 	TransferFrom = "transferFrom(address,address,uint256)",
+	#[view]
+	#[returns("string")]
 	Name = "name()",
+	#[view]
+	#[returns("string")]
 	Symbol = "symbol()",
+	#[view]
+	#[returns("uint8")]
 	Decimals = "decimals()",
+	#[view]
+	#[returns("uint256")]
 	MinimumBalance = "minimumBalance()",
+	#[returns("bool")]
 	Mint = "mint(address,uint256)",
+	#[returns("bool")]
 	Burn = "burn(address,uint256)",
+	Permit = "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+	#[view]
+	#[returns("uint256")]
+	Nonces = "nonces(address)",
+	#[view]
+	#[returns("bytes32")]
+	DomainSeparator = "DOMAIN_SEPARATOR()",
+	#[returns("bool")]
+	SetMetadata = "setMetadata(string,string,uint8)",
+	#[returns("bool")]
+	ClearMetadata = "clearMetadata()",
+	#[returns("bool")]
+	Freeze = "freeze(address)",
+	#[returns("bool")]
+	Thaw = "thaw(address)",
+	#[returns("bool")]
+	FreezeAsset = "freezeAsset()",
+	#[returns("bool")]
+	ThawAsset = "thawAsset()",
+	#[returns("bool")]
+	TransferOwnership = "transferOwnership(address)",
+	#[returns("bool")]
+	SetTeam = "setTeam(address,address,address)",
 }
 
 /// This trait ensure we can convert EVM address to AssetIds
@@ -112,25 +184,25 @@ pub trait AddressToAssetId<AssetId> {
 ///     0xFFFFFFFF00000000000000000000000000000000 - 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF
 /// The precompile for AssetId X, where X is a u128 (i.e.16 bytes), if 0XFFFFFFFF + Bytes(AssetId)
 /// In order to route the address to Erc20AssetsPrecompile<R>, we first check whether the AssetId
-/// exists in pallet-assets
-/// We cannot do this right now, so instead we check whether the total supply is zero. If so, we
-/// do not route to the precompiles
+/// exists in pallet-assets, whatever its supply.
 
 /// This means that every address that starts with 0xFFFFFFFF will go through an additional db read,
 /// but the probability for this to happen is 2^-32 for random addresses
-pub struct Erc20AssetsPrecompileSet<Runtime, Instance: 'static = ()>(
-	PhantomData<(Runtime, Instance)>,
+pub struct Erc20AssetsPrecompileSet<Runtime, Instance: 'static = (), Weights = ()>(
+	PhantomData<(Runtime, Instance, Weights)>,
 );
 
-impl<Runtime, Instance> Erc20AssetsPrecompileSet<Runtime, Instance> {
+impl<Runtime, Instance, Weights> Erc20AssetsPrecompileSet<Runtime, Instance, Weights> {
 	pub fn new() -> Self {
 		Self(PhantomData)
 	}
 }
 
-impl<Runtime, Instance> PrecompileSet for Erc20AssetsPrecompileSet<Runtime, Instance>
+impl<Runtime, Instance, Weights> PrecompileSet
+	for Erc20AssetsPrecompileSet<Runtime, Instance, Weights>
 where
 	Instance: 'static,
+	Weights: WeightInfo,
 	Runtime: pallet_assets::Config<Instance> + pallet_evm::Config + frame_system::Config,
 	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
 	Runtime::RuntimeCall: From<pallet_assets::Call<Runtime, Instance>>,
@@ -159,7 +231,16 @@ where
 						Action::Transfer |
 						Action::TransferFrom |
 						Action::Mint |
-						Action::Burn => FunctionModifier::NonPayable,
+						Action::Burn |
+						Action::Permit |
+						Action::SetMetadata |
+						Action::ClearMetadata |
+						Action::Freeze |
+						Action::Thaw |
+						Action::FreezeAsset |
+						Action::ThawAsset |
+						Action::TransferOwnership |
+						Action::SetTeam => FunctionModifier::NonPayable,
 						_ => FunctionModifier::View,
 					}) {
 						return Some(Err(err))
@@ -180,6 +261,23 @@ where
 						Action::MinimumBalance => Self::minimum_balance(asset_id, handle),
 						Action::Mint => Self::mint(asset_id, handle),
 						Action::Burn => Self::burn(asset_id, handle),
+						// EIP-2612
+						Action::Permit =>
+							Eip2612::<Runtime, Instance, Weights>::permit(asset_id, handle),
+						Action::Nonces => Eip2612::<Runtime, Instance, Weights>::nonces(handle),
+						Action::DomainSeparator =>
+							Eip2612::<Runtime, Instance, Weights>::domain_separator(
+								asset_id, handle,
+							),
+						// Asset management
+						Action::SetMetadata => Self::set_metadata(asset_id, handle),
+						Action::ClearMetadata => Self::clear_metadata(asset_id, handle),
+						Action::Freeze => Self::freeze(asset_id, handle),
+						Action::Thaw => Self::thaw(asset_id, handle),
+						Action::FreezeAsset => Self::freeze_asset(asset_id, handle),
+						Action::ThawAsset => Self::thaw_asset(asset_id, handle),
+						Action::TransferOwnership => Self::transfer_ownership(asset_id, handle),
+						Action::SetTeam => Self::set_team(asset_id, handle),
 					}
 				};
 				return Some(result)
@@ -190,14 +288,8 @@ where
 
 	fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
 		let is_precompile = if let Some(asset_id) = Runtime::address_to_asset_id(address) {
-			// If the assetId has non-zero supply
-			// "total_supply" returns both 0 if the assetId does not exist or if the supply is 0
-			// The assumption I am making here is that a 0 supply asset is not interesting from
-			// the perspective of the precompiles. Once pallet-assets has more publicly accesible
-			// storage we can use another function for this, like check_asset_existence.
-			// The other options is to check the asset existence in pallet-asset-manager, but
-			// this makes the precompiles dependent on such a pallet, which is not ideal
-			!pallet_assets::Pallet::<Runtime, Instance>::total_supply(asset_id).is_zero()
+			// "maybe_total_supply" returns Some if the asset exists, even with a zero supply.
+			pallet_assets::Pallet::<Runtime, Instance>::maybe_total_supply(asset_id).is_some()
 		} else {
 			false
 		};
@@ -206,9 +298,10 @@ where
 	}
 }
 
-impl<Runtime, Instance> Erc20AssetsPrecompileSet<Runtime, Instance>
+impl<Runtime, Instance, Weights> Erc20AssetsPrecompileSet<Runtime, Instance, Weights>
 where
 	Instance: 'static,
+	Weights: WeightInfo,
 	Runtime: pallet_assets::Config<Instance> + pallet_evm::Config + frame_system::Config,
 	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
 	Runtime::RuntimeCall: From<pallet_assets::Call<Runtime, Instance>>,
@@ -221,10 +314,7 @@ where
 		asset_id: AssetIdOf<Runtime, Instance>,
 		handle: &mut impl PrecompileHandle,
 	) -> EvmResult<PrecompileOutput> {
-		// TODO: benchmark this function so we can measure ref time & PoV correctly
-		// Storage item: Asset:
-		// Blake2_128(16) + AssetId(16) + AssetDetails((4 * AccountId(32)) + (3 * Balance(16)) + 15)
-		handle.record_db_read::<Runtime>(223)?;
+		handle.record_weight::<Runtime>(Weights::total_supply())?;
 
 		// Fetch info.
 		let amount: U256 =
@@ -237,12 +327,7 @@ where
 		asset_id: AssetIdOf<Runtime, Instance>,
 		handle: &mut impl PrecompileHandle,
 	) -> EvmResult<PrecompileOutput> {
-		// TODO: benchmark this function so we can measure ref time & PoV correctly
-		// Storage item: Account:
-		// Blake2_128(16) + AssetId(16) + Blake2_128(16) + AccountId(32) + AssetAccount(19 + Extra)
-		handle.record_db_read::<Runtime>(
-			99 + <Runtime as pallet_assets::Config<Instance>>::Extra::max_encoded_len(),
-		)?;
+		handle.record_weight::<Runtime>(Weights::balance_of())?;
 
 		let mut input = handle.read_input()?;
 		input.expect_arguments(1)?;
@@ -262,10 +347,7 @@ where
 		asset_id: AssetIdOf<Runtime, Instance>,
 		handle: &mut impl PrecompileHandle,
 	) -> EvmResult<PrecompileOutput> {
-		// TODO: benchmark this function so we can measure ref time & PoV correctly
-		// Storage item: Approvals:
-		// Blake2_128(16) + AssetId(16) + (2 * Blake2_128(16) + AccountId(20)) + Approval(32)
-		handle.record_db_read::<Runtime>(148)?;
+		handle.record_weight::<Runtime>(Weights::allowance())?;
 
 		let mut input = handle.read_input()?;
 		input.expect_arguments(2)?;
@@ -304,35 +386,7 @@ where
 			let amount: BalanceOf<Runtime, Instance> =
 				amount.try_into().unwrap_or_else(|_| Bounded::max_value());
 
-			// Allowance read
-			handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
-
-			// If previous approval exists, we need to clean it
-			if pallet_assets::Pallet::<Runtime, Instance>::allowance(
-				asset_id.clone(),
-				&origin,
-				&spender,
-			) != 0u32.into()
-			{
-				RuntimeHelper::<Runtime>::try_dispatch(
-					handle,
-					Some(origin.clone()).into(),
-					pallet_assets::Call::<Runtime, Instance>::cancel_approval {
-						id: asset_id.clone().into(),
-						delegate: Runtime::Lookup::unlookup(spender.clone()),
-					},
-				)?;
-			}
-			// Dispatch call (if enough gas).
-			RuntimeHelper::<Runtime>::try_dispatch(
-				handle,
-				Some(origin).into(),
-				pallet_assets::Call::<Runtime, Instance>::approve_transfer {
-					id: asset_id.into(),
-					delegate: Runtime::Lookup::unlookup(spender),
-					amount,
-				},
-			)?;
+			Self::approve_inner(asset_id, handle, origin, spender, amount)?;
 		}
 
 		LogsBuilder::new(handle.context().address)
@@ -347,6 +401,45 @@ where
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
 
+	/// Sets the approval of `spender` over the assets of `owner` to `amount`, replacing any
+	/// previous approval.
+	pub(crate) fn approve_inner(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+		owner: Runtime::AccountId,
+		spender: Runtime::AccountId,
+		amount: BalanceOf<Runtime, Instance>,
+	) -> EvmResult {
+		// Allowance read
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		// If previous approval exists, we need to clean it
+		if pallet_assets::Pallet::<Runtime, Instance>::allowance(asset_id.clone(), &owner, &spender) !=
+			0u32.into()
+		{
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(owner.clone()).into(),
+				pallet_assets::Call::<Runtime, Instance>::cancel_approval {
+					id: asset_id.clone().into(),
+					delegate: Runtime::Lookup::unlookup(spender.clone()),
+				},
+			)?;
+		}
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(owner).into(),
+			pallet_assets::Call::<Runtime, Instance>::approve_transfer {
+				id: asset_id.into(),
+				delegate: Runtime::Lookup::unlookup(spender),
+				amount,
+			},
+		)?;
+
+		Ok(())
+	}
+
 	fn transfer(
 		asset_id: AssetIdOf<Runtime, Instance>,
 		handle: &mut impl PrecompileHandle,
@@ -359,6 +452,9 @@ where
 		let to: H160 = input.read::<Address>()?.into();
 		let amount = input.read::<BalanceOf<Runtime, Instance>>()?;
 
+		let caller = handle.context().caller;
+		Self::ensure_balance(asset_id.clone(), handle, caller, amount)?;
+
 		// Build call with origin.
 		{
 			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
@@ -401,6 +497,12 @@ where
 		let to: H160 = input.read::<Address>()?.into();
 		let amount = input.read::<BalanceOf<Runtime, Instance>>()?;
 
+		let caller = handle.context().caller;
+		if caller != from {
+			Self::ensure_allowance(asset_id.clone(), handle, from, caller, amount)?;
+		}
+		Self::ensure_balance(asset_id.clone(), handle, from, amount)?;
+
 		{
 			let caller: Runtime::AccountId =
 				Runtime::AddressMapping::into_account_id(handle.context().caller);
@@ -441,18 +543,69 @@ where
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
 
-	fn name(
+	/// Reverts with `ERC20InsufficientBalance` if `owner` holds less than `amount`, which the
+	/// pallet would otherwise only report as a dispatch error.
+	fn ensure_balance(
 		asset_id: AssetIdOf<Runtime, Instance>,
 		handle: &mut impl PrecompileHandle,
-	) -> EvmResult<PrecompileOutput> {
-		// TODO: benchmark this function so we can measure ref time & PoV correctly
-		// Storage item: Metadata:
-		// Blake2_128(16) + AssetId(16) + AssetMetadata[deposit(16) + name(StringLimit)
-		// + symbol(StringLimit) + decimals(1) + is_frozen(1)]
+		owner: H160,
+		amount: BalanceOf<Runtime, Instance>,
+	) -> EvmResult {
+		// Storage item: Account, as read by `balance_of`. Charged even though the dispatched
+		// transfer reads it again, as nothing is dispatched when the check reverts.
 		handle.record_db_read::<Runtime>(
-			50 + (2 * <Runtime as pallet_assets::Config<Instance>>::StringLimit::get()) as usize,
+			99 + <Runtime as pallet_assets::Config<Instance>>::Extra::max_encoded_len(),
 		)?;
 
+		let balance = pallet_assets::Pallet::<Runtime, Instance>::balance(
+			asset_id,
+			&Runtime::AddressMapping::into_account_id(owner),
+		);
+		if balance < amount {
+			return Err(CustomError::new(SELECTOR_ERROR_INSUFFICIENT_BALANCE)
+				.write(Address(owner))
+				.write::<U256>(balance.into())
+				.write::<U256>(amount.into())
+				.into())
+		}
+
+		Ok(())
+	}
+
+	/// Reverts with `ERC20InsufficientAllowance` if `spender` may spend less than `amount` of the
+	/// assets of `owner`.
+	fn ensure_allowance(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+		owner: H160,
+		spender: H160,
+		amount: BalanceOf<Runtime, Instance>,
+	) -> EvmResult {
+		// Storage item: Approvals, as read by `allowance`.
+		handle.record_db_read::<Runtime>(148)?;
+
+		let allowance = pallet_assets::Pallet::<Runtime, Instance>::allowance(
+			asset_id,
+			&Runtime::AddressMapping::into_account_id(owner),
+			&Runtime::AddressMapping::into_account_id(spender),
+		);
+		if allowance < amount {
+			return Err(CustomError::new(SELECTOR_ERROR_INSUFFICIENT_ALLOWANCE)
+				.write(Address(spender))
+				.write::<U256>(allowance.into())
+				.write::<U256>(amount.into())
+				.into())
+		}
+
+		Ok(())
+	}
+
+	fn name(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		handle.record_weight::<Runtime>(Weights::name())?;
+
 		Ok(succeed(
 			EvmDataWriter::new()
 				.write::<Bytes>(
@@ -466,13 +619,7 @@ where
 		asset_id: AssetIdOf<Runtime, Instance>,
 		handle: &mut impl PrecompileHandle,
 	) -> EvmResult<PrecompileOutput> {
-		// TODO: benchmark this function so we can measure ref time & PoV correctly
-		// Storage item: Metadata:
-		// Blake2_128(16) + AssetId(16) + AssetMetadata[deposit(16) + name(StringLimit)
-		// + symbol(StringLimit) + decimals(1) + is_frozen(1)]
-		handle.record_db_read::<Runtime>(
-			50 + (2 * <Runtime as pallet_assets::Config<Instance>>::StringLimit::get()) as usize,
-		)?;
+		handle.record_weight::<Runtime>(Weights::symbol())?;
 
 		// Build output.
 		Ok(succeed(
@@ -488,13 +635,7 @@ where
 		asset_id: AssetIdOf<Runtime, Instance>,
 		handle: &mut impl PrecompileHandle,
 	) -> EvmResult<PrecompileOutput> {
-		// TODO: benchmark this function so we can measure ref time & PoV correctly
-		// Storage item: Metadata:
-		// Blake2_128(16) + AssetId(16) + AssetMetadata[deposit(16) + name(StringLimit)
-		// + symbol(StringLimit) + decimals(1) + is_frozen(1)]
-		handle.record_db_read::<Runtime>(
-			50 + (2 * <Runtime as pallet_assets::Config<Instance>>::StringLimit::get()) as usize,
-		)?;
+		handle.record_weight::<Runtime>(Weights::decimals())?;
 
 		// Build output.
 		Ok(succeed(
@@ -508,10 +649,7 @@ where
 		asset_id: AssetIdOf<Runtime, Instance>,
 		handle: &mut impl PrecompileHandle,
 	) -> EvmResult<PrecompileOutput> {
-		// TODO: benchmark this function so we can measure ref time & PoV correctly
-		// Storage item: AssetDetails:
-		// Blake2_128(16) + AssetDetails((4 * AccountId(32)) + (3 * Balance(16)) + 15)
-		handle.record_db_read::<Runtime>(207)?;
+		handle.record_weight::<Runtime>(Weights::minimum_balance())?;
 
 		let min_balance: U256 =
 			pallet_assets::Pallet::<Runtime, Instance>::minimum_balance(asset_id).into();
@@ -572,4 +710,186 @@ where
 
 		Ok(succeed(EvmDataWriter::new().write(true).build()))
 	}
+
+	fn set_metadata(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+
+		let name: Vec<u8> = input.read::<Bytes>()?.into();
+		let symbol: Vec<u8> = input.read::<Bytes>()?.into();
+		let decimals: u8 = input.read()?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_assets::Call::<Runtime, Instance>::set_metadata {
+				id: asset_id.into(),
+				name,
+				symbol,
+				decimals,
+			},
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn clear_metadata(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_assets::Call::<Runtime, Instance>::clear_metadata { id: asset_id.into() },
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn freeze(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let who: H160 = input.read::<Address>()?.into();
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let who = Runtime::AddressMapping::into_account_id(who);
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_assets::Call::<Runtime, Instance>::freeze {
+				id: asset_id.into(),
+				who: Runtime::Lookup::unlookup(who),
+			},
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn thaw(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let who: H160 = input.read::<Address>()?.into();
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let who = Runtime::AddressMapping::into_account_id(who);
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_assets::Call::<Runtime, Instance>::thaw {
+				id: asset_id.into(),
+				who: Runtime::Lookup::unlookup(who),
+			},
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn freeze_asset(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_assets::Call::<Runtime, Instance>::freeze_asset { id: asset_id.into() },
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn thaw_asset(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_assets::Call::<Runtime, Instance>::thaw_asset { id: asset_id.into() },
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn transfer_ownership(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let owner: H160 = input.read::<Address>()?.into();
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let owner = Runtime::AddressMapping::into_account_id(owner);
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_assets::Call::<Runtime, Instance>::transfer_ownership {
+				id: asset_id.into(),
+				owner: Runtime::Lookup::unlookup(owner),
+			},
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn set_team(
+		asset_id: AssetIdOf<Runtime, Instance>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+
+		let issuer: H160 = input.read::<Address>()?.into();
+		let admin: H160 = input.read::<Address>()?.into();
+		let freezer: H160 = input.read::<Address>()?.into();
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let issuer = Runtime::AddressMapping::into_account_id(issuer);
+		let admin = Runtime::AddressMapping::into_account_id(admin);
+		let freezer = Runtime::AddressMapping::into_account_id(freezer);
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_assets::Call::<Runtime, Instance>::set_team {
+				id: asset_id.into(),
+				issuer: Runtime::Lookup::unlookup(issuer),
+				admin: Runtime::Lookup::unlookup(admin),
+				freezer: Runtime::Lookup::unlookup(freezer),
+			},
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
 }
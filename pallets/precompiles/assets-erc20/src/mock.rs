@@ -46,19 +46,19 @@ use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 
 use frame_system::EnsureRoot;
 use pallet_evm::{AddressMapping, EnsureAddressNever, EnsureAddressRoot};
+use precompile_utils::testing::CryptoAlith;
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_core::{ConstU32, H160, H256};
+use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
 use sp_runtime::{
-
     traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage,
 };
 
 pub type AccountId = Account;
 pub type AssetId = u128;
 pub type Balance = u128;
-pub type BlockNumber = u64;
-pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 pub type Block = frame_system::mocking::MockBlock<Runtime>;
 
 /// A simple account type.
@@ -81,6 +81,8 @@ pub enum Account {
     Alice,
     Bob,
     Charlie,
+    /// Account with a known private key, used to sign permits.
+    Alith,
     Bogus,
     AssetId(AssetId),
 }
@@ -97,6 +99,7 @@ impl AddressMapping<Account> for Account {
             a if a == H160::repeat_byte(0xAA) => Self::Alice,
             a if a == H160::repeat_byte(0xBB) => Self::Bob,
             a if a == H160::repeat_byte(0xCC) => Self::Charlie,
+            a if a == H160::from(CryptoAlith) => Self::Alith,
             _ => {
                 let mut data = [0u8; 16];
                 let (prefix_part, id_part) = h160_account.as_fixed_bytes().split_at(4);
@@ -142,6 +145,7 @@ impl From<Account> for H160 {
             Account::Alice => H160::repeat_byte(0xAA),
             Account::Bob => H160::repeat_byte(0xBB),
             Account::Charlie => H160::repeat_byte(0xCC),
+            Account::Alith => CryptoAlith.into(),
             Account::AssetId(asset_id) => {
                 let mut data = [0u8; 20];
                 let id_as_bytes = asset_id.to_be_bytes();
@@ -171,7 +175,7 @@ impl frame_system::Config for Runtime {
     type DbWeight = ();
     type RuntimeOrigin = RuntimeOrigin;
     type Nonce = u64;
-    type Block = BlockNumber;
+    type Block = Block;
     type RuntimeCall = RuntimeCall;
     type Hash = H256;
     type Hashing = BlakeTwo256;
@@ -217,6 +221,7 @@ impl pallet_balances::Config for Runtime {
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
+    type RuntimeHoldReason = ();
     type FreezeIdentifier = ();
     type MaxHolds = ConstU32<0>;
     type MaxFreezes = ConstU32<0>;
@@ -251,6 +256,31 @@ impl pallet_evm::Config for Runtime {
     type GasLimitPovSizeRatio = ConstU64<4>;
 }
 
+/// Address the asset factory precompile is mounted at in tests.
+pub const FACTORY_ADDRESS: H160 = H160::repeat_byte(0x50);
+
+/// Precompile set only serving the asset factory.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FactoryPrecompileSet;
+
+impl PrecompileSet for FactoryPrecompileSet {
+    fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<EvmResult<PrecompileOutput>> {
+        match handle.code_address() {
+            a if a == FACTORY_ADDRESS => {
+                Some(Erc20AssetsFactoryPrecompile::<Runtime>::execute(handle))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
+        IsPrecompileResult::Answer {
+            is_precompile: address == FACTORY_ADDRESS,
+            extra_cost: 0,
+        }
+    }
+}
+
 // These parameters dont matter much as this will only be called by root with the forced arguments
 // No deposit is substracted with those methods
 parameter_types! {
@@ -280,21 +310,34 @@ impl pallet_assets::Config for Runtime {
     type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
     type RemoveItemsLimit = ConstU32<0>;
     type AssetIdParameter = AssetId;
-    type CallbackHandle = ();
+    type CallbackHandle = RevertCodeHandler;
     #[cfg(feature = "runtime-benchmarks")]
     type BenchmarkHelper = ();
 }
 
+/// Registers the revert code at the address of a new asset, as the runtime does.
+pub struct RevertCodeHandler;
+impl pallet_assets::AssetsCallback<AssetId, AccountId> for RevertCodeHandler {
+    fn created(id: &AssetId, _: &AccountId) -> Result<(), ()> {
+        pallet_evm::AccountCodes::<Runtime>::insert(
+            Runtime::asset_id_to_address(*id),
+            EVM_REVERT_CODE.to_vec(),
+        );
+        Ok(())
+    }
+}
+
 // Configure a mock runtime to test the pallet.
-construct_runtime!(
-	pub struct Runtime {
+construct_runtime! {
+    pub enum Runtime
+    {
         System: frame_system,
         Balances: pallet_balances,
         Assets: pallet_assets,
         Evm: pallet_evm,
         Timestamp: pallet_timestamp,
-	}
-);
+    }
+}
 
 pub(crate) struct ExtBuilder {
     // endowed accounts with balances
@@ -314,8 +357,8 @@ impl ExtBuilder {
     }
 
     pub(crate) fn build(self) -> sp_io::TestExternalities {
-        let mut t = frame_system::GenesisConfig::default()
-            .build_storage::<Runtime>()
+        let mut t = frame_system::GenesisConfig::<Runtime>::default()
+            .build_storage()
             .expect("Frame system builds valid default genesis config");
 
         pallet_balances::GenesisConfig::<Runtime> {
@@ -325,7 +368,11 @@ impl ExtBuilder {
         .expect("Pallet balances storage can be assimilated");
 
         let mut ext = sp_io::TestExternalities::new(t);
+        ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
         ext.execute_with(|| System::set_block_number(1));
         ext
     }
 }
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::benchmarking::Config for Runtime {}
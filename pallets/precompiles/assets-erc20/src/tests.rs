@@ -33,19 +33,34 @@
 
 // You should have received a copy of the GNU General Public License
 // along with AssetsERC20.  If not, see <http://www.gnu.org/licenses/>.
-use frame_support::assert_ok;
+use frame_support::{assert_ok, weights::Weight};
 use std::str::from_utf8;
 
 use crate::mock::*;
 use crate::*;
 
-use precompile_utils::{testing::*, EvmDataWriter, LogsBuilder};
+use pallet_evm::GasWeightMapping;
+use precompile_utils::{
+    log_costs, testing::*, CustomError, EvmDataWriter, LogsBuilder, Revert, RuntimeHelper,
+};
 use sha3::{Digest, Keccak256};
+use sp_core::H256;
+use sp_runtime::traits::Zero;
 
 fn precompiles() -> Erc20AssetsPrecompileSet<Runtime> {
     PrecompilesValue::get()
 }
 
+/// Output of a revert with a custom error.
+fn custom_error(error: CustomError) -> Vec<u8> {
+    Revert::from(error).to_encoded_bytes()
+}
+
+/// Gas charged for a benchmarked weight.
+fn weight_cost(weight: Weight) -> u64 {
+    <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight)
+}
+
 #[test]
 fn selector_less_than_four_bytes() {
     ExtBuilder::default().build().execute_with(|| {
@@ -98,6 +113,18 @@ fn selectors() {
     assert_eq!(Action::MinimumBalance as u32, 0xb9d1d49b);
     assert_eq!(Action::Mint as u32, 0x40c10f19);
     assert_eq!(Action::Burn as u32, 0x9dc29fac);
+    assert_eq!(Action::Permit as u32, 0xd505accf);
+    assert_eq!(Action::Nonces as u32, 0x7ecebe00);
+    assert_eq!(Action::DomainSeparator as u32, 0x3644e515);
+    assert_eq!(Action::SetMetadata as u32, 0x37d2c2f4);
+    assert_eq!(Action::ClearMetadata as u32, 0xefb6d432);
+    assert_eq!(Action::Freeze as u32, 0x8d1fdf2f);
+    assert_eq!(Action::Thaw as u32, 0x5ea20216);
+    assert_eq!(Action::FreezeAsset as u32, 0xd4937f51);
+    assert_eq!(Action::ThawAsset as u32, 0x51ec2ad7);
+    assert_eq!(Action::TransferOwnership as u32, 0xf2fde38b);
+    assert_eq!(Action::SetTeam as u32, 0xc7d93c59);
+    assert_eq!(FactoryAction::Create as u32, 0xd0ef1a2f);
 
     assert_eq!(
         crate::SELECTOR_LOG_TRANSFER,
@@ -108,6 +135,49 @@ fn selectors() {
         crate::SELECTOR_LOG_APPROVAL,
         &Keccak256::digest(b"Approval(address,address,uint256)")[..]
     );
+
+    assert_eq!(
+        crate::PERMIT_TYPEHASH,
+        &Keccak256::digest(
+            b"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+        )[..]
+    );
+
+    assert_eq!(
+        crate::PERMIT_DOMAIN,
+        &Keccak256::digest(
+            b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+        )[..]
+    );
+}
+
+#[test]
+fn is_precompile_for_existing_assets() {
+    ExtBuilder::default().build().execute_with(|| {
+        let is_precompile = |address: H160| {
+            matches!(
+                precompiles().is_precompile(address, 0),
+                IsPrecompileResult::Answer {
+                    is_precompile: true,
+                    ..
+                }
+            )
+        };
+
+        assert!(!is_precompile(Account::AssetId(0u128).into()));
+
+        // Existing asset with a zero supply.
+        assert_ok!(Assets::force_create(
+            RuntimeOrigin::root(),
+            0u128,
+            Account::Alice.into(),
+            true,
+            1
+        ));
+        assert!(is_precompile(Account::AssetId(0u128).into()));
+        assert!(!is_precompile(Account::AssetId(1u128).into()));
+        assert!(!is_precompile(Account::Alice.into()));
+    });
 }
 
 #[test]
@@ -136,7 +206,7 @@ fn get_total_supply() {
                     Account::AssetId(0u128),
                     EvmDataWriter::new_with_selector(Action::TotalSupply).build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::total_supply()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(1000u64)).build());
         });
@@ -170,7 +240,7 @@ fn get_balances_known_user() {
                         .write(Address(Account::Alice.into()))
                         .build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::balance_of()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(1000u64)).build());
         });
@@ -198,7 +268,7 @@ fn get_balances_unknown_user() {
                         .write(Address(Account::Bob.into()))
                         .build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::balance_of()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(0u64)).build());
         });
@@ -289,7 +359,7 @@ fn approve_saturating() {
                         .write(Address(Account::Bob.into()))
                         .build(),
                 )
-                .expect_cost(0u64)
+                .expect_cost(weight_cost(<() as WeightInfo>::allowance()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(u128::MAX)).build());
         });
@@ -335,7 +405,7 @@ fn check_allowance_existing() {
                         .write(Address(Account::Bob.into()))
                         .build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::allowance()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(500u64)).build());
         });
@@ -364,7 +434,7 @@ fn check_allowance_not_existing() {
                         .write(Address(Account::Bob.into()))
                         .build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::allowance()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(0u64)).build());
         });
@@ -415,7 +485,7 @@ fn transfer() {
                         .write(Address(Account::Bob.into()))
                         .build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::balance_of()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(400)).build());
 
@@ -427,7 +497,7 @@ fn transfer() {
                         .write(Address(Account::Alice.into()))
                         .build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::balance_of()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(600)).build());
         });
@@ -462,11 +532,18 @@ fn transfer_not_enough_founds() {
                         .write(U256::from(50))
                         .build(),
                 )
+                // The balance read is charged even though nothing is dispatched.
+                .expect_cost(
+                    log_costs(3, 32).unwrap() + RuntimeHelper::<Runtime>::db_read_gas_cost(),
+                )
                 .execute_reverts(|output| {
-                    from_utf8(&output)
-                        .unwrap()
-                        .contains("Dispatched call failed with error: DispatchErrorWithPostInfo")
-                        && from_utf8(&output).unwrap().contains("BalanceLow")
+                    output
+                        == custom_error(
+                            CustomError::new(SELECTOR_ERROR_INSUFFICIENT_BALANCE)
+                                .write(Address(Account::Alice.into()))
+                                .write(U256::from(1))
+                                .write(U256::from(50)),
+                        )
                 });
         });
 }
@@ -539,7 +616,7 @@ fn transfer_from() {
                         .write(Address(Account::Alice.into()))
                         .build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::balance_of()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(600)).build());
 
@@ -551,7 +628,7 @@ fn transfer_from() {
                         .write(Address(Account::Bob.into()))
                         .build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::balance_of()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(0)).build());
 
@@ -563,7 +640,7 @@ fn transfer_from() {
                         .write(Address(Account::Charlie.into()))
                         .build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::balance_of()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(400)).build());
         });
@@ -641,10 +718,12 @@ fn transfer_from_non_incremental_approval() {
                 )
                 .execute_reverts(|output| {
                     output
-                        == b"Dispatched call failed with error: DispatchErrorWithPostInfo { \
-					post_info: PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes }, \
-					error: Module(ModuleError { index: 2, error: [10, 0, 0, 0], \
-					message: Some(\"Unapproved\") }) }"
+                        == custom_error(
+                            CustomError::new(SELECTOR_ERROR_INSUFFICIENT_ALLOWANCE)
+                                .write(Address(Account::Bob.into()))
+                                .write(U256::from(300))
+                                .write(U256::from(500)),
+                        )
                 });
         });
 }
@@ -690,12 +769,18 @@ fn transfer_from_above_allowance() {
                         .write(U256::from(400))
                         .build(),
                 )
+                // The allowance read is charged even though nothing is dispatched.
+                .expect_cost(
+                    log_costs(3, 32).unwrap() + RuntimeHelper::<Runtime>::db_read_gas_cost(),
+                )
                 .execute_reverts(|output| {
                     output
-                        == b"Dispatched call failed with error: DispatchErrorWithPostInfo { \
-					post_info: PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes }, \
-					error: Module(ModuleError { index: 2, error: [10, 0, 0, 0], \
-					message: Some(\"Unapproved\") }) }"
+                        == custom_error(
+                            CustomError::new(SELECTOR_ERROR_INSUFFICIENT_ALLOWANCE)
+                                .write(Address(Account::Bob.into()))
+                                .write(U256::from(300))
+                                .write(U256::from(400)),
+                        )
                 });
         });
 }
@@ -746,7 +831,7 @@ fn transfer_from_self() {
                         .write(Address(Account::Alice.into()))
                         .build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::balance_of()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(600)).build());
 
@@ -758,7 +843,7 @@ fn transfer_from_self() {
                         .write(Address(Account::Bob.into()))
                         .build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::balance_of()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(U256::from(400)).build());
         });
@@ -792,7 +877,7 @@ fn get_metadata() {
                     Account::AssetId(0u128),
                     EvmDataWriter::new_with_selector(Action::Name).build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::name()))
                 .expect_no_logs()
                 .execute_returns(
                     EvmDataWriter::new()
@@ -806,7 +891,7 @@ fn get_metadata() {
                     Account::AssetId(0u128),
                     EvmDataWriter::new_with_selector(Action::Symbol).build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::symbol()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write::<Bytes>("Test".into()).build());
 
@@ -816,7 +901,7 @@ fn get_metadata() {
                     Account::AssetId(0u128),
                     EvmDataWriter::new_with_selector(Action::Decimals).build(),
                 )
-                .expect_cost(0) // TODO: Test db read/write costs
+                .expect_cost(weight_cost(<() as WeightInfo>::decimals()))
                 .expect_no_logs()
                 .execute_returns(EvmDataWriter::new().write(12u8).build());
         });
@@ -840,7 +925,7 @@ fn minimum_balance_is_right() {
                 Account::AssetId(0u128),
                 EvmDataWriter::new_with_selector(Action::MinimumBalance).build(),
             )
-            .expect_cost(0) // TODO: Test db read/write costs
+            .expect_cost(weight_cost(<() as WeightInfo>::minimum_balance()))
             .expect_no_logs()
             .execute_returns(EvmDataWriter::new().write(expected_min_balance).build());
     });
@@ -981,3 +1066,554 @@ fn burn_non_admin_is_not_ok() {
             .execute_reverts(|output| from_utf8(&output).unwrap().contains("NoPermission"));
     });
 }
+
+/// Creates asset 0 named "TestToken" and mints 1000 of it to Alith.
+fn create_asset_for_permit() {
+    assert_ok!(Assets::force_create(
+        RuntimeOrigin::root(),
+        0u128,
+        Account::Alice.into(),
+        true,
+        1
+    ));
+    assert_ok!(Assets::force_set_metadata(
+        RuntimeOrigin::root(),
+        0u128,
+        b"TestToken".to_vec(),
+        b"Test".to_vec(),
+        12,
+        false
+    ));
+    assert_ok!(Assets::mint(
+        RuntimeOrigin::signed(Account::Alice),
+        0u128,
+        Account::Alith.into(),
+        1000
+    ));
+}
+
+// Signatures below were produced by Alith's key over the EIP-712 digest of
+// Permit(owner: Alith, spender: Bob, value: 500, nonce, deadline: U256::MAX)
+// in the domain ("TestToken", "1", chain id 0, 0xffffffff00000000000000000000000000000000).
+const PERMIT_NONCE_0: (u8, [u8; 32], [u8; 32]) = (
+    27,
+    hex_literal::hex!("a9c0c769501f9122e30dd526defd86d33cd4bcdcae284c3e207e37058c086030"),
+    hex_literal::hex!("5311444f1d59345504709efc9f0490a8fb713177ba2efaa302f34df0229b1420"),
+);
+const PERMIT_NONCE_1: (u8, [u8; 32], [u8; 32]) = (
+    27,
+    hex_literal::hex!("886b5393e197a58df8e23fe58885d6874cae29aaed5b2db88f93ecc23c8dc39f"),
+    hex_literal::hex!("3db29d49ca2e0aa6d62bdf6eea4e2caf6c9218e55168674c5bf76ceeb010e688"),
+);
+
+fn permit_input(value: U256, deadline: U256, signature: (u8, [u8; 32], [u8; 32])) -> Vec<u8> {
+    EvmDataWriter::new_with_selector(Action::Permit)
+        .write(Address(Account::Alith.into()))
+        .write(Address(Account::Bob.into()))
+        .write(value)
+        .write(deadline)
+        .write(signature.0)
+        .write(H256::from(signature.1))
+        .write(H256::from(signature.2))
+        .build()
+}
+
+#[test]
+fn domain_separator() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_asset_for_permit();
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::AssetId(0u128),
+                EvmDataWriter::new_with_selector(Action::DomainSeparator).build(),
+            )
+            .expect_no_logs()
+            .execute_returns(
+                EvmDataWriter::new()
+                    .write(H256::from(hex_literal::hex!(
+                        "c2b00daf7a42e42162192bc271c2fd4ecdf19e0d13cf9c1c30bce2877f7a448d"
+                    )))
+                    .build(),
+            );
+
+        assert_eq!(
+            Eip2612::<Runtime>::generate_permit(
+                Account::AssetId(0u128).into(),
+                0u128,
+                Account::Alith.into(),
+                Account::Bob.into(),
+                U256::from(500),
+                U256::zero(),
+                U256::MAX,
+            ),
+            hex_literal::hex!("fc7c7f8b64949f69bb789ade5a4b4563687c3f43c05e911bfa08c0c30ce0db18")
+        );
+    });
+}
+
+#[test]
+fn permit_valid() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_asset_for_permit();
+
+        precompiles()
+            .prepare_test(
+                Account::Charlie,
+                Account::AssetId(0u128),
+                permit_input(U256::from(500), U256::MAX, PERMIT_NONCE_0),
+            )
+            .expect_log(LogsBuilder::new(Account::AssetId(0u128).into()).log3(
+                SELECTOR_LOG_APPROVAL,
+                Account::Alith,
+                Account::Bob,
+                EvmDataWriter::new().write(U256::from(500)).build(),
+            ))
+            .execute_returns(vec![]);
+
+        precompiles()
+            .prepare_test(
+                Account::Charlie,
+                Account::AssetId(0u128),
+                EvmDataWriter::new_with_selector(Action::Allowance)
+                    .write(Address(Account::Alith.into()))
+                    .write(Address(Account::Bob.into()))
+                    .build(),
+            )
+            .execute_returns(EvmDataWriter::new().write(U256::from(500)).build());
+
+        precompiles()
+            .prepare_test(
+                Account::Charlie,
+                Account::AssetId(0u128),
+                EvmDataWriter::new_with_selector(Action::Nonces)
+                    .write(Address(Account::Alith.into()))
+                    .build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(U256::one()).build());
+
+        // The next permit must be signed over the next nonce.
+        precompiles()
+            .prepare_test(
+                Account::Charlie,
+                Account::AssetId(0u128),
+                permit_input(U256::from(500), U256::MAX, PERMIT_NONCE_1),
+            )
+            .execute_returns(vec![]);
+    });
+}
+
+#[test]
+fn permit_replay_is_rejected() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_asset_for_permit();
+
+        precompiles()
+            .prepare_test(
+                Account::Charlie,
+                Account::AssetId(0u128),
+                permit_input(U256::from(500), U256::MAX, PERMIT_NONCE_0),
+            )
+            .execute_returns(vec![]);
+
+        precompiles()
+            .prepare_test(
+                Account::Charlie,
+                Account::AssetId(0u128),
+                permit_input(U256::from(500), U256::MAX, PERMIT_NONCE_0),
+            )
+            .execute_reverts(|output| output == b"Invalid permit");
+    });
+}
+
+#[test]
+fn permit_invalid_value() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_asset_for_permit();
+
+        precompiles()
+            .prepare_test(
+                Account::Charlie,
+                Account::AssetId(0u128),
+                permit_input(U256::from(501), U256::MAX, PERMIT_NONCE_0),
+            )
+            .execute_reverts(|output| output == b"Invalid permit");
+
+        precompiles()
+            .prepare_test(
+                Account::Charlie,
+                Account::AssetId(0u128),
+                EvmDataWriter::new_with_selector(Action::Nonces)
+                    .write(Address(Account::Alith.into()))
+                    .build(),
+            )
+            .execute_returns(EvmDataWriter::new().write(U256::zero()).build());
+    });
+}
+
+#[test]
+fn permit_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        create_asset_for_permit();
+        // 10_000 seconds after epoch.
+        pallet_timestamp::Pallet::<Runtime>::set_timestamp(10_000_000);
+
+        precompiles()
+            .prepare_test(
+                Account::Charlie,
+                Account::AssetId(0u128),
+                permit_input(U256::from(500), U256::from(9_999), PERMIT_NONCE_0),
+            )
+            .execute_reverts(|output| output == b"Permit expired");
+    });
+}
+
+#[test]
+fn set_metadata_is_ok() {
+    ExtBuilder::default().build().execute_with(|| {
+        let asset_id = 0;
+        assert_ok!(Assets::force_create(
+            RuntimeOrigin::root(),
+            asset_id,
+            Account::Alice.into(),
+            true,
+            1,
+        ));
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::SetMetadata)
+                    .write::<Bytes>("TestToken".into())
+                    .write::<Bytes>("Test".into())
+                    .write(12u8)
+                    .build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(true).build());
+
+        assert_eq!(Assets::name(asset_id), b"TestToken".to_vec());
+        assert_eq!(Assets::symbol(asset_id), b"Test".to_vec());
+        assert_eq!(Assets::decimals(asset_id), 12);
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::ClearMetadata).build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(true).build());
+
+        assert!(Assets::name(asset_id).is_empty());
+    });
+}
+
+#[test]
+fn set_metadata_non_owner_is_not_ok() {
+    ExtBuilder::default().build().execute_with(|| {
+        let asset_id = 0;
+        assert_ok!(Assets::force_create(
+            RuntimeOrigin::root(),
+            asset_id,
+            Account::Alice.into(),
+            true,
+            1,
+        ));
+
+        precompiles()
+            .prepare_test(
+                Account::Bob,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::SetMetadata)
+                    .write::<Bytes>("TestToken".into())
+                    .write::<Bytes>("Test".into())
+                    .write(12u8)
+                    .build(),
+            )
+            .expect_no_logs()
+            .execute_reverts(|output| from_utf8(&output).unwrap().contains("NoPermission"));
+    });
+}
+
+#[test]
+fn freeze_and_thaw_account() {
+    ExtBuilder::default().build().execute_with(|| {
+        let asset_id = 0;
+        assert_ok!(Assets::force_create(
+            RuntimeOrigin::root(),
+            asset_id,
+            Account::Alice.into(),
+            true,
+            1,
+        ));
+        assert_ok!(Assets::mint(
+            RuntimeOrigin::signed(Account::Alice),
+            asset_id,
+            Account::Bob.into(),
+            100,
+        ));
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::Freeze)
+                    .write(Address(Account::Bob.into()))
+                    .build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(true).build());
+
+        precompiles()
+            .prepare_test(
+                Account::Bob,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::Transfer)
+                    .write(Address(Account::Charlie.into()))
+                    .write(U256::from(10))
+                    .build(),
+            )
+            .execute_reverts(|output| from_utf8(&output).unwrap().contains("Frozen"));
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::Thaw)
+                    .write(Address(Account::Bob.into()))
+                    .build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(true).build());
+
+        assert_ok!(Assets::transfer(
+            RuntimeOrigin::signed(Account::Bob),
+            asset_id,
+            Account::Charlie.into(),
+            10,
+        ));
+    });
+}
+
+#[test]
+fn freeze_non_freezer_is_not_ok() {
+    ExtBuilder::default().build().execute_with(|| {
+        let asset_id = 0;
+        assert_ok!(Assets::force_create(
+            RuntimeOrigin::root(),
+            asset_id,
+            Account::Alice.into(),
+            true,
+            1,
+        ));
+        assert_ok!(Assets::mint(
+            RuntimeOrigin::signed(Account::Alice),
+            asset_id,
+            Account::Bob.into(),
+            100,
+        ));
+
+        precompiles()
+            .prepare_test(
+                Account::Bob,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::Freeze)
+                    .write(Address(Account::Bob.into()))
+                    .build(),
+            )
+            .expect_no_logs()
+            .execute_reverts(|output| from_utf8(&output).unwrap().contains("NoPermission"));
+    });
+}
+
+#[test]
+fn freeze_and_thaw_asset() {
+    ExtBuilder::default().build().execute_with(|| {
+        let asset_id = 0;
+        assert_ok!(Assets::force_create(
+            RuntimeOrigin::root(),
+            asset_id,
+            Account::Alice.into(),
+            true,
+            1,
+        ));
+        assert_ok!(Assets::mint(
+            RuntimeOrigin::signed(Account::Alice),
+            asset_id,
+            Account::Bob.into(),
+            100,
+        ));
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::FreezeAsset).build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(true).build());
+
+        precompiles()
+            .prepare_test(
+                Account::Bob,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::Transfer)
+                    .write(Address(Account::Charlie.into()))
+                    .write(U256::from(10))
+                    .build(),
+            )
+            .execute_reverts(|output| from_utf8(&output).unwrap().contains("AssetNotLive"));
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::ThawAsset).build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(true).build());
+
+        assert_ok!(Assets::transfer(
+            RuntimeOrigin::signed(Account::Bob),
+            asset_id,
+            Account::Charlie.into(),
+            10,
+        ));
+    });
+}
+
+#[test]
+fn transfer_ownership_is_ok() {
+    ExtBuilder::default().build().execute_with(|| {
+        let asset_id = 0;
+        assert_ok!(Assets::force_create(
+            RuntimeOrigin::root(),
+            asset_id,
+            Account::Alice.into(),
+            true,
+            1,
+        ));
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::TransferOwnership)
+                    .write(Address(Account::Bob.into()))
+                    .build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(true).build());
+
+        // Alice is no longer the owner.
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::TransferOwnership)
+                    .write(Address(Account::Charlie.into()))
+                    .build(),
+            )
+            .execute_reverts(|output| from_utf8(&output).unwrap().contains("NoPermission"));
+    });
+}
+
+#[test]
+fn set_team_is_ok() {
+    ExtBuilder::default().build().execute_with(|| {
+        let asset_id = 0;
+        assert_ok!(Assets::force_create(
+            RuntimeOrigin::root(),
+            asset_id,
+            Account::Alice.into(),
+            true,
+            1,
+        ));
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::SetTeam)
+                    .write(Address(Account::Bob.into()))
+                    .write(Address(Account::Bob.into()))
+                    .write(Address(Account::Charlie.into()))
+                    .build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(true).build());
+
+        // Bob is now the issuer.
+        precompiles()
+            .prepare_test(
+                Account::Bob,
+                Account::AssetId(asset_id),
+                EvmDataWriter::new_with_selector(Action::Mint)
+                    .write(Address(Account::Bob.into()))
+                    .write(U256::from(42))
+                    .build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(true).build());
+
+        assert_eq!(Assets::balance(asset_id, &Account::Bob.into()), 42);
+    });
+}
+
+#[test]
+fn factory_create_is_ok() {
+    ExtBuilder::default().build().execute_with(|| {
+        let asset_id = 7u128;
+
+        FactoryPrecompileSet
+            .prepare_test(
+                Account::Alice,
+                FACTORY_ADDRESS,
+                EvmDataWriter::new_with_selector(FactoryAction::Create)
+                    .write(asset_id)
+                    .write(Address(Account::Alice.into()))
+                    .write(U256::from(1))
+                    .build(),
+            )
+            .expect_no_logs()
+            .execute_returns(
+                EvmDataWriter::new()
+                    .write(Address(Account::AssetId(asset_id).into()))
+                    .build(),
+            );
+
+        assert!(Assets::maybe_total_supply(asset_id).is_some());
+        assert_eq!(Assets::minimum_balance(asset_id), 1);
+
+        // The new asset is served by the precompile set before anything is minted.
+        let asset_address: H160 = Account::AssetId(asset_id).into();
+        assert_eq!(
+            pallet_evm::AccountCodes::<Runtime>::get(asset_address),
+            EVM_REVERT_CODE.to_vec()
+        );
+        assert!(matches!(
+            precompiles().is_precompile(asset_address, 0),
+            IsPrecompileResult::Answer {
+                is_precompile: true,
+                ..
+            }
+        ));
+
+        // The asset already exists.
+        FactoryPrecompileSet
+            .prepare_test(
+                Account::Alice,
+                FACTORY_ADDRESS,
+                EvmDataWriter::new_with_selector(FactoryAction::Create)
+                    .write(asset_id)
+                    .write(Address(Account::Alice.into()))
+                    .write(U256::from(1))
+                    .build(),
+            )
+            .execute_reverts(|output| from_utf8(&output).unwrap().contains("InUse"));
+    });
+}
@@ -0,0 +1,133 @@
+// This file is part of gpu.
+
+// Copyright (C) gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_evm_precompile_assets_erc20`, covering the functions reading the state of an
+//! asset and `permit`. The other functions dispatching a call of `pallet_assets` are charged the
+//! weight of that call.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_evm_precompile_assets_erc20.
+pub trait WeightInfo {
+	fn total_supply() -> Weight;
+	fn balance_of() -> Weight;
+	fn allowance() -> Weight;
+	fn name() -> Weight;
+	fn symbol() -> Weight;
+	fn decimals() -> Weight;
+	fn minimum_balance() -> Weight;
+	fn nonces() -> Weight;
+	fn domain_separator() -> Weight;
+	fn permit() -> Weight;
+}
+
+/// Estimated weights for pallet_evm_precompile_assets_erc20.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn total_supply() -> Weight {
+		Weight::from_parts(10_000_000, 3687)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	fn balance_of() -> Weight {
+		Weight::from_parts(13_000_000, 6308)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	fn allowance() -> Weight {
+		Weight::from_parts(14_000_000, 6322)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	fn name() -> Weight {
+		Weight::from_parts(13_000_000, 6314)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	fn symbol() -> Weight {
+		Weight::from_parts(12_000_000, 6314)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	fn decimals() -> Weight {
+		Weight::from_parts(12_000_000, 6314)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	fn minimum_balance() -> Weight {
+		Weight::from_parts(10_000_000, 3687)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	fn nonces() -> Weight {
+		Weight::from_parts(12_000_000, 4236)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	fn domain_separator() -> Weight {
+		Weight::from_parts(17_000_000, 6314)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	fn permit() -> Weight {
+		Weight::from_parts(86_000_000, 14859)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn total_supply() -> Weight {
+		Weight::from_parts(10_000_000, 3687)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	fn balance_of() -> Weight {
+		Weight::from_parts(13_000_000, 6308)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	fn allowance() -> Weight {
+		Weight::from_parts(14_000_000, 6322)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	fn name() -> Weight {
+		Weight::from_parts(13_000_000, 6314)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	fn symbol() -> Weight {
+		Weight::from_parts(12_000_000, 6314)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	fn decimals() -> Weight {
+		Weight::from_parts(12_000_000, 6314)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	fn minimum_balance() -> Weight {
+		Weight::from_parts(10_000_000, 3687)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	fn nonces() -> Weight {
+		Weight::from_parts(12_000_000, 4236)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	fn domain_separator() -> Weight {
+		Weight::from_parts(17_000_000, 6314)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	fn permit() -> Weight {
+		Weight::from_parts(86_000_000, 14859)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+}
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// Interface to the native currency ERC20 precompiled contract
+/// Predeployed at the address 0x0000000000000000000000000000000000005008
+
+/// @title ERC20 interface of the native currency
+/// @dev Balances and transfers are those of pallet-balances, allowances are stored by the
+/// precompile itself.
+interface IERC20 {
+    /// @dev Returns the name of the token.
+    function name() external view returns (string memory);
+
+    /// @dev Returns the symbol of the token.
+    function symbol() external view returns (string memory);
+
+    /// @dev Returns the decimals places of the token.
+    function decimals() external view returns (uint8);
+
+    /// @dev Total issuance of the native currency.
+    function totalSupply() external view returns (uint256);
+
+    /// @dev Transferable balance of `who`.
+    function balanceOf(address who) external view returns (uint256);
+
+    /// @dev Amount `spender` is still allowed to withdraw from `owner`.
+    function allowance(address owner, address spender) external view returns (uint256);
+
+    /// @dev Transfer `value` to `to` from the caller.
+    function transfer(address to, uint256 value) external returns (bool);
+
+    /// @dev Allow `spender` to withdraw up to `value` from the caller, overriding any previous
+    /// allowance.
+    function approve(address spender, uint256 value) external returns (bool);
+
+    /// @dev Transfer `value` from `from` to `to` using the allowance given to the caller.
+    function transferFrom(address from, address to, uint256 value) external returns (bool);
+
+    /// @dev Emitted when `value` is moved from `from` to `to`.
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    /// @dev Emitted when `owner` sets the allowance of `spender` to `value`.
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+}
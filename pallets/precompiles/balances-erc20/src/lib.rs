@@ -0,0 +1,344 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! ERC20 interface over the native currency held in `pallet_balances`.
+//!
+//! Balances and transfers go through the pallet, approvals are kept in the precompile's own
+//! storage since `pallet_balances` has no notion of allowance.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use fp_evm::{PrecompileHandle, PrecompileOutput};
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	sp_runtime::traits::StaticLookup,
+	storage::types::{StorageDoubleMap, ValueQuery},
+	traits::StorageInstance,
+	Blake2_128Concat,
+};
+use pallet_evm::{AddressMapping, Precompile};
+use precompile_utils::{
+	keccak256, revert, succeed, Address, Bytes, EvmData, EvmDataWriter, EvmResult,
+	FunctionModifier, LogExt, LogsBuilder, PrecompileHandleExt, RuntimeHelper,
+};
+use sp_core::{H160, U256};
+use sp_runtime::traits::Bounded;
+use sp_std::{
+	convert::{TryFrom, TryInto},
+	marker::PhantomData,
+};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Solidity selector of the Transfer log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_TRANSFER: [u8; 32] = keccak256!("Transfer(address,address,uint256)");
+
+/// Solidity selector of the Approval log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_APPROVAL: [u8; 32] = keccak256!("Approval(address,address,uint256)");
+
+/// Alias for the Balance type for the provided Runtime.
+pub type BalanceOf<Runtime> = <Runtime as pallet_balances::Config>::Balance;
+
+/// Storage prefix of the approvals, placed under a pallet prefix no pallet uses.
+pub struct ApprovesPrefix;
+
+impl StorageInstance for ApprovesPrefix {
+	const STORAGE_PREFIX: &'static str = "Approves";
+
+	fn pallet_prefix() -> &'static str {
+		"BalancesErc20"
+	}
+}
+
+/// Allowances given by an owner account to a spender address.
+pub type ApprovesStorage<Runtime> = StorageDoubleMap<
+	ApprovesPrefix,
+	Blake2_128Concat,
+	<Runtime as frame_system::Config>::AccountId,
+	Blake2_128Concat,
+	H160,
+	BalanceOf<Runtime>,
+	ValueQuery,
+>;
+
+#[precompile_utils::generate_function_selector]
+#[event("Transfer(address indexed,address indexed,uint256)")]
+#[event("Approval(address indexed,address indexed,uint256)")]
+#[derive(Debug, PartialEq)]
+pub enum Action {
+	#[view]
+	#[returns("uint256")]
+	TotalSupply = "totalSupply()",
+	#[view]
+	#[returns("uint256")]
+	BalanceOf = "balanceOf(address)",
+	#[view]
+	#[returns("uint256")]
+	Allowance = "allowance(address,address)",
+	#[returns("bool")]
+	Transfer = "transfer(address,uint256)",
+	#[returns("bool")]
+	Approve = "approve(address,uint256)",
+	#[returns("bool")]
+	TransferFrom = "transferFrom(address,address,uint256)",
+	#[view]
+	#[returns("string")]
+	Name = "name()",
+	#[view]
+	#[returns("string")]
+	Symbol = "symbol()",
+	#[view]
+	#[returns("uint8")]
+	Decimals = "decimals()",
+}
+
+/// Token metadata of the native currency.
+///
+/// The runtime should return the same values the chain spec advertises as `tokenSymbol` and
+/// `tokenDecimals`, so wallets display the token consistently on both sides.
+pub trait Erc20Metadata {
+	/// Returns the name of the token.
+	fn name() -> &'static str;
+
+	/// Returns the symbol of the token.
+	fn symbol() -> &'static str;
+
+	/// Returns the decimals places of the token.
+	fn decimals() -> u8;
+}
+
+/// A precompile exposing the native currency as an ERC20 token.
+pub struct Erc20BalancesPrecompile<Runtime, Metadata>(PhantomData<(Runtime, Metadata)>);
+
+impl<Runtime, Metadata> Precompile for Erc20BalancesPrecompile<Runtime, Metadata>
+where
+	Runtime: pallet_balances::Config + pallet_evm::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<pallet_balances::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256> + EvmData,
+	Metadata: Erc20Metadata,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		log::trace!(target: "balances-erc20-precompile", "In balances erc20 precompile");
+
+		let selector = handle.read_selector()?;
+
+		handle.check_function_modifier(match selector {
+			Action::Approve | Action::Transfer | Action::TransferFrom =>
+				FunctionModifier::NonPayable,
+			_ => FunctionModifier::View,
+		})?;
+
+		match selector {
+			Action::TotalSupply => Self::total_supply(handle),
+			Action::BalanceOf => Self::balance_of(handle),
+			Action::Allowance => Self::allowance(handle),
+			Action::Approve => Self::approve(handle),
+			Action::Transfer => Self::transfer(handle),
+			Action::TransferFrom => Self::transfer_from(handle),
+			Action::Name => Self::name(handle),
+			Action::Symbol => Self::symbol(handle),
+			Action::Decimals => Self::decimals(handle),
+		}
+	}
+}
+
+impl<Runtime, Metadata> Erc20BalancesPrecompile<Runtime, Metadata>
+where
+	Runtime: pallet_balances::Config + pallet_evm::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<pallet_balances::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256> + EvmData,
+	Metadata: Erc20Metadata,
+{
+	fn total_supply(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		// Storage item: TotalIssuance: Balance(16)
+		handle.record_db_read::<Runtime>(16)?;
+
+		let amount: U256 = pallet_balances::Pallet::<Runtime>::total_issuance().into();
+
+		Ok(succeed(EvmDataWriter::new().write(amount).build()))
+	}
+
+	fn balance_of(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		// Storage item: Account:
+		// Blake2_128(16) + AccountId(32) + AccountInfo(4 * Index(4) + AccountData(4 * Balance(16)))
+		handle.record_db_read::<Runtime>(128)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let owner: H160 = input.read::<Address>()?.into();
+
+		let amount: U256 = {
+			let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner);
+			pallet_balances::Pallet::<Runtime>::usable_balance(&owner).into()
+		};
+
+		Ok(succeed(EvmDataWriter::new().write(amount).build()))
+	}
+
+	fn allowance(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		// Storage item: Approves:
+		// 2 * Blake2_128(16) + AccountId(32) + H160(20) + Balance(16)
+		handle.record_db_read::<Runtime>(100)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let owner: H160 = input.read::<Address>()?.into();
+		let spender: H160 = input.read::<Address>()?.into();
+
+		let amount: U256 = {
+			let owner: Runtime::AccountId = Runtime::AddressMapping::into_account_id(owner);
+			ApprovesStorage::<Runtime>::get(owner, spender).into()
+		};
+
+		Ok(succeed(EvmDataWriter::new().write(amount).build()))
+	}
+
+	fn approve(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let spender: H160 = input.read::<Address>()?.into();
+		let amount: U256 = input.read()?;
+
+		{
+			let owner = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			// Amount saturate if too high.
+			let amount: BalanceOf<Runtime> =
+				amount.try_into().unwrap_or_else(|_| Bounded::max_value());
+
+			ApprovesStorage::<Runtime>::insert(owner, spender, amount);
+		}
+
+		LogsBuilder::new(handle.context().address)
+			.log3(
+				SELECTOR_LOG_APPROVAL,
+				handle.context().caller,
+				spender,
+				EvmDataWriter::new().write(amount).build(),
+			)
+			.record(handle)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn transfer(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_log_costs_manual(3, 32)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let to: H160 = input.read::<Address>()?.into();
+		let amount = input.read::<BalanceOf<Runtime>>()?;
+
+		{
+			let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+			let to = Runtime::AddressMapping::into_account_id(to);
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(origin).into(),
+				pallet_balances::Call::<Runtime>::transfer_allow_death {
+					dest: Runtime::Lookup::unlookup(to),
+					value: amount,
+				},
+			)?;
+		}
+
+		LogsBuilder::new(handle.context().address)
+			.log3(
+				SELECTOR_LOG_TRANSFER,
+				handle.context().caller,
+				to,
+				EvmDataWriter::new().write(amount).build(),
+			)
+			.record(handle)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn transfer_from(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_log_costs_manual(3, 32)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+
+		let from: H160 = input.read::<Address>()?.into();
+		let to: H160 = input.read::<Address>()?.into();
+		let amount = input.read::<BalanceOf<Runtime>>()?;
+
+		{
+			let caller: H160 = handle.context().caller;
+			let from_account: Runtime::AccountId = Runtime::AddressMapping::into_account_id(from);
+			let to: Runtime::AccountId = Runtime::AddressMapping::into_account_id(to);
+
+			// If caller is "from", it can spend as much as it wants from its own balance.
+			if caller != from {
+				// Allowance read and write
+				handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+				handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+
+				ApprovesStorage::<Runtime>::try_mutate(&from_account, caller, |allowance| {
+					if *allowance < amount {
+						return Err(revert("trying to spend more than allowed"))
+					}
+					*allowance -= amount;
+					Ok(())
+				})?;
+			}
+
+			// Dispatch call (if enough gas).
+			RuntimeHelper::<Runtime>::try_dispatch(
+				handle,
+				Some(from_account).into(),
+				pallet_balances::Call::<Runtime>::transfer_allow_death {
+					dest: Runtime::Lookup::unlookup(to),
+					value: amount,
+				},
+			)?;
+		}
+
+		LogsBuilder::new(handle.context().address)
+			.log3(SELECTOR_LOG_TRANSFER, from, to, EvmDataWriter::new().write(amount).build())
+			.record(handle)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn name(_handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		Ok(succeed(EvmDataWriter::new().write::<Bytes>(Metadata::name().into()).build()))
+	}
+
+	fn symbol(_handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		Ok(succeed(EvmDataWriter::new().write::<Bytes>(Metadata::symbol().into()).build()))
+	}
+
+	fn decimals(_handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		Ok(succeed(EvmDataWriter::new().write::<u8>(Metadata::decimals()).build()))
+	}
+}
@@ -0,0 +1,224 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Testing utilities.
+
+use super::*;
+
+use fp_evm::IsPrecompileResult;
+use frame_support::{
+    construct_runtime, parameter_types,
+    traits::{ConstU32, ConstU64, Everything},
+    weights::Weight,
+};
+use pallet_evm::{EnsureAddressNever, EnsureAddressRoot, PrecompileResult, PrecompileSet};
+use sp_core::{crypto::AccountId32, H160, H256};
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage,
+};
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+pub type Block = frame_system::mocking::MockBlock<Runtime>;
+
+pub const PRECOMPILE_ADDRESS: H160 = H160::repeat_byte(0x50);
+
+/// Maps an H160 into the first 20 bytes of an `AccountId32`, which keeps test accounts readable.
+pub struct MockAddressMapping;
+
+impl AddressMapping<AccountId> for MockAddressMapping {
+    fn into_account_id(address: H160) -> AccountId {
+        let mut data = [0u8; 32];
+        data[0..20].copy_from_slice(&address[..]);
+        AccountId::from(data)
+    }
+}
+
+pub fn account(address: impl Into<H160>) -> AccountId {
+    MockAddressMapping::into_account_id(address.into())
+}
+
+pub struct MockMetadata;
+
+impl Erc20Metadata for MockMetadata {
+    fn name() -> &'static str {
+        "Mock token"
+    }
+
+    fn symbol() -> &'static str {
+        "MOCK"
+    }
+
+    fn decimals() -> u8 {
+        18
+    }
+}
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for Runtime {
+    type BaseCallFilter = Everything;
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type Nonce = u64;
+    type Block = Block;
+    type RuntimeCall = RuntimeCall;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type BlockWeights = ();
+    type BlockLength = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TestPrecompileSet<R>(PhantomData<R>);
+
+impl<R> PrecompileSet for TestPrecompileSet<R>
+where
+    R: pallet_evm::Config,
+    Erc20BalancesPrecompile<R, MockMetadata>: Precompile,
+{
+    fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+        match handle.code_address() {
+            a if a == PRECOMPILE_ADDRESS => {
+                Some(Erc20BalancesPrecompile::<R, MockMetadata>::execute(handle))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
+        IsPrecompileResult::Answer {
+            is_precompile: address == PRECOMPILE_ADDRESS,
+            extra_cost: 0,
+        }
+    }
+}
+
+parameter_types! {
+    pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Runtime {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u128 = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+    type MaxReserves = ();
+    type ReserveIdentifier = ();
+    type MaxLocks = ConstU32<50>;
+    type Balance = Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type RuntimeHoldReason = ();
+    type FreezeIdentifier = ();
+    type MaxHolds = ();
+    type MaxFreezes = ();
+}
+
+parameter_types! {
+    pub const PrecompilesValue: TestPrecompileSet<Runtime> =
+        TestPrecompileSet(PhantomData);
+    pub const WeightPerGas: Weight = Weight::from_parts(1, 0);
+}
+
+impl pallet_evm::Config for Runtime {
+    type FeeCalculator = ();
+    type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+    type WeightPerGas = WeightPerGas;
+    type CallOrigin = EnsureAddressRoot<AccountId>;
+    type WithdrawOrigin = EnsureAddressNever<AccountId>;
+    type AddressMapping = MockAddressMapping;
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type Runner = pallet_evm::runner::stack::Runner<Self>;
+    type PrecompilesType = TestPrecompileSet<Self>;
+    type PrecompilesValue = PrecompilesValue;
+    type Timestamp = Timestamp;
+    type ChainId = ();
+    type OnChargeTransaction = ();
+    type BlockGasLimit = ();
+    type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+    type OnCreate = ();
+    type FindAuthor = ();
+    type WeightInfo = ();
+    type GasLimitPovSizeRatio = ConstU64<4>;
+}
+
+// Configure a mock runtime to test the pallet.
+construct_runtime! {
+    pub enum Runtime
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Evm: pallet_evm,
+        Timestamp: pallet_timestamp,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ExtBuilder {
+    balances: Vec<(AccountId, Balance)>,
+}
+
+impl ExtBuilder {
+    pub(crate) fn with_balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
+        self.balances = balances;
+        self
+    }
+
+    pub(crate) fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::<Runtime>::default()
+            .build_storage()
+            .expect("Frame system builds valid default genesis config");
+
+        pallet_balances::GenesisConfig::<Runtime> {
+            balances: self.balances,
+        }
+        .assimilate_storage(&mut t)
+        .expect("Pallet balances storage can be assimilated");
+
+        let mut ext = sp_io::TestExternalities::new(t);
+        ext.execute_with(|| System::set_block_number(1));
+        ext
+    }
+}
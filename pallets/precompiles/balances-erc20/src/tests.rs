@@ -0,0 +1,367 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::mock::*;
+use crate::*;
+
+use precompile_utils::testing::*;
+
+fn precompiles() -> TestPrecompileSet<Runtime> {
+    PrecompilesValue::get()
+}
+
+#[test]
+fn selector_less_than_four_bytes() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Alice, PRECOMPILE_ADDRESS, vec![1u8, 2u8, 3u8])
+            .execute_reverts(|output| output == b"tried to parse selector out of bounds");
+    });
+}
+
+#[test]
+fn no_selector_exists_but_length_is_right() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Alice, PRECOMPILE_ADDRESS, vec![1u8, 2u8, 3u8, 4u8])
+            .execute_reverts(|output| output == b"unknown selector");
+    });
+}
+
+#[test]
+fn total_supply_works() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000), (account(Bob), 500)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::TotalSupply).build(),
+                )
+                .expect_no_logs()
+                .execute_returns(EvmDataWriter::new().write(U256::from(1500)).build());
+        });
+}
+
+#[test]
+fn balance_of_works() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Bob,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::BalanceOf)
+                        .write(Address(Alice.into()))
+                        .build(),
+                )
+                .expect_no_logs()
+                .execute_returns(EvmDataWriter::new().write(U256::from(1000)).build());
+
+            precompiles()
+                .prepare_test(
+                    Bob,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::BalanceOf)
+                        .write(Address(Charlie.into()))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(U256::zero()).build());
+        });
+}
+
+#[test]
+fn transfer_works() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Transfer)
+                        .write(Address(Bob.into()))
+                        .write(U256::from(400))
+                        .build(),
+                )
+                .expect_log(LogsBuilder::new(PRECOMPILE_ADDRESS).log3(
+                    SELECTOR_LOG_TRANSFER,
+                    H160::from(Alice),
+                    H160::from(Bob),
+                    EvmDataWriter::new().write(U256::from(400)).build(),
+                ))
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            assert_eq!(Balances::free_balance(account(Alice)), 600);
+            assert_eq!(Balances::free_balance(account(Bob)), 400);
+        });
+}
+
+#[test]
+fn transfer_not_enough_funds() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Transfer)
+                        .write(Address(Bob.into()))
+                        .write(U256::from(1400))
+                        .build(),
+                )
+                .execute_reverts(|output| {
+                    output.starts_with(b"Dispatched call failed with error: ")
+                });
+        });
+}
+
+#[test]
+fn approve_works() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Approve)
+                        .write(Address(Bob.into()))
+                        .write(U256::from(500))
+                        .build(),
+                )
+                .expect_log(LogsBuilder::new(PRECOMPILE_ADDRESS).log3(
+                    SELECTOR_LOG_APPROVAL,
+                    H160::from(Alice),
+                    H160::from(Bob),
+                    EvmDataWriter::new().write(U256::from(500)).build(),
+                ))
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Allowance)
+                        .write(Address(Alice.into()))
+                        .write(Address(Bob.into()))
+                        .build(),
+                )
+                .expect_no_logs()
+                .execute_returns(EvmDataWriter::new().write(U256::from(500)).build());
+        });
+}
+
+#[test]
+fn approve_overwrites_previous_allowance() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            for amount in [500u32, 200u32] {
+                precompiles()
+                    .prepare_test(
+                        Alice,
+                        PRECOMPILE_ADDRESS,
+                        EvmDataWriter::new_with_selector(Action::Approve)
+                            .write(Address(Bob.into()))
+                            .write(U256::from(amount))
+                            .build(),
+                    )
+                    .execute_returns(EvmDataWriter::new().write(true).build());
+            }
+
+            assert_eq!(
+                ApprovesStorage::<Runtime>::get(account(Alice), H160::from(Bob)),
+                200
+            );
+        });
+}
+
+#[test]
+fn approve_saturating() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Approve)
+                        .write(Address(Bob.into()))
+                        .write(U256::MAX)
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Allowance)
+                        .write(Address(Alice.into()))
+                        .write(Address(Bob.into()))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(U256::from(u128::MAX)).build());
+        });
+}
+
+#[test]
+fn transfer_from_works() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Approve)
+                        .write(Address(Bob.into()))
+                        .write(U256::from(500))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            precompiles()
+                .prepare_test(
+                    Bob,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::TransferFrom)
+                        .write(Address(Alice.into()))
+                        .write(Address(Charlie.into()))
+                        .write(U256::from(400))
+                        .build(),
+                )
+                .expect_log(LogsBuilder::new(PRECOMPILE_ADDRESS).log3(
+                    SELECTOR_LOG_TRANSFER,
+                    H160::from(Alice),
+                    H160::from(Charlie),
+                    EvmDataWriter::new().write(U256::from(400)).build(),
+                ))
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            assert_eq!(Balances::free_balance(account(Alice)), 600);
+            assert_eq!(Balances::free_balance(account(Charlie)), 400);
+            assert_eq!(
+                ApprovesStorage::<Runtime>::get(account(Alice), H160::from(Bob)),
+                100
+            );
+        });
+}
+
+#[test]
+fn transfer_from_above_allowance() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Approve)
+                        .write(Address(Bob.into()))
+                        .write(U256::from(300))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            precompiles()
+                .prepare_test(
+                    Bob,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::TransferFrom)
+                        .write(Address(Alice.into()))
+                        .write(Address(Charlie.into()))
+                        .write(U256::from(400))
+                        .build(),
+                )
+                .execute_reverts(|output| output == b"trying to spend more than allowed");
+
+            assert_eq!(Balances::free_balance(account(Alice)), 1000);
+        });
+}
+
+#[test]
+fn transfer_from_self() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::TransferFrom)
+                        .write(Address(Alice.into()))
+                        .write(Address(Bob.into()))
+                        .write(U256::from(400))
+                        .build(),
+                )
+                .expect_log(LogsBuilder::new(PRECOMPILE_ADDRESS).log3(
+                    SELECTOR_LOG_TRANSFER,
+                    H160::from(Alice),
+                    H160::from(Bob),
+                    EvmDataWriter::new().write(U256::from(400)).build(),
+                ))
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            assert_eq!(Balances::free_balance(account(Bob)), 400);
+        });
+}
+
+#[test]
+fn get_metadata() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::Name).build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write::<Bytes>("Mock token".into()).build());
+
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::Symbol).build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write::<Bytes>("MOCK".into()).build());
+
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::Decimals).build(),
+            )
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(18u8).build());
+    });
+}
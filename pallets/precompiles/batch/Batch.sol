@@ -52,7 +52,8 @@ interface Batch {
     /// @dev Batch multiple calls into a single transaction.
     /// All calls are performed from the address calling this precompile.
     ///
-    /// In case of one subcall reverting, the entire batch will revert.
+    /// In case of one subcall reverting, the entire batch will revert with `BatchSubcallFailed`,
+    /// or with `BatchOutOfGas` if it ran out of gas.
     ///
     /// @param to List of addresses to call.
     /// @param value List of values for each subcall. If array is shorter than `to` then additional
@@ -74,4 +75,10 @@ interface Batch {
     /// Emitted when a subcall fails.
     event SubcallFailed(uint256 index);
 
+    /// Raised by `batchAll` when the subcall `index` reverts or fails, with what it returned.
+    error BatchSubcallFailed(uint256 index, bytes output);
+
+    /// Raised by `batchAll` when the gas left can't pay for the subcall `index`.
+    error BatchOutOfGas(uint256 index);
+
 }
@@ -0,0 +1,95 @@
+// This file is part of gpu.
+
+// Copyright (C) gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Benchmarks of the batch precompile, making `x` subcalls with no value nor call data.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use precompile_utils::benchmarking::BenchmarkHandle;
+
+pub struct Pallet<T: Config>(PhantomData<T>);
+
+pub trait Config: pallet_evm::Config {}
+
+/// Handle of a call of `action` with `subcalls` subcalls.
+fn batch_handle(action: Action, subcalls: u32) -> BenchmarkHandle {
+	let to: Vec<_> = (0..subcalls).map(|i| Address(H160::from_low_u64_be(i.into()))).collect();
+	let input = EvmDataWriter::new_with_selector(action)
+		.write(to)
+		.write(Vec::<U256>::new())
+		.write(Vec::<Bytes>::new())
+		.write(Vec::<u64>::new())
+		.build();
+	BenchmarkHandle::new(H160::zero(), H160::zero(), input)
+}
+
+#[benchmarks(
+	where <T as frame_system::Config>::RuntimeCall:
+		Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo
+)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn batch_some(x: Linear<0, ARRAY_LIMIT>) {
+		let mut handle = batch_handle(Action::BatchSome, x);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute::<BatchPrecompile<T>>();
+		}
+
+		assert!(result.is_ok());
+		assert_eq!(handle.subcalls, x as usize);
+	}
+
+	#[benchmark]
+	fn batch_some_until_failure(x: Linear<0, ARRAY_LIMIT>) {
+		let mut handle = batch_handle(Action::BatchSomeUntilFailure, x);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute::<BatchPrecompile<T>>();
+		}
+
+		assert!(result.is_ok());
+		assert_eq!(handle.subcalls, x as usize);
+	}
+
+	#[benchmark]
+	fn batch_all(x: Linear<0, ARRAY_LIMIT>) {
+		let mut handle = batch_handle(Action::BatchAll, x);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute::<BatchPrecompile<T>>();
+		}
+
+		assert!(result.is_ok());
+		assert_eq!(handle.subcalls, x as usize);
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::ExtBuilder::default().build(),
+		crate::mock::Runtime
+	);
+}
@@ -46,10 +46,15 @@ use precompile_utils::{bytes::BoundedBytes, data::BoundedVec, *};
 use sp_core::{H160, U256};
 use sp_std::{iter::repeat, marker::PhantomData, vec, vec::Vec};
 
-// #[cfg(test)]
-// mod mock;
-// #[cfg(test)]
-// mod tests;
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Mode {
@@ -60,6 +65,10 @@ pub enum Mode {
 
 pub const LOG_SUBCALL_SUCCEEDED: [u8; 32] = keccak256!("SubcallSucceeded(uint256)");
 pub const LOG_SUBCALL_FAILED: [u8; 32] = keccak256!("SubcallFailed(uint256)");
+/// Error `batchAll` reverts with when a subcall reverts or fails, carrying its index and output.
+pub const ERROR_SUBCALL_FAILED: [u8; 32] = keccak256!("BatchSubcallFailed(uint256,bytes)");
+/// Error `batchAll` reverts with when the gas left can't pay for a subcall, carrying its index.
+pub const ERROR_OUT_OF_GAS: [u8; 32] = keccak256!("BatchOutOfGas(uint256)");
 pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
 pub const ARRAY_LIMIT: u32 = 2u32.pow(9);
 
@@ -76,21 +85,40 @@ fn log_subcall_failed(address: impl Into<H160>, index: usize) -> Log {
 		.log1(LOG_SUBCALL_FAILED, data::encode_event_data(U256::from(index)))
 }
 
+fn subcall_failed(index: usize, output: Vec<u8>) -> PrecompileFailure {
+	CustomError::new(ERROR_SUBCALL_FAILED)
+		.write(U256::from(index))
+		.write(Bytes(output))
+		.into()
+}
+
+fn out_of_gas(index: usize) -> PrecompileFailure {
+	CustomError::new(ERROR_OUT_OF_GAS).write(U256::from(index)).into()
+}
+
 #[precompile_utils::generate_function_selector]
+#[event("SubcallSucceeded(uint256)")]
+#[event("SubcallFailed(uint256)")]
+#[error("BatchSubcallFailed(uint256,bytes)")]
+#[error("BatchOutOfGas(uint256)")]
 #[derive(Debug, PartialEq)]
 pub enum Action {
+	#[returns("bool")]
 	BatchSome = "batchSome(address[],uint256[],bytes[],uint64[])",
+	#[returns("bool")]
 	BatchSomeUntilFailure = "batchSomeUntilFailure(address[],uint256[],bytes[],uint64[])",
+	#[returns("bool")]
 	BatchAll = "batchAll(address[],uint256[],bytes[],uint64[])",
 }
 
 /// Batch precompile.
 #[derive(Debug, Clone)]
-pub struct BatchPrecompile<Runtime>(PhantomData<Runtime>);
+pub struct BatchPrecompile<Runtime, Weights = ()>(PhantomData<(Runtime, Weights)>);
 
-impl<Runtime> Precompile for BatchPrecompile<Runtime>
+impl<Runtime, Weights> Precompile for BatchPrecompile<Runtime, Weights>
 where
 	Runtime: pallet_evm::Config,
+	Weights: WeightInfo,
 	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
 {
 	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
@@ -107,9 +135,10 @@ where
 // No funds are transfered to the precompile address.
 // Transfers will directly be made on the behalf of the user by the precompile.
 // #[precompile_utils::precompile]
-impl<Runtime> BatchPrecompile<Runtime>
+impl<Runtime, Weights> BatchPrecompile<Runtime, Weights>
 where
 	Runtime: pallet_evm::Config,
+	Weights: WeightInfo,
 	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
 {
 	fn batch_some(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
@@ -156,7 +185,15 @@ where
 		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
 		gas_limit: BoundedVec<u64, GetArrayLimit>,
 	) -> EvmResult<PrecompileOutput> {
-		let addresses = Vec::from(to).into_iter().enumerate();
+		let to = Vec::from(to);
+		let weight = match mode {
+			Mode::BatchSome => Weights::batch_some(to.len() as u32),
+			Mode::BatchSomeUntilFailure => Weights::batch_some_until_failure(to.len() as u32),
+			Mode::BatchAll => Weights::batch_all(to.len() as u32),
+		};
+		handle.record_weight::<Runtime>(weight)?;
+
+		let addresses = to.into_iter().enumerate();
 		let values = Vec::from(value).into_iter().map(|x| Some(x)).chain(repeat(None));
 		let calls_data =
 			Vec::from(call_data).into_iter().map(|x| Some(x.into())).chain(repeat(None));
@@ -170,9 +207,7 @@ where
 		).chain(repeat(None));
 
 		// Cost of batch log. (doesn't change when index changes)
-		let log_cost = log_subcall_failed(handle.code_address(), 0)
-			.compute_cost()
-			.map_err(|_| revert("Failed to compute log cost"))?;
+		let log_cost = log_subcall_failed(handle.code_address(), 0).compute_cost()?;
 
 		for ((i, address), (value, (call_data, gas_limit))) in
 			addresses.zip(values.zip(calls_data.zip(gas_limits)))
@@ -199,8 +234,7 @@ where
 
 			let forwarded_gas = match (remaining_gas.checked_sub(log_cost), mode) {
 				(Some(remaining), _) => remaining,
-				(None, Mode::BatchAll) =>
-					return Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas }),
+				(None, Mode::BatchAll) => return Err(out_of_gas(i)),
 				(None, _) => return Ok(succeed(EvmDataWriter::new().write(true).build())),
 			};
 
@@ -215,10 +249,7 @@ where
 					log.record(handle)?;
 
 					match mode {
-						Mode::BatchAll =>
-							return Err(PrecompileFailure::Error {
-								exit_status: ExitError::OutOfGas,
-							}),
+						Mode::BatchAll => return Err(out_of_gas(i)),
 						Mode::BatchSomeUntilFailure =>
 							return Ok(succeed(EvmDataWriter::new().write(true).build())),
 						Mode::BatchSome => continue,
@@ -236,10 +267,7 @@ where
 						log.record(handle)?;
 
 						match mode {
-							Mode::BatchAll =>
-								return Err(PrecompileFailure::Error {
-									exit_status: ExitError::OutOfGas,
-								}),
+							Mode::BatchAll => return Err(out_of_gas(i)),
 							Mode::BatchSomeUntilFailure =>
 								return Ok(succeed(EvmDataWriter::new().write(true).build())),
 							Mode::BatchSome => continue,
@@ -274,11 +302,11 @@ where
 				(_, ExitReason::Fatal(exit_status)) =>
 					return Err(PrecompileFailure::Fatal { exit_status }),
 
-				// BatchAll : Reverts and errors are immediatly forwarded.
-				(Mode::BatchAll, ExitReason::Revert(exit_status)) =>
-					return Err(PrecompileFailure::Revert { exit_status, output }),
-				(Mode::BatchAll, ExitReason::Error(exit_status)) =>
-					return Err(PrecompileFailure::Error { exit_status }),
+				// BatchAll : Reverts and errors revert the batch with the index of the subcall.
+				(Mode::BatchAll, ExitReason::Error(ExitError::OutOfGas)) =>
+					return Err(out_of_gas(i)),
+				(Mode::BatchAll, ExitReason::Revert(_) | ExitReason::Error(_)) =>
+					return Err(subcall_failed(i, output)),
 
 				// BatchSomeUntilFailure : Reverts and errors prevent subsequent subcalls to
 				// be executed but the precompile still succeed.
@@ -45,19 +45,17 @@ use precompile_utils::{mock_account, testing::MockAccount};
 use sp_core::H256;
 use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
-    Perbill,
+    BuildStorage, Perbill,
 };
 
 pub type AccountId = MockAccount;
 pub type Balance = u128;
-pub type BlockNumber = u32;
 
-type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 type Block = frame_system::mocking::MockBlock<Runtime>;
 
 construct_runtime! {
-	pub enum Runtime
-	{
+    pub enum Runtime
+    {
         System: frame_system,
         Balances: pallet_balances,
         Evm: pallet_evm,
@@ -66,7 +64,7 @@ construct_runtime! {
 }
 
 parameter_types! {
-    pub const BlockHashCount: u32 = 250;
+    pub const BlockHashCount: u64 = 250;
     pub const MaximumBlockWeight: Weight = Weight::from_parts(1024, 1);
     pub const MaximumBlockLength: u32 = 2 * 1024;
     pub const AvailableBlockRatio: Perbill = Perbill::one();
@@ -78,7 +76,7 @@ impl frame_system::Config for Runtime {
     type DbWeight = ();
     type RuntimeOrigin = RuntimeOrigin;
     type Nonce = u64;
-    type Block = BlockNumber;
+    type Block = Block;
     type RuntimeCall = RuntimeCall;
     type Hash = H256;
     type Hashing = BlakeTwo256;
@@ -111,6 +109,7 @@ impl pallet_balances::Config for Runtime {
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
+    type RuntimeHoldReason = ();
     type FreezeIdentifier = ();
     type MaxHolds = ();
     type MaxFreezes = ();
@@ -197,8 +196,8 @@ impl Default for ExtBuilder {
 
 impl ExtBuilder {
     pub(crate) fn build(self) -> sp_io::TestExternalities {
-        let mut t = frame_system::GenesisConfig::default()
-            .build_storage::<Runtime>()
+        let mut t = frame_system::GenesisConfig::<Runtime>::default()
+            .build_storage()
             .expect("Frame system builds valid default genesis config");
 
         pallet_balances::GenesisConfig::<Runtime> {
@@ -218,3 +217,6 @@ impl ExtBuilder {
         ext
     }
 }
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::benchmarking::Config for Runtime {}
@@ -36,8 +36,8 @@
 
 use crate::mock::{precompile_address, BatchPrecompileMock, ExtBuilder, PrecompilesValue, Runtime};
 use crate::{log_subcall_failed, log_subcall_succeeded, Mode, *};
-use fp_evm::ExitError;
-use precompile_utils::{call_cost, testing::*, LogsBuilder};
+use pallet_evm::GasWeightMapping;
+use precompile_utils::{call_cost, testing::*, CustomError, LogsBuilder, Revert};
 use sp_core::{H256, U256};
 
 fn precompiles() -> BatchPrecompileMock<Runtime> {
@@ -53,6 +53,26 @@ fn costs() -> (u64, u64) {
     (return_log_cost, call_cost)
 }
 
+/// Output of `batchAll` reverting with `error`.
+fn custom_error(error: CustomError) -> Vec<u8> {
+    Revert::from(error).to_encoded_bytes()
+}
+
+/// Output of `batchAll` running out of gas for the subcall `index`.
+fn out_of_gas_error(index: u32) -> Vec<u8> {
+    custom_error(CustomError::new(ERROR_OUT_OF_GAS).write(U256::from(index)))
+}
+
+/// Gas charged for the weight of a batch of `calls` subcalls, before making any of them.
+fn weight_cost(mode: Mode, calls: u32) -> u64 {
+    let weight = match mode {
+        Mode::BatchSome => <() as WeightInfo>::batch_some(calls),
+        Mode::BatchSomeUntilFailure => <() as WeightInfo>::batch_some_until_failure(calls),
+        Mode::BatchAll => <() as WeightInfo>::batch_all(calls),
+    };
+    <Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight)
+}
+
 #[test]
 fn batch_some_empty() {
     ExtBuilder::default().build().execute_with(|| {
@@ -135,7 +155,7 @@ fn batch_returns(
                 .write::<std::vec::Vec<U256>>(vec![])
                 .build(),
         )
-        .with_target_gas(Some(100_000))
+        .with_target_gas(Some(weight_cost(mode, 2) + 100_000))
         .with_subcall_handle(move |subcall| {
             let Subcall {
                 address,
@@ -208,7 +228,7 @@ fn batch_returns(
                 _ => panic!("unexpected subcall"),
             }
         })
-        .expect_cost(13 + 17 + total_call_cost * 2)
+        .expect_cost(weight_cost(mode, 2) + 13 + 17 + total_call_cost * 2)
 }
 
 #[test]
@@ -264,7 +284,7 @@ fn batch_out_of_gas(
                 .write::<std::vec::Vec<U256>>(vec![])
                 .build(),
         )
-        .with_target_gas(Some(50_000))
+        .with_target_gas(Some(weight_cost(mode, 1) + 50_000))
         .with_subcall_handle(move |subcall| {
             let Subcall {
                 address,
@@ -327,7 +347,8 @@ fn batch_some_until_failure_out_of_gas() {
 #[test]
 fn batch_all_out_of_gas() {
     ExtBuilder::default().build().execute_with(|| {
-        batch_out_of_gas(&precompiles(), Mode::BatchAll).execute_error(ExitError::OutOfGas)
+        batch_out_of_gas(&precompiles(), Mode::BatchAll)
+            .execute_reverts(|output| output == out_of_gas_error(0))
     })
 }
 
@@ -355,7 +376,7 @@ fn batch_incomplete(
                 .write::<std::vec::Vec<U256>>(vec![])
                 .build(),
         )
-        .with_target_gas(Some(300_000))
+        .with_target_gas(Some(weight_cost(mode, 3) + 300_000))
         .with_subcall_handle(move |subcall| {
             let Subcall {
                 address,
@@ -466,7 +487,7 @@ fn batch_some_incomplete() {
             .expect_log(log_subcall_failed(precompile_address(), 1))
             .expect_log(LogsBuilder::new(Alice.into()).log1(H256::repeat_byte(0x33), vec![]))
             .expect_log(log_subcall_succeeded(precompile_address(), 2))
-            .expect_cost(13 + 17 + 19 + total_call_cost * 3)
+            .expect_cost(weight_cost(Mode::BatchSome, 3) + 13 + 17 + 19 + total_call_cost * 3)
             .execute_returns(EvmDataWriter::new().write(true).build())
     })
 }
@@ -480,7 +501,9 @@ fn batch_some_until_failure_incomplete() {
             .expect_log(LogsBuilder::new(Bob.into()).log1(H256::repeat_byte(0x11), vec![]))
             .expect_log(log_subcall_succeeded(precompile_address(), 0))
             .expect_log(log_subcall_failed(precompile_address(), 1))
-            .expect_cost(13 + 17 + total_call_cost * 2)
+            .expect_cost(
+                weight_cost(Mode::BatchSomeUntilFailure, 3) + 13 + 17 + total_call_cost * 2,
+            )
             .execute_returns(EvmDataWriter::new().write(true).build())
     })
 }
@@ -489,7 +512,14 @@ fn batch_some_until_failure_incomplete() {
 fn batch_all_incomplete() {
     ExtBuilder::default().build().execute_with(|| {
         batch_incomplete(&precompiles(), Mode::BatchAll)
-            .execute_reverts(|output| output == b"Revert message")
+            .execute_reverts(|output| {
+                output
+                    == custom_error(
+                        CustomError::new(ERROR_SUBCALL_FAILED)
+                            .write(U256::from(1))
+                            .write(Bytes::from("Revert message")),
+                    )
+            })
     })
 }
 
@@ -511,14 +541,15 @@ fn batch_log_out_of_gas(
                 .write::<std::vec::Vec<U256>>(vec![])
                 .build(),
         )
-        .with_target_gas(Some(log_cost - 1))
+        .with_target_gas(Some(weight_cost(mode, 1) + log_cost - 1))
         .with_subcall_handle(move |_subcall| panic!("there shouldn't be any subcalls"))
 }
 
 #[test]
 fn batch_all_log_out_of_gas() {
     ExtBuilder::default().build().execute_with(|| {
-        batch_log_out_of_gas(&precompiles(), Mode::BatchAll).execute_error(ExitError::OutOfGas);
+        batch_log_out_of_gas(&precompiles(), Mode::BatchAll)
+            .execute_reverts(|output| output == out_of_gas_error(0));
     })
 }
 
@@ -558,14 +589,15 @@ fn batch_call_out_of_gas(
                 .write::<std::vec::Vec<U256>>(vec![])
                 .build(),
         )
-        .with_target_gas(Some(total_call_cost - 1))
+        .with_target_gas(Some(weight_cost(mode, 1) + total_call_cost - 1))
         .with_subcall_handle(move |_subcall| panic!("there shouldn't be any subcalls"))
 }
 
 #[test]
 fn batch_all_call_out_of_gas() {
     ExtBuilder::default().build().execute_with(|| {
-        batch_call_out_of_gas(&precompiles(), Mode::BatchAll).execute_error(ExitError::OutOfGas);
+        batch_call_out_of_gas(&precompiles(), Mode::BatchAll)
+            .execute_reverts(|output| output == out_of_gas_error(0));
     })
 }
 
@@ -605,14 +637,15 @@ fn batch_gas_limit(
                 .write::<std::vec::Vec<U256>>(vec![U256::from(50_000 - total_call_cost + 1)])
                 .build(),
         )
-        .with_target_gas(Some(50_000))
+        .with_target_gas(Some(weight_cost(mode, 1) + 50_000))
         .with_subcall_handle(move |_subcall| panic!("there shouldn't be any subcalls"))
 }
 
 #[test]
 fn batch_all_gas_limit() {
     ExtBuilder::default().build().execute_with(|| {
-        batch_gas_limit(&precompiles(), Mode::BatchAll).execute_error(ExitError::OutOfGas);
+        batch_gas_limit(&precompiles(), Mode::BatchAll)
+            .execute_reverts(|output| output == out_of_gas_error(0));
     })
 }
 
@@ -623,7 +656,7 @@ fn batch_some_gas_limit() {
 
         batch_gas_limit(&precompiles(), Mode::BatchSome)
             .expect_log(log_subcall_failed(precompile_address(), 0))
-            .expect_cost(return_log_cost)
+            .expect_cost(weight_cost(Mode::BatchSome, 1) + return_log_cost)
             .execute_returns(EvmDataWriter::new().write(true).build());
     })
 }
@@ -0,0 +1,430 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! EIP-2537 BLS12-381 precompiles.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Arkworks
+use ark_bls12_381::{
+	g1, g2, Bls12_381, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective,
+};
+use ark_ec::{
+	hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurve},
+	pairing::Pairing,
+	AffineRepr, CurveGroup, VariableBaseMSM,
+};
+use ark_ff::{BigInteger384, PrimeField, Zero};
+use ark_std::vec::Vec;
+
+// Frontier
+use fp_evm::{
+	ExitError, ExitSucceed, Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput,
+	PrecompileResult,
+};
+
+/// Gas discount table for G1 MSM operations.
+/// https://eips.ethereum.org/EIPS/eip-2537#g1g2-msm
+const G1_MSM_DISCOUNT_TABLE: [u16; 128] = [
+	1000, 949, 848, 797, 764, 750, 738, 728, 719, 712, 705, 698, 692, 687, 682, 677, 673, 669, 665,
+	661, 658, 654, 651, 648, 645, 642, 640, 637, 635, 632, 630, 627, 625, 623, 621, 619, 617, 615,
+	613, 611, 609, 608, 606, 604, 603, 601, 599, 598, 596, 595, 593, 592, 591, 589, 588, 586, 585,
+	584, 582, 581, 580, 579, 577, 576, 575, 574, 573, 572, 570, 569, 568, 567, 566, 565, 564, 563,
+	562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 551, 550, 549, 548, 547, 547, 546, 545,
+	544, 543, 542, 541, 540, 540, 539, 538, 537, 536, 536, 535, 534, 533, 532, 532, 531, 530, 529,
+	528, 528, 527, 526, 525, 525, 524, 523, 522, 522, 521, 520, 520, 519,
+];
+
+/// Gas discount table for G2 MSM operations.
+/// https://eips.ethereum.org/EIPS/eip-2537#g1g2-msm
+const G2_MSM_DISCOUNT_TABLE: [u16; 128] = [
+	1000, 1000, 923, 884, 855, 832, 812, 796, 782, 770, 759, 749, 740, 732, 724, 717, 711, 704,
+	699, 693, 688, 683, 679, 674, 670, 666, 663, 659, 655, 652, 649, 646, 643, 640, 637, 634, 632,
+	629, 627, 624, 622, 620, 618, 615, 613, 611, 609, 607, 606, 604, 602, 600, 598, 597, 595, 593,
+	592, 590, 589, 587, 586, 584, 583, 582, 580, 579, 578, 576, 575, 574, 573, 571, 570, 569, 568,
+	567, 566, 565, 563, 562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 552, 551, 550, 549,
+	548, 547, 546, 545, 545, 544, 543, 542, 541, 541, 540, 539, 538, 537, 537, 536, 535, 535, 534,
+	533, 532, 532, 531, 530, 530, 529, 528, 528, 527, 526, 526, 525, 524, 524,
+];
+
+/// Length of an encoded base field element.
+const FP_LEN: usize = 64;
+/// Length of an encoded G1 point.
+const G1_LEN: usize = 2 * FP_LEN;
+/// Length of an encoded G2 point.
+const G2_LEN: usize = 4 * FP_LEN;
+/// Length of an encoded scalar.
+const SCALAR_LEN: usize = 32;
+
+fn error(reason: &'static str) -> PrecompileFailure {
+	PrecompileFailure::Error { exit_status: ExitError::Other(reason.into()) }
+}
+
+/// Gas of an MSM of `k` pairs, each pair costing `mul_cost` less the discount for `k`.
+fn msm_gas_cost(k: usize, mul_cost: u64, discount_table: &[u16; 128]) -> u64 {
+	if k == 0 {
+		return 0
+	}
+	let discount = discount_table[k.min(discount_table.len()) - 1];
+	k as u64 * mul_cost * discount as u64 / 1_000
+}
+
+/// Encode Fq as `64` bytes by performing Big-Endian encoding of the corresponding (unsigned)
+/// integer (top 16 bytes are always zeroes).
+fn encode_fq(field: Fq) -> [u8; FP_LEN] {
+	let mut result = [0u8; FP_LEN];
+	let rep = field.into_bigint().0;
+	for (i, limb) in rep.iter().rev().enumerate() {
+		result[16 + i * 8..][..8].copy_from_slice(&limb.to_be_bytes());
+	}
+	result
+}
+
+/// Encode point G1 as byte concatenation of encodings of the `x` and `y` affine coordinates, the
+/// point at infinity being all zeroes.
+fn encode_g1(g1: G1Affine) -> [u8; G1_LEN] {
+	let mut result = [0u8; G1_LEN];
+	if !g1.is_zero() {
+		result[..FP_LEN].copy_from_slice(&encode_fq(g1.x));
+		result[FP_LEN..].copy_from_slice(&encode_fq(g1.y));
+	}
+	result
+}
+
+/// Encode point G2 as byte concatenation of encodings of the `x` and `y` affine coordinates, the
+/// point at infinity being all zeroes.
+fn encode_g2(g2: G2Affine) -> [u8; G2_LEN] {
+	let mut result = [0u8; G2_LEN];
+	if !g2.is_zero() {
+		result[..FP_LEN].copy_from_slice(&encode_fq(g2.x.c0));
+		result[FP_LEN..][..FP_LEN].copy_from_slice(&encode_fq(g2.x.c1));
+		result[2 * FP_LEN..][..FP_LEN].copy_from_slice(&encode_fq(g2.y.c0));
+		result[3 * FP_LEN..].copy_from_slice(&encode_fq(g2.y.c1));
+	}
+	result
+}
+
+/// Decode Fq from `64` bytes at `offset`. The top 16 bytes must be zero and the value must be
+/// below the modulus.
+fn decode_fq(input: &[u8], offset: usize) -> Result<Fq, PrecompileFailure> {
+	let bytes = &input[offset..][..FP_LEN];
+	if bytes[..16].iter().any(|b| *b != 0) {
+		return Err(error("invalid field element top bytes"))
+	}
+
+	let mut tmp = BigInteger384::new([0; 6]);
+	for (i, limb) in tmp.0.iter_mut().rev().enumerate() {
+		let mut buf = [0u8; 8];
+		buf.copy_from_slice(&bytes[16 + i * 8..][..8]);
+		*limb = u64::from_be_bytes(buf);
+	}
+
+	Fq::from_bigint(tmp).ok_or_else(|| error("invalid fp.Element encoding"))
+}
+
+/// Decode Fq2 from its `c0` and `c1` encodings at `offset`.
+fn decode_fq2(input: &[u8], offset: usize) -> Result<Fq2, PrecompileFailure> {
+	Ok(Fq2::new(decode_fq(input, offset)?, decode_fq(input, offset + FP_LEN)?))
+}
+
+/// Decode Fr from `32` bytes at `offset`. Scalars are not required to be below the group order.
+fn decode_fr(input: &[u8], offset: usize) -> Fr {
+	Fr::from_be_bytes_mod_order(&input[offset..][..SCALAR_LEN])
+}
+
+/// Decode a G1 point from `128` bytes at `offset`, checking that it is on the curve and, if
+/// `subgroup_check` is set, that it is in the prime order subgroup.
+fn decode_g1(
+	input: &[u8],
+	offset: usize,
+	subgroup_check: bool,
+) -> Result<G1Affine, PrecompileFailure> {
+	let px = decode_fq(input, offset)?;
+	let py = decode_fq(input, offset + FP_LEN)?;
+
+	if px.is_zero() && py.is_zero() {
+		return Ok(G1Affine::zero())
+	}
+
+	let g1 = G1Affine::new_unchecked(px, py);
+	if !g1.is_on_curve() {
+		return Err(error("invalid point: not on curve"))
+	}
+	if subgroup_check && !g1.is_in_correct_subgroup_assuming_on_curve() {
+		return Err(error("g1 point is not on correct subgroup"))
+	}
+	Ok(g1)
+}
+
+/// Decode a G2 point from `256` bytes at `offset`, checking that it is on the curve and, if
+/// `subgroup_check` is set, that it is in the prime order subgroup.
+fn decode_g2(
+	input: &[u8],
+	offset: usize,
+	subgroup_check: bool,
+) -> Result<G2Affine, PrecompileFailure> {
+	let px = decode_fq2(input, offset)?;
+	let py = decode_fq2(input, offset + 2 * FP_LEN)?;
+
+	if px.is_zero() && py.is_zero() {
+		return Ok(G2Affine::zero())
+	}
+
+	let g2 = G2Affine::new_unchecked(px, py);
+	if !g2.is_on_curve() {
+		return Err(error("invalid point: not on curve"))
+	}
+	if subgroup_check && !g2.is_in_correct_subgroup_assuming_on_curve() {
+		return Err(error("g2 point is not on correct subgroup"))
+	}
+	Ok(g2)
+}
+
+fn succeed(output: &[u8]) -> PrecompileResult {
+	Ok(PrecompileOutput { exit_status: ExitSucceed::Returned, output: output.to_vec() })
+}
+
+/// Bls12381G1Add implements EIP-2537 G1ADD precompile.
+pub struct Bls12381G1Add;
+
+impl Bls12381G1Add {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const GAS_COST: u64 = 375;
+}
+
+impl Precompile for Bls12381G1Add {
+	/// Implements EIP-2537 G1ADD precompile.
+	/// > G1 addition call expects `256` bytes as an input that is interpreted as byte concatenation of two G1 points (`128` bytes each).
+	/// > Output is an encoding of addition operation result - single G1 point (`128` bytes).
+	/// > There is no subgroup check for the G1 addition precompile.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381G1Add::GAS_COST)?;
+
+		let input = handle.input();
+		if input.len() != 2 * G1_LEN {
+			return Err(error("invalid input length"))
+		}
+
+		let p0 = decode_g1(input, 0, false)?;
+		let p1 = decode_g1(input, G1_LEN, false)?;
+		let r = p0 + p1;
+
+		succeed(&encode_g1(r.into_affine()))
+	}
+}
+
+/// Bls12381G1Msm implements EIP-2537 G1MSM precompile.
+pub struct Bls12381G1Msm;
+
+impl Bls12381G1Msm {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const MUL_GAS_COST: u64 = 12_000;
+}
+
+impl Precompile for Bls12381G1Msm {
+	/// Implements EIP-2537 G1MSM precompile.
+	/// > G1 MSM call expects `160*k` bytes as an input that is interpreted as byte concatenation of `k` slices each of them being a byte concatenation of encoding of G1 point (`128` bytes) and encoding of a scalar value (`32` bytes).
+	/// > Output is an encoding of MSM operation result - single G1 point (`128` bytes).
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let pair_len = G1_LEN + SCALAR_LEN;
+		let k = handle.input().len() / pair_len;
+		handle.record_cost(msm_gas_cost(k, Self::MUL_GAS_COST, &G1_MSM_DISCOUNT_TABLE))?;
+
+		let input = handle.input();
+		if input.is_empty() || input.len() % pair_len != 0 {
+			return Err(error("invalid input length"))
+		}
+
+		let mut points = Vec::with_capacity(k);
+		let mut scalars = Vec::with_capacity(k);
+		for idx in 0..k {
+			let offset = idx * pair_len;
+			points.push(decode_g1(input, offset, true)?);
+			scalars.push(decode_fr(input, offset + G1_LEN));
+		}
+
+		let r = G1Projective::msm(&points, &scalars).map_err(|_| error("MSM failed"))?;
+
+		succeed(&encode_g1(r.into_affine()))
+	}
+}
+
+/// Bls12381G2Add implements EIP-2537 G2ADD precompile.
+pub struct Bls12381G2Add;
+
+impl Bls12381G2Add {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const GAS_COST: u64 = 600;
+}
+
+impl Precompile for Bls12381G2Add {
+	/// Implements EIP-2537 G2ADD precompile.
+	/// > G2 addition call expects `512` bytes as an input that is interpreted as byte concatenation of two G2 points (`256` bytes each).
+	/// > Output is an encoding of addition operation result - single G2 point (`256` bytes).
+	/// > There is no subgroup check for the G2 addition precompile.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381G2Add::GAS_COST)?;
+
+		let input = handle.input();
+		if input.len() != 2 * G2_LEN {
+			return Err(error("invalid input length"))
+		}
+
+		let p0 = decode_g2(input, 0, false)?;
+		let p1 = decode_g2(input, G2_LEN, false)?;
+		let r = p0 + p1;
+
+		succeed(&encode_g2(r.into_affine()))
+	}
+}
+
+/// Bls12381G2Msm implements EIP-2537 G2MSM precompile.
+pub struct Bls12381G2Msm;
+
+impl Bls12381G2Msm {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const MUL_GAS_COST: u64 = 22_500;
+}
+
+impl Precompile for Bls12381G2Msm {
+	/// Implements EIP-2537 G2MSM precompile.
+	/// > G2 MSM call expects `288*k` bytes as an input that is interpreted as byte concatenation of `k` slices each of them being a byte concatenation of encoding of G2 point (`256` bytes) and encoding of a scalar value (`32` bytes).
+	/// > Output is an encoding of MSM operation result - single G2 point (`256` bytes).
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let pair_len = G2_LEN + SCALAR_LEN;
+		let k = handle.input().len() / pair_len;
+		handle.record_cost(msm_gas_cost(k, Self::MUL_GAS_COST, &G2_MSM_DISCOUNT_TABLE))?;
+
+		let input = handle.input();
+		if input.is_empty() || input.len() % pair_len != 0 {
+			return Err(error("invalid input length"))
+		}
+
+		let mut points = Vec::with_capacity(k);
+		let mut scalars = Vec::with_capacity(k);
+		for idx in 0..k {
+			let offset = idx * pair_len;
+			points.push(decode_g2(input, offset, true)?);
+			scalars.push(decode_fr(input, offset + G2_LEN));
+		}
+
+		let r = G2Projective::msm(&points, &scalars).map_err(|_| error("MSM failed"))?;
+
+		succeed(&encode_g2(r.into_affine()))
+	}
+}
+
+/// Bls12381Pairing implements EIP-2537 PAIRING_CHECK precompile.
+pub struct Bls12381Pairing;
+
+impl Bls12381Pairing {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const BASE_GAS: u64 = 37_700;
+	const PER_PAIR_GAS: u64 = 32_600;
+}
+
+impl Precompile for Bls12381Pairing {
+	/// Implements EIP-2537 PAIRING_CHECK precompile.
+	/// > Pairing call expects `384*k` bytes as an inputs that is interpreted as byte concatenation of `k` slices. Each slice has the following structure:
+	/// > - `128` bytes of G1 point encoding
+	/// > - `256` bytes of G2 point encoding
+	/// > Output is a `32` bytes where last single byte is `0x01` if pairing result is equal to multiplicative identity in a pairing target field and `0x00` otherwise.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let pair_len = G1_LEN + G2_LEN;
+		let k = handle.input().len() / pair_len;
+		handle.record_cost(Self::BASE_GAS + k as u64 * Self::PER_PAIR_GAS)?;
+
+		let input = handle.input();
+		if input.is_empty() || input.len() % pair_len != 0 {
+			return Err(error("invalid input length"))
+		}
+
+		let mut a = Vec::with_capacity(k);
+		let mut b = Vec::with_capacity(k);
+		for idx in 0..k {
+			let offset = idx * pair_len;
+			a.push(decode_g1(input, offset, true)?);
+			b.push(decode_g2(input, offset + G1_LEN, true)?);
+		}
+
+		let mut output = [0u8; 32];
+		if Bls12_381::multi_pairing(a, b).is_zero() {
+			output[31] = 1;
+		}
+
+		succeed(&output)
+	}
+}
+
+/// Bls12381MapG1 implements EIP-2537 MAP_FP_TO_G1 precompile.
+pub struct Bls12381MapG1;
+
+impl Bls12381MapG1 {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const GAS_COST: u64 = 5_500;
+}
+
+impl Precompile for Bls12381MapG1 {
+	/// Implements EIP-2537 MAP_FP_TO_G1 precompile.
+	/// > Field-to-curve call expects `64` bytes as an input that is interpreted as an element of the base field.
+	/// > Output of this call is `128` bytes and is an encoded G1 point, mapped with the simplified SWU map and cleared of its cofactor.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381MapG1::GAS_COST)?;
+
+		let input = handle.input();
+		if input.len() != FP_LEN {
+			return Err(error("invalid input length"))
+		}
+
+		let fp = decode_fq(input, 0)?;
+		let point = WBMap::<g1::Config>::new()
+			.and_then(|map| map.map_to_curve(fp))
+			.map_err(|_| error("map to curve failed"))?;
+
+		succeed(&encode_g1(point.clear_cofactor()))
+	}
+}
+
+/// Bls12381MapG2 implements EIP-2537 MAP_FP2_TO_G2 precompile.
+pub struct Bls12381MapG2;
+
+impl Bls12381MapG2 {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const GAS_COST: u64 = 23_800;
+}
+
+impl Precompile for Bls12381MapG2 {
+	/// Implements EIP-2537 MAP_FP2_TO_G2 precompile.
+	/// > Field-to-curve call expects `128` bytes as an input that is interpreted as an element of the quadratic extension field.
+	/// > Output of this call is `256` bytes and is an encoded G2 point, mapped with the simplified SWU map and cleared of its cofactor.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381MapG2::GAS_COST)?;
+
+		let input = handle.input();
+		if input.len() != 2 * FP_LEN {
+			return Err(error("invalid input length"))
+		}
+
+		let fp2 = decode_fq2(input, 0)?;
+		let point = WBMap::<g2::Config>::new()
+			.and_then(|map| map.map_to_curve(fp2))
+			.map_err(|_| error("map to curve failed"))?;
+
+		succeed(&encode_g2(point.clear_cofactor()))
+	}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (c) 2020-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use pallet_evm_test_vector_support::{
+	test_precompile_failure_test_vectors, test_precompile_test_vectors, MockHandle,
+};
+
+#[test]
+fn process_consensus_tests() -> Result<(), String> {
+	test_precompile_test_vectors::<Bls12381G1Add>("testdata/bls12381G1Add.json")?;
+	test_precompile_test_vectors::<Bls12381G1Msm>("testdata/bls12381G1MSM.json")?;
+	test_precompile_test_vectors::<Bls12381G2Add>("testdata/bls12381G2Add.json")?;
+	test_precompile_test_vectors::<Bls12381G2Msm>("testdata/bls12381G2MSM.json")?;
+	test_precompile_test_vectors::<Bls12381Pairing>("testdata/bls12381Pairing.json")?;
+	test_precompile_test_vectors::<Bls12381MapG1>("testdata/bls12381MapG1.json")?;
+	test_precompile_test_vectors::<Bls12381MapG2>("testdata/bls12381MapG2.json")?;
+	Ok(())
+}
+
+#[test]
+fn process_consensus_failure_tests() -> Result<(), String> {
+	test_precompile_failure_test_vectors::<Bls12381G1Add>("testdata/fail-bls12381G1Add.json")?;
+	test_precompile_failure_test_vectors::<Bls12381G1Msm>("testdata/fail-bls12381G1MSM.json")?;
+	test_precompile_failure_test_vectors::<Bls12381G2Add>("testdata/fail-bls12381G2Add.json")?;
+	test_precompile_failure_test_vectors::<Bls12381G2Msm>("testdata/fail-bls12381G2MSM.json")?;
+	test_precompile_failure_test_vectors::<Bls12381Pairing>("testdata/fail-bls12381Pairing.json")?;
+	test_precompile_failure_test_vectors::<Bls12381MapG1>("testdata/fail-bls12381MapG1.json")?;
+	test_precompile_failure_test_vectors::<Bls12381MapG2>("testdata/fail-bls12381MapG2.json")?;
+	Ok(())
+}
+
+fn execute<P: Precompile>(input: Vec<u8>) -> (Vec<u8>, u64) {
+	let context = fp_evm::Context {
+		address: Default::default(),
+		caller: Default::default(),
+		apparent_value: From::from(0),
+	};
+	let mut handle = MockHandle::new(input, Some(10_000_000), context);
+	let output = P::execute(&mut handle).expect("valid input").output;
+	(output, handle.gas_used)
+}
+
+fn encoded_fq(value: u64) -> Vec<u8> {
+	encode_fq(Fq::from(value)).to_vec()
+}
+
+#[test]
+fn map_fp_to_g1_returns_subgroup_points() {
+	let (output, gas) = execute::<Bls12381MapG1>(encoded_fq(1));
+	assert_eq!(gas, 5_500);
+
+	let point = decode_g1(&output, 0, true).expect("point is in the subgroup");
+	assert!(!point.is_zero());
+
+	let (other, _) = execute::<Bls12381MapG1>(encoded_fq(2));
+	assert_ne!(output, other);
+	assert_eq!(execute::<Bls12381MapG1>(encoded_fq(1)).0, output);
+}
+
+#[test]
+fn map_fp2_to_g2_returns_subgroup_points() {
+	let (output, gas) = execute::<Bls12381MapG2>([encoded_fq(1), encoded_fq(2)].concat());
+	assert_eq!(gas, 23_800);
+
+	let point = decode_g2(&output, 0, true).expect("point is in the subgroup");
+	assert!(!point.is_zero());
+
+	let (other, _) = execute::<Bls12381MapG2>([encoded_fq(2), encoded_fq(1)].concat());
+	assert_ne!(output, other);
+}
+
+#[test]
+fn msm_gas_cost_follows_discount_table() {
+	assert_eq!(msm_gas_cost(0, 12_000, &G1_MSM_DISCOUNT_TABLE), 0);
+	assert_eq!(msm_gas_cost(1, 12_000, &G1_MSM_DISCOUNT_TABLE), 12_000);
+	assert_eq!(msm_gas_cost(2, 22_500, &G2_MSM_DISCOUNT_TABLE), 45_000);
+	// The last discount applies to any larger input.
+	assert_eq!(msm_gas_cost(200, 12_000, &G1_MSM_DISCOUNT_TABLE), 200 * 12_000 * 519 / 1_000);
+	assert_eq!(msm_gas_cost(200, 22_500, &G2_MSM_DISCOUNT_TABLE), 200 * 22_500 * 524 / 1_000);
+}
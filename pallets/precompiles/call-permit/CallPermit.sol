@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// Interface to the call permit precompiled contract
+/// Predeployed at the address 0x0000000000000000000000000000000000005009
+
+/// @title Call permit precompile
+/// @dev Dispatch a call on behalf of an account that signed an EIP-712 permit.
+/// The caller of `dispatch` pays the gas, the subcall is executed with `from` as `msg.sender`.
+///
+/// The EIP-712 domain is:
+/// - name: "Call Permit Precompile"
+/// - version: "1"
+/// - chainId: the EVM chain id
+/// - verifyingContract: the address of this precompile
+///
+/// The signed message is:
+/// CallPermit(address from,address to,uint256 value,bytes data,uint64 gaslimit,uint256 nonce,uint256 deadline)
+interface CallPermit {
+    /// @dev Dispatch a call on behalf of `from`, which signed the permit.
+    /// Batched relays can be built by calling this function through the batch precompile.
+    ///
+    /// @param from Signer of the permit, used as caller of the subcall.
+    /// @param to Address to call.
+    /// @param value Value transferred from `from` to `to`.
+    /// @param data Call data of the subcall.
+    /// @param gaslimit Gas forwarded to the subcall. The dispatch reverts if less gas is available.
+    /// @param deadline Timestamp in seconds after which the permit is no longer valid.
+    /// @param v V of the permit signature.
+    /// @param r R of the permit signature.
+    /// @param s S of the permit signature.
+    /// @return output Output of the subcall.
+    function dispatch(
+        address from,
+        address to,
+        uint256 value,
+        bytes memory data,
+        uint64 gaslimit,
+        uint256 deadline,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external returns (bytes memory output);
+
+    /// @dev Nonce the next permit of `owner` must be signed with.
+    function nonces(address owner) external view returns (uint256);
+
+    /// @dev EIP-712 domain separator of this precompile.
+    // solhint-disable-next-line func-name-mixedcase
+    function DOMAIN_SEPARATOR() external view returns (bytes32);
+}
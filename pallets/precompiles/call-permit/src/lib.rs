@@ -0,0 +1,272 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Precompile dispatching an EVM call on behalf of an account that signed an EIP-712 permit.
+//!
+//! The transaction sender pays the gas, while the subcall is executed with the signer as caller.
+//! A permit can only be used once: it commits to the signer nonce, which is incremented on
+//! dispatch. Combined with the batch precompile, a relayer can bundle several permits in one
+//! transaction.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ::evm::ExitReason;
+use fp_evm::{Context, PrecompileFailure, PrecompileHandle, Transfer};
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	storage::types::{StorageMap, ValueQuery},
+	traits::{ConstU32, Get, StorageInstance, Time},
+	Blake2_128Concat,
+};
+use pallet_evm::{Precompile, PrecompileOutput};
+use precompile_utils::{bytes::BoundedBytes, *};
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// EIP-712 TypeHash of the call permit message.
+pub const PERMIT_TYPEHASH: [u8; 32] = keccak256!(
+	"CallPermit(address from,address to,uint256 value,bytes data,uint64 gaslimit\
+,uint256 nonce,uint256 deadline)"
+);
+
+/// EIP-712 TypeHash of the domain.
+pub const PERMIT_DOMAIN: [u8; 32] = keccak256!(
+	"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+);
+
+/// Name of the EIP-712 domain.
+pub const PERMIT_DOMAIN_NAME: &str = "Call Permit Precompile";
+
+pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
+
+type GetCallDataLimit = ConstU32<CALL_DATA_LIMIT>;
+
+/// Storage prefix of the permit nonces, placed under a pallet prefix no pallet uses.
+pub struct NoncesPrefix;
+
+impl StorageInstance for NoncesPrefix {
+	const STORAGE_PREFIX: &'static str = "Nonces";
+
+	fn pallet_prefix() -> &'static str {
+		"PrecompileCallPermit"
+	}
+}
+
+/// Next permit nonce of an account, keyed by its address.
+pub type NoncesStorage = StorageMap<NoncesPrefix, Blake2_128Concat, H160, U256, ValueQuery>;
+
+#[precompile_utils::generate_function_selector]
+#[derive(Debug, PartialEq)]
+pub enum Action {
+	#[returns("bytes")]
+	Dispatch = "dispatch(address,address,uint256,bytes,uint64,uint256,uint8,bytes32,bytes32)",
+	#[view]
+	#[returns("uint256")]
+	Nonces = "nonces(address)",
+	#[view]
+	#[returns("bytes32")]
+	DomainSeparator = "DOMAIN_SEPARATOR()",
+}
+
+/// Call permit precompile.
+#[derive(Debug, Clone)]
+pub struct CallPermitPrecompile<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for CallPermitPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let selector = handle.read_selector()?;
+
+		handle.check_function_modifier(match selector {
+			Action::Dispatch => FunctionModifier::NonPayable,
+			_ => FunctionModifier::View,
+		})?;
+
+		match selector {
+			Action::Dispatch => Self::dispatch(handle),
+			Action::Nonces => Self::nonces(handle),
+			Action::DomainSeparator => Self::domain_separator(handle),
+		}
+	}
+}
+
+impl<Runtime> CallPermitPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+{
+	pub fn compute_domain_separator(address: H160) -> [u8; 32] {
+		let name: H256 = keccak_256(PERMIT_DOMAIN_NAME.as_bytes()).into();
+		let version: H256 = keccak256!("1").into();
+		let chain_id: U256 = <Runtime as pallet_evm::Config>::ChainId::get().into();
+
+		let domain_separator_inner = EvmDataWriter::new()
+			.write(H256::from(PERMIT_DOMAIN))
+			.write(name)
+			.write(version)
+			.write(chain_id)
+			.write(Address(address))
+			.build();
+
+		keccak_256(&domain_separator_inner)
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn generate_permit(
+		address: H160,
+		from: H160,
+		to: H160,
+		value: U256,
+		data: &[u8],
+		gas_limit: u64,
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		let domain_separator = Self::compute_domain_separator(address);
+
+		let permit_content = EvmDataWriter::new()
+			.write(H256::from(PERMIT_TYPEHASH))
+			.write(Address(from))
+			.write(Address(to))
+			.write(value)
+			// bytes are encoded as the keccak_256 of the content
+			.write(H256::from(keccak_256(data)))
+			.write(gas_limit)
+			.write(nonce)
+			.write(deadline)
+			.build();
+		let permit_content = keccak_256(&permit_content);
+
+		let mut pre_digest = Vec::with_capacity(2 + 32 + 32);
+		pre_digest.extend_from_slice(b"\x19\x01");
+		pre_digest.extend_from_slice(&domain_separator);
+		pre_digest.extend_from_slice(&permit_content);
+		keccak_256(&pre_digest)
+	}
+
+	fn dispatch(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		// Storage item: Nonces:
+		// Blake2_128(16) + from(20) + nonce(32)
+		handle.record_db_read::<Runtime>(68)?;
+		// Nonce write
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(9)?;
+
+		let from: H160 = input.read::<Address>()?.into();
+		let to: H160 = input.read::<Address>()?.into();
+		let value: U256 = input.read()?;
+		let data: Vec<u8> = input.read::<BoundedBytes<GetCallDataLimit>>()?.into();
+		let gas_limit: u64 = input.read()?;
+		let deadline: U256 = input.read()?;
+		let v: u8 = input.read()?;
+		let r: H256 = input.read()?;
+		let s: H256 = input.read()?;
+
+		// The precompile pays for the subcall and forwards exactly `gas_limit` to it, so the
+		// signer gets the gas it signed for or the whole dispatch fails.
+		let call_cost = call_cost(value, <Runtime as pallet_evm::Config>::config());
+		let total_cost = gas_limit
+			.checked_add(call_cost)
+			.ok_or_else(|| revert("call require too much gas (uint64 overflow)"))?;
+		if total_cost > handle.remaining_gas() {
+			return Err(revert("gaslimit is too low to dispatch provided call"))
+		}
+
+		// Deadline is expressed in seconds, like `block.timestamp`.
+		let now: u128 = <Runtime as pallet_evm::Config>::Timestamp::now().unique_saturated_into();
+		if deadline < U256::from(now / 1000) {
+			return Err(revert("Permit expired"))
+		}
+
+		let nonce = NoncesStorage::get(from);
+
+		let permit = Self::generate_permit(
+			handle.code_address(),
+			from,
+			to,
+			value,
+			&data,
+			gas_limit,
+			nonce,
+			deadline,
+		);
+
+		let mut sig = [0u8; 65];
+		sig[0..32].copy_from_slice(r.as_bytes());
+		sig[32..64].copy_from_slice(s.as_bytes());
+		sig[64] = v;
+
+		let signer = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &permit)
+			.map_err(|_| revert("Invalid permit"))?;
+		let signer = H160::from(H256::from_slice(keccak_256(&signer).as_slice()));
+
+		if signer != from {
+			return Err(revert("Invalid permit"))
+		}
+
+		NoncesStorage::insert(from, nonce + U256::one());
+
+		let sub_context = Context { caller: from, address: to, apparent_value: value };
+
+		let transfer =
+			if value.is_zero() { None } else { Some(Transfer { source: from, target: to, value }) };
+
+		let (reason, output) =
+			handle.call(to, transfer, data, Some(gas_limit), false, &sub_context);
+
+		match reason {
+			ExitReason::Error(exit_status) => Err(PrecompileFailure::Error { exit_status }),
+			ExitReason::Fatal(exit_status) => Err(PrecompileFailure::Fatal { exit_status }),
+			ExitReason::Revert(exit_status) =>
+				Err(PrecompileFailure::Revert { exit_status, output }),
+			ExitReason::Succeed(_) =>
+				Ok(succeed(EvmDataWriter::new().write(Bytes(output)).build())),
+		}
+	}
+
+	fn nonces(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		// Storage item: Nonces:
+		// Blake2_128(16) + from(20) + nonce(32)
+		handle.record_db_read::<Runtime>(68)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let from: H160 = input.read::<Address>()?.into();
+
+		let nonce = NoncesStorage::get(from);
+
+		Ok(succeed(EvmDataWriter::new().write(nonce).build()))
+	}
+
+	fn domain_separator(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let domain_separator = Self::compute_domain_separator(handle.code_address());
+
+		Ok(succeed(EvmDataWriter::new().write(H256::from(domain_separator)).build()))
+	}
+}
@@ -0,0 +1,271 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::mock::*;
+use crate::*;
+
+use hex_literal::hex;
+use precompile_utils::testing::*;
+
+fn precompiles() -> TestPrecompileSet<Runtime> {
+    PrecompilesValue::get()
+}
+
+fn dispatch_input(value: U256, v: u8, r: H256, s: H256) -> Vec<u8> {
+    EvmDataWriter::new_with_selector(Action::Dispatch)
+        .write(Address(CryptoAlith.into()))
+        .write(Address(Bob.into()))
+        .write(value)
+        .write(Bytes(hex!("deadbeef").to_vec()))
+        .write(100_000u64)
+        .write(U256::from(1000u32))
+        .write(v)
+        .write(r)
+        .write(s)
+        .build()
+}
+
+// Signature by Alith of the permit with value 0, data 0xdeadbeef, gas limit 100_000, nonce 0 and
+// deadline 1000.
+fn valid_permit_input() -> Vec<u8> {
+    dispatch_input(
+        U256::zero(),
+        27,
+        H256::from(hex!(
+            "82061b99b58d98012580a7fab05f41b73a31af8800742206b7a1102fe245d64d"
+        )),
+        H256::from(hex!(
+            "693a1a5b48be8ec0e167f4cae6f2d9524781de764a3bd2e1336e17cfad599f1f"
+        )),
+    )
+}
+
+fn subcall_to_bob(subcall: Subcall) -> SubcallOutput {
+    let Subcall {
+        address,
+        transfer,
+        input,
+        target_gas,
+        is_static,
+        context,
+    } = subcall;
+
+    assert_eq!(address, Bob.into());
+    assert!(transfer.is_none());
+    assert_eq!(input, hex!("deadbeef").to_vec());
+    assert_eq!(target_gas, Some(100_000));
+    assert!(!is_static);
+    assert_eq!(context.caller, CryptoAlith.into());
+    assert_eq!(context.address, Bob.into());
+    assert_eq!(context.apparent_value, U256::zero());
+
+    SubcallOutput {
+        output: b"TEST".to_vec(),
+        cost: 13,
+        ..SubcallOutput::succeed()
+    }
+}
+
+#[test]
+fn selectors() {
+    assert_eq!(Action::Dispatch as u32, 0xb5ea0966);
+    assert_eq!(Action::Nonces as u32, 0x7ecebe00);
+    assert_eq!(Action::DomainSeparator as u32, 0x3644e515);
+}
+
+#[test]
+fn selector_less_than_four_bytes() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Alice, PRECOMPILE_ADDRESS, vec![1u8, 2u8, 3u8])
+            .execute_reverts(|output| output == b"tried to parse selector out of bounds");
+    });
+}
+
+#[test]
+fn no_selector_exists_but_length_is_right() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Alice, PRECOMPILE_ADDRESS, vec![1u8, 2u8, 3u8, 4u8])
+            .execute_reverts(|output| output == b"unknown selector");
+    });
+}
+
+#[test]
+fn domain_separator() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::DomainSeparator).build(),
+            )
+            .expect_cost(0)
+            .expect_no_logs()
+            .execute_returns(
+                EvmDataWriter::new()
+                    .write(H256::from(hex!(
+                        "77d88352fa6499d8e504166b7bad8c48f22e084b249988bcb375ada19dbb96e2"
+                    )))
+                    .build(),
+            );
+    });
+}
+
+#[test]
+fn valid_permit_dispatches_call() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(CryptoAlith), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(Charlie, PRECOMPILE_ADDRESS, valid_permit_input())
+                .with_subcall_handle(subcall_to_bob)
+                .expect_no_logs()
+                .execute_returns(EvmDataWriter::new().write(Bytes::from("TEST")).build());
+
+            precompiles()
+                .prepare_test(
+                    Charlie,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Nonces)
+                        .write(Address(CryptoAlith.into()))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(U256::one()).build());
+        });
+}
+
+#[test]
+fn permit_replay_is_rejected() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Charlie, PRECOMPILE_ADDRESS, valid_permit_input())
+            .with_subcall_handle(subcall_to_bob)
+            .execute_returns(EvmDataWriter::new().write(Bytes::from("TEST")).build());
+
+        precompiles()
+            .prepare_test(Charlie, PRECOMPILE_ADDRESS, valid_permit_input())
+            .with_subcall_handle(|_| panic!("no subcall expected"))
+            .execute_reverts(|output| output == b"Invalid permit");
+    });
+}
+
+#[test]
+fn permit_invalid_value() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Signature of the valid permit, but for a non zero value.
+        precompiles()
+            .prepare_test(
+                Charlie,
+                PRECOMPILE_ADDRESS,
+                dispatch_input(
+                    U256::from(42u32),
+                    27,
+                    H256::from(hex!(
+                        "82061b99b58d98012580a7fab05f41b73a31af8800742206b7a1102fe245d64d"
+                    )),
+                    H256::from(hex!(
+                        "693a1a5b48be8ec0e167f4cae6f2d9524781de764a3bd2e1336e17cfad599f1f"
+                    )),
+                ),
+            )
+            .with_subcall_handle(|_| panic!("no subcall expected"))
+            .execute_reverts(|output| output == b"Invalid permit");
+
+        precompiles()
+            .prepare_test(
+                Charlie,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::Nonces)
+                    .write(Address(CryptoAlith.into()))
+                    .build(),
+            )
+            .execute_returns(EvmDataWriter::new().write(U256::zero()).build());
+    });
+}
+
+#[test]
+fn permit_with_value_transfers_from_signer() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(CryptoAlith), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Charlie,
+                    PRECOMPILE_ADDRESS,
+                    dispatch_input(
+                        U256::from(42u32),
+                        28,
+                        H256::from(hex!(
+                            "d84336b990fe2196869c982435f74d294eb0c72af6040ded8a29713494a68702"
+                        )),
+                        H256::from(hex!(
+                            "2e37751ab27be193336cc5dca0c011aa3df4489a9f831bcf7d16008a0f3fc2e9"
+                        )),
+                    ),
+                )
+                .with_subcall_handle(|Subcall { transfer, context, .. }| {
+                    let transfer = transfer.expect("a transfer is expected");
+                    assert_eq!(transfer.source, CryptoAlith.into());
+                    assert_eq!(transfer.target, Bob.into());
+                    assert_eq!(transfer.value, U256::from(42u32));
+                    assert_eq!(context.caller, CryptoAlith.into());
+                    assert_eq!(context.apparent_value, U256::from(42u32));
+
+                    SubcallOutput::succeed()
+                })
+                .execute_returns(EvmDataWriter::new().write(Bytes(vec![])).build());
+        });
+}
+
+#[test]
+fn subcall_revert_is_forwarded() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Charlie, PRECOMPILE_ADDRESS, valid_permit_input())
+            .with_subcall_handle(|_| SubcallOutput {
+                output: b"Subcall reverted".to_vec(),
+                ..SubcallOutput::revert()
+            })
+            .execute_reverts(|output| output == b"Subcall reverted");
+    });
+}
+
+#[test]
+fn permit_expired() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Deadline is 1000 seconds.
+        Timestamp::set_timestamp(1_001_000);
+
+        precompiles()
+            .prepare_test(Charlie, PRECOMPILE_ADDRESS, valid_permit_input())
+            .with_subcall_handle(|_| panic!("no subcall expected"))
+            .execute_reverts(|output| output == b"Permit expired");
+    });
+}
+
+#[test]
+fn gas_limit_above_remaining_gas() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Charlie, PRECOMPILE_ADDRESS, valid_permit_input())
+            .with_target_gas(Some(50_000))
+            .with_subcall_handle(|_| panic!("no subcall expected"))
+            .execute_reverts(|output| output == b"gaslimit is too low to dispatch provided call");
+    });
+}
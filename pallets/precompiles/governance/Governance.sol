@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// Interface to the governance precompiled contract
+/// Predeployed at the address 0x0000000000000000000000000000000000005007
+
+/// @title Governance precompile
+/// @dev Vote, delegate and submit referenda through pallet-conviction-voting and pallet-referenda.
+/// The caller address is mapped to the Substrate account the pallets see as signer.
+interface Governance {
+    /// @dev Conviction multiplies the voting power at the cost of a longer lock.
+    /// 0 = None (0.1x), 1 = Locked1x, 2 = Locked2x, ... 6 = Locked6x
+
+    /// @dev Referendum status, as returned by `referendumStatus`.
+    /// 0 = Ongoing, 1 = Approved, 2 = Rejected, 3 = Cancelled, 4 = TimedOut, 5 = Killed
+
+    /// @dev Vote on an ongoing referendum.
+    /// @param pollIndex Index of the referendum.
+    /// @param aye Whether the vote is in favour.
+    /// @param conviction Conviction of the vote, between 0 and 6.
+    /// @param voteAmount Balance locked for the vote.
+    function vote(uint32 pollIndex, bool aye, uint8 conviction, uint256 voteAmount)
+        external
+        returns (bool);
+
+    /// @dev Remove the caller's vote on a referendum.
+    function removeVote(uint32 pollIndex) external returns (bool);
+
+    /// @dev Delegate the caller's voting power on a track.
+    /// @param trackId Track the delegation applies to.
+    /// @param representative Account receiving the voting power.
+    /// @param conviction Conviction of the delegation, between 0 and 6.
+    /// @param amount Balance locked for the delegation.
+    function delegate(uint16 trackId, address representative, uint8 conviction, uint256 amount)
+        external
+        returns (bool);
+
+    /// @dev Remove the caller's delegation on a track.
+    function undelegate(uint16 trackId) external returns (bool);
+
+    /// @dev Submit a referendum on a track, the call must already be noted as preimage.
+    /// @param trackId Track the referendum is submitted on, it determines the dispatch origin.
+    /// @param proposalHash Hash of the preimage of the proposed call.
+    /// @param enactmentDelay Number of blocks to wait after approval before enacting.
+    /// @return referendumIndex Index of the new referendum.
+    function submit(uint16 trackId, bytes32 proposalHash, uint32 enactmentDelay)
+        external
+        returns (uint32 referendumIndex);
+
+    /// @dev Place the decision deposit of a referendum.
+    function placeDecisionDeposit(uint32 index) external returns (bool);
+
+    /// @dev Status of a referendum, reverts if it does not exist.
+    function referendumStatus(uint32 index) external view returns (uint8);
+
+    /// @dev Tally of an ongoing referendum.
+    function referendumTally(uint32 index)
+        external
+        view
+        returns (uint256 ayes, uint256 nays, uint256 support);
+
+    /// @dev Parameters of a track.
+    function trackInfo(uint16 trackId)
+        external
+        view
+        returns (
+            string memory name,
+            uint32 maxDeciding,
+            uint256 decisionDeposit,
+            uint32 preparePeriod,
+            uint32 decisionPeriod,
+            uint32 confirmPeriod,
+            uint32 minEnactmentPeriod
+        );
+
+    /// @dev Emitted when `voter` votes on a referendum.
+    event Voted(
+        uint32 indexed pollIndex,
+        address voter,
+        bool aye,
+        uint256 voteAmount,
+        uint8 conviction
+    );
+
+    /// @dev Emitted when `voter` removes their vote.
+    event VoteRemoved(uint32 indexed pollIndex, address voter);
+
+    /// @dev Emitted when `caller` delegates to `representative`.
+    event Delegated(
+        uint16 indexed trackId,
+        address caller,
+        address representative,
+        uint256 amount,
+        uint8 conviction
+    );
+
+    /// @dev Emitted when `caller` removes their delegation.
+    event Undelegated(uint16 indexed trackId, address caller);
+
+    /// @dev Emitted when a referendum is submitted.
+    event Submitted(uint32 indexed referendumIndex, uint16 trackId, bytes32 proposalHash);
+
+    /// @dev Emitted when the decision deposit of a referendum is placed.
+    event DecisionDepositPlaced(uint32 indexed index, address caller, uint256 amount);
+}
@@ -0,0 +1,499 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Precompile exposing OpenGov (`pallet_referenda` and `pallet_conviction_voting`) to EVM
+//! accounts.
+//!
+//! Every state changing function emits a log mirroring the event deposited by the pallet, so
+//! dApps can index governance activity from the EVM side only.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use fp_evm::{PrecompileHandle, PrecompileOutput};
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	sp_runtime::traits::StaticLookup,
+	traits::{schedule::DispatchTime, Bounded, Polling, QueryPreimage},
+};
+use pallet_conviction_voting::{AccountVote, Conviction, Vote};
+use pallet_evm::{AddressMapping, Precompile};
+use pallet_referenda::{PalletsOriginOf, ReferendumIndex, ReferendumInfo, TracksInfo};
+use precompile_utils::{
+	keccak256, revert, succeed, Address, Bytes, EvmData, EvmDataWriter, EvmResult,
+	FunctionModifier, LogExt, LogsBuilder, PrecompileHandleExt, RuntimeHelper,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::{
+	boxed::Box,
+	convert::{TryFrom, TryInto},
+	marker::PhantomData,
+};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Solidity selector of the Voted log.
+pub const SELECTOR_LOG_VOTED: [u8; 32] = keccak256!("Voted(uint32,address,bool,uint256,uint8)");
+
+/// Solidity selector of the VoteRemoved log.
+pub const SELECTOR_LOG_VOTE_REMOVED: [u8; 32] = keccak256!("VoteRemoved(uint32,address)");
+
+/// Solidity selector of the Delegated log.
+pub const SELECTOR_LOG_DELEGATED: [u8; 32] =
+	keccak256!("Delegated(uint16,address,address,uint256,uint8)");
+
+/// Solidity selector of the Undelegated log.
+pub const SELECTOR_LOG_UNDELEGATED: [u8; 32] = keccak256!("Undelegated(uint16,address)");
+
+/// Solidity selector of the Submitted log.
+pub const SELECTOR_LOG_SUBMITTED: [u8; 32] = keccak256!("Submitted(uint32,uint16,bytes32)");
+
+/// Solidity selector of the DecisionDepositPlaced log.
+pub const SELECTOR_LOG_DECISION_DEPOSIT_PLACED: [u8; 32] =
+	keccak256!("DecisionDepositPlaced(uint32,address,uint256)");
+
+/// Alias for the voting balance type of the provided Runtime.
+pub type VotingBalanceOf<Runtime> = pallet_conviction_voting::BalanceOf<Runtime>;
+
+/// Alias for the referenda balance type of the provided Runtime.
+pub type ReferendaBalanceOf<Runtime> = pallet_referenda::BalanceOf<Runtime>;
+
+/// Alias for the poll index type of the provided Runtime.
+pub type PollIndexOf<Runtime> = pallet_conviction_voting::PollIndexOf<Runtime>;
+
+/// Alias for the voting class type of the provided Runtime.
+pub type ClassOf<Runtime> = pallet_conviction_voting::ClassOf<Runtime>;
+
+/// Alias for the track id type of the provided Runtime.
+pub type TrackIdOf<Runtime> = pallet_referenda::TrackIdOf<Runtime, ()>;
+
+#[precompile_utils::generate_function_selector]
+#[event("Voted(uint32 indexed,address,bool,uint256,uint8)")]
+#[event("VoteRemoved(uint32 indexed,address)")]
+#[event("Delegated(uint16 indexed,address,address,uint256,uint8)")]
+#[event("Undelegated(uint16 indexed,address)")]
+#[event("Submitted(uint32 indexed,uint16,bytes32)")]
+#[event("DecisionDepositPlaced(uint32 indexed,address,uint256)")]
+#[derive(Debug, PartialEq)]
+pub enum Action {
+	#[returns("bool")]
+	Vote = "vote(uint32,bool,uint8,uint256)",
+	#[returns("bool")]
+	RemoveVote = "removeVote(uint32)",
+	#[returns("bool")]
+	Delegate = "delegate(uint16,address,uint8,uint256)",
+	#[returns("bool")]
+	Undelegate = "undelegate(uint16)",
+	#[returns("uint32")]
+	Submit = "submit(uint16,bytes32,uint32)",
+	#[returns("bool")]
+	PlaceDecisionDeposit = "placeDecisionDeposit(uint32)",
+	#[view]
+	#[returns("uint8")]
+	ReferendumStatus = "referendumStatus(uint32)",
+	#[view]
+	#[returns("uint256,uint256,uint256")]
+	ReferendumTally = "referendumTally(uint32)",
+	#[view]
+	#[returns("bytes,uint32,uint256,uint32,uint32,uint32,uint32")]
+	TrackInfo = "trackInfo(uint16)",
+}
+
+/// Status of a referendum as returned by `referendumStatus`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ReferendumStatus {
+	Ongoing = 0,
+	Approved = 1,
+	Rejected = 2,
+	Cancelled = 3,
+	TimedOut = 4,
+	Killed = 5,
+}
+
+/// Resolves the origin a proposal is dispatched with from the track it is submitted on.
+///
+/// This is the inverse of `TracksInfo::track_for`, which the runtime is expected to implement on
+/// the same type.
+pub trait TrackOrigin<Origin> {
+	fn track_origin(track_id: u16) -> Option<Origin>;
+}
+
+/// A precompile to wrap the functionality from pallet-referenda and pallet-conviction-voting.
+pub struct GovernancePrecompile<Runtime, Origins>(PhantomData<(Runtime, Origins)>);
+
+impl<Runtime, Origins> Precompile for GovernancePrecompile<Runtime, Origins>
+where
+	Runtime: pallet_referenda::Config + pallet_conviction_voting::Config + pallet_evm::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<pallet_referenda::Call<Runtime>>,
+	Runtime::RuntimeCall: From<pallet_conviction_voting::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	Origins: TrackOrigin<PalletsOriginOf<Runtime>>,
+	VotingBalanceOf<Runtime>: TryFrom<U256> + Into<U256> + EvmData,
+	ReferendaBalanceOf<Runtime>: Into<U256>,
+	PollIndexOf<Runtime>: TryFrom<u32>,
+	ClassOf<Runtime>: TryFrom<u16>,
+	TrackIdOf<Runtime>: TryFrom<u16>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		log::trace!(target: "governance-precompile", "In governance precompile");
+
+		let selector = handle.read_selector()?;
+
+		handle.check_function_modifier(match selector {
+			Action::ReferendumStatus | Action::ReferendumTally | Action::TrackInfo =>
+				FunctionModifier::View,
+			_ => FunctionModifier::NonPayable,
+		})?;
+
+		match selector {
+			// Conviction voting
+			Action::Vote => Self::vote(handle),
+			Action::RemoveVote => Self::remove_vote(handle),
+			Action::Delegate => Self::delegate(handle),
+			Action::Undelegate => Self::undelegate(handle),
+			// Referenda
+			Action::Submit => Self::submit(handle),
+			Action::PlaceDecisionDeposit => Self::place_decision_deposit(handle),
+			// Storage getters
+			Action::ReferendumStatus => Self::referendum_status(handle),
+			Action::ReferendumTally => Self::referendum_tally(handle),
+			Action::TrackInfo => Self::track_info(handle),
+		}
+	}
+}
+
+impl<Runtime, Origins> GovernancePrecompile<Runtime, Origins>
+where
+	Runtime: pallet_referenda::Config + pallet_conviction_voting::Config + pallet_evm::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<pallet_referenda::Call<Runtime>>,
+	Runtime::RuntimeCall: From<pallet_conviction_voting::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	Origins: TrackOrigin<PalletsOriginOf<Runtime>>,
+	VotingBalanceOf<Runtime>: TryFrom<U256> + Into<U256> + EvmData,
+	ReferendaBalanceOf<Runtime>: Into<U256>,
+	PollIndexOf<Runtime>: TryFrom<u32>,
+	ClassOf<Runtime>: TryFrom<u16>,
+	TrackIdOf<Runtime>: TryFrom<u16>,
+{
+	fn vote(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_log_costs_manual(2, 32 * 4)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(4)?;
+
+		let poll_index = input.read::<u32>()?;
+		let aye = input.read::<bool>()?;
+		let conviction_raw = input.read::<u8>()?;
+		let balance = input.read::<VotingBalanceOf<Runtime>>()?;
+
+		let conviction = Self::parse_conviction(conviction_raw)?;
+		let vote = AccountVote::Standard { vote: Vote { aye, conviction }, balance };
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_conviction_voting::Call::<Runtime>::vote {
+				poll_index: Self::parse_poll_index(poll_index)?,
+				vote,
+			},
+		)?;
+
+		LogsBuilder::new(handle.context().address)
+			.log2(
+				SELECTOR_LOG_VOTED,
+				H256::from_low_u64_be(poll_index as u64),
+				EvmDataWriter::new()
+					.write(Address(handle.context().caller))
+					.write(aye)
+					.write::<U256>(balance.into())
+					.write(conviction_raw)
+					.build(),
+			)
+			.record(handle)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn remove_vote(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_log_costs_manual(2, 32)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let poll_index = input.read::<u32>()?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_conviction_voting::Call::<Runtime>::remove_vote {
+				class: None,
+				index: Self::parse_poll_index(poll_index)?,
+			},
+		)?;
+
+		LogsBuilder::new(handle.context().address)
+			.log2(
+				SELECTOR_LOG_VOTE_REMOVED,
+				H256::from_low_u64_be(poll_index as u64),
+				EvmDataWriter::new().write(Address(handle.context().caller)).build(),
+			)
+			.record(handle)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn delegate(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_log_costs_manual(2, 32 * 4)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(4)?;
+
+		let track_id = input.read::<u16>()?;
+		let representative: H160 = input.read::<Address>()?.into();
+		let conviction_raw = input.read::<u8>()?;
+		let balance = input.read::<VotingBalanceOf<Runtime>>()?;
+
+		let class = Self::parse_class(track_id)?;
+		let conviction = Self::parse_conviction(conviction_raw)?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let to = Runtime::AddressMapping::into_account_id(representative);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_conviction_voting::Call::<Runtime>::delegate {
+				class,
+				to: Runtime::Lookup::unlookup(to),
+				conviction,
+				balance,
+			},
+		)?;
+
+		LogsBuilder::new(handle.context().address)
+			.log2(
+				SELECTOR_LOG_DELEGATED,
+				H256::from_low_u64_be(track_id as u64),
+				EvmDataWriter::new()
+					.write(Address(handle.context().caller))
+					.write(Address(representative))
+					.write::<U256>(balance.into())
+					.write(conviction_raw)
+					.build(),
+			)
+			.record(handle)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn undelegate(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_log_costs_manual(2, 32)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let track_id = input.read::<u16>()?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_conviction_voting::Call::<Runtime>::undelegate {
+				class: Self::parse_class(track_id)?,
+			},
+		)?;
+
+		LogsBuilder::new(handle.context().address)
+			.log2(
+				SELECTOR_LOG_UNDELEGATED,
+				H256::from_low_u64_be(track_id as u64),
+				EvmDataWriter::new().write(Address(handle.context().caller)).build(),
+			)
+			.record(handle)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn submit(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_log_costs_manual(2, 32 * 2)?;
+		// Storage items: ReferendumCount, PreimageFor (length only)
+		handle.record_db_read::<Runtime>(4)?;
+		handle.record_db_read::<Runtime>(52)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+
+		let track_id = input.read::<u16>()?;
+		let preimage_hash = input.read::<H256>()?;
+		let enactment = input.read::<u32>()?;
+
+		let proposal_origin =
+			Origins::track_origin(track_id).ok_or_else(|| revert("Unknown track"))?;
+		let len = <Runtime as pallet_referenda::Config>::Preimages::len(&preimage_hash)
+			.ok_or_else(|| revert("Preimage not found"))?;
+
+		let referendum_index = pallet_referenda::ReferendumCount::<Runtime>::get();
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_referenda::Call::<Runtime>::submit {
+				proposal_origin: Box::new(proposal_origin),
+				proposal: Bounded::Lookup { hash: preimage_hash, len },
+				enactment_moment: DispatchTime::After(enactment.into()),
+			},
+		)?;
+
+		LogsBuilder::new(handle.context().address)
+			.log2(
+				SELECTOR_LOG_SUBMITTED,
+				H256::from_low_u64_be(referendum_index as u64),
+				EvmDataWriter::new().write(track_id).write(preimage_hash).build(),
+			)
+			.record(handle)?;
+
+		Ok(succeed(EvmDataWriter::new().write(referendum_index).build()))
+	}
+
+	fn place_decision_deposit(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_log_costs_manual(2, 32 * 2)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let index = input.read::<u32>()?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_referenda::Call::<Runtime>::place_decision_deposit { index },
+		)?;
+
+		// The deposit is not part of the call, read it back from the referendum.
+		handle.record_db_read::<Runtime>(REFERENDUM_INFO_SIZE)?;
+		let amount: U256 = match pallet_referenda::ReferendumInfoFor::<Runtime>::get(index) {
+			Some(ReferendumInfo::Ongoing(status)) =>
+				status.decision_deposit.map(|deposit| deposit.amount.into()).unwrap_or_default(),
+			_ => U256::zero(),
+		};
+
+		LogsBuilder::new(handle.context().address)
+			.log2(
+				SELECTOR_LOG_DECISION_DEPOSIT_PLACED,
+				H256::from_low_u64_be(index as u64),
+				EvmDataWriter::new()
+					.write(Address(handle.context().caller))
+					.write(amount)
+					.build(),
+			)
+			.record(handle)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn referendum_status(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_db_read::<Runtime>(REFERENDUM_INFO_SIZE)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let index: ReferendumIndex = input.read::<u32>()?;
+
+		let status = match pallet_referenda::ReferendumInfoFor::<Runtime>::get(index)
+			.ok_or_else(|| revert("Referendum not found"))?
+		{
+			ReferendumInfo::Ongoing(..) => ReferendumStatus::Ongoing,
+			ReferendumInfo::Approved(..) => ReferendumStatus::Approved,
+			ReferendumInfo::Rejected(..) => ReferendumStatus::Rejected,
+			ReferendumInfo::Cancelled(..) => ReferendumStatus::Cancelled,
+			ReferendumInfo::TimedOut(..) => ReferendumStatus::TimedOut,
+			ReferendumInfo::Killed(..) => ReferendumStatus::Killed,
+		};
+
+		Ok(succeed(EvmDataWriter::new().write(status as u8).build()))
+	}
+
+	fn referendum_tally(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		handle.record_db_read::<Runtime>(REFERENDUM_INFO_SIZE)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let index = Self::parse_poll_index(input.read::<u32>()?)?;
+
+		let (tally, _class) =
+			<Runtime as pallet_conviction_voting::Config>::Polls::as_ongoing(index)
+				.ok_or_else(|| revert("Referendum is not ongoing"))?;
+
+		Ok(succeed(
+			EvmDataWriter::new()
+				.write::<U256>(tally.ayes.into())
+				.write::<U256>(tally.nays.into())
+				.write::<U256>(tally.support.into())
+				.build(),
+		))
+	}
+
+	fn track_info(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let track_id = input.read::<u16>()?;
+		let track_id: TrackIdOf<Runtime> =
+			track_id.try_into().map_err(|_| revert("Track id is too large"))?;
+
+		let info = <Runtime as pallet_referenda::Config>::Tracks::info(track_id)
+			.ok_or_else(|| revert("Unknown track"))?;
+
+		Ok(succeed(
+			EvmDataWriter::new()
+				.write::<Bytes>(info.name.into())
+				.write(info.max_deciding)
+				.write::<U256>(info.decision_deposit.into())
+				.write::<u32>(info.prepare_period.unique_saturated_into())
+				.write::<u32>(info.decision_period.unique_saturated_into())
+				.write::<u32>(info.confirm_period.unique_saturated_into())
+				.write::<u32>(info.min_enactment_period.unique_saturated_into())
+				.build(),
+		))
+	}
+
+	fn parse_conviction(conviction: u8) -> EvmResult<Conviction> {
+		conviction.try_into().map_err(|_| revert("Conviction must be between 0 and 6"))
+	}
+
+	fn parse_poll_index(index: u32) -> EvmResult<PollIndexOf<Runtime>> {
+		index.try_into().map_err(|_| revert("Poll index is too large"))
+	}
+
+	fn parse_class(track_id: u16) -> EvmResult<ClassOf<Runtime>> {
+		track_id.try_into().map_err(|_| revert("Track id is too large"))
+	}
+}
+
+/// Storage item: ReferendumInfoFor:
+/// Blake2_128(16) + ReferendumIndex(4) + ReferendumInfo(ReferendumStatus with an inlined origin,
+/// a bounded call lookup, two deposits, the deciding status, the tally and the alarm)
+const REFERENDUM_INFO_SIZE: usize = 366;
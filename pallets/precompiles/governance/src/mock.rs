@@ -0,0 +1,343 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Testing utilities.
+
+use super::*;
+
+use fp_evm::IsPrecompileResult;
+use frame_support::{
+    construct_runtime, parameter_types,
+    traits::{ConstU32, ConstU64, EqualPrivilegeOnly, Everything, TotalIssuanceOf},
+    weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureSigned};
+use pallet_evm::{EnsureAddressNever, EnsureAddressRoot, PrecompileResult, PrecompileSet};
+use pallet_referenda::{Curve, TrackInfo};
+use sp_core::{crypto::AccountId32, H160, H256};
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage, Perbill,
+};
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+pub type BlockNumber = u64;
+pub type Block = frame_system::mocking::MockBlock<Runtime>;
+
+pub const PRECOMPILE_ADDRESS: H160 = H160::repeat_byte(0x50);
+
+/// Maps an H160 into the first 20 bytes of an `AccountId32`, which keeps test accounts readable.
+pub struct MockAddressMapping;
+
+impl AddressMapping<AccountId> for MockAddressMapping {
+    fn into_account_id(address: H160) -> AccountId {
+        let mut data = [0u8; 32];
+        data[0..20].copy_from_slice(&address[..]);
+        AccountId::from(data)
+    }
+}
+
+pub fn account(address: impl Into<H160>) -> AccountId {
+    MockAddressMapping::into_account_id(address.into())
+}
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for Runtime {
+    type BaseCallFilter = Everything;
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type Nonce = u64;
+    type Block = Block;
+    type RuntimeCall = RuntimeCall;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type BlockWeights = ();
+    type BlockLength = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TestPrecompileSet<R>(PhantomData<R>);
+
+impl<R> PrecompileSet for TestPrecompileSet<R>
+where
+    R: pallet_evm::Config,
+    GovernancePrecompile<R, TestTracksInfo>: Precompile,
+{
+    fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+        match handle.code_address() {
+            a if a == PRECOMPILE_ADDRESS => {
+                Some(GovernancePrecompile::<R, TestTracksInfo>::execute(handle))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
+        IsPrecompileResult::Answer {
+            is_precompile: address == PRECOMPILE_ADDRESS,
+            extra_cost: 0,
+        }
+    }
+}
+
+parameter_types! {
+    pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Runtime {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u128 = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+    type MaxReserves = ConstU32<50>;
+    type ReserveIdentifier = [u8; 8];
+    type MaxLocks = ConstU32<50>;
+    type Balance = Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type RuntimeHoldReason = ();
+    type FreezeIdentifier = ();
+    type MaxHolds = ();
+    type MaxFreezes = ();
+}
+
+impl pallet_preimage::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type ManagerOrigin = EnsureRoot<AccountId>;
+    type BaseDeposit = ();
+    type ByteDeposit = ();
+}
+
+parameter_types! {
+    pub MaximumSchedulerWeight: Weight = Weight::from_parts(2_000_000_000_000, u64::MAX);
+}
+
+impl pallet_scheduler::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type PalletsOrigin = OriginCaller;
+    type RuntimeCall = RuntimeCall;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type ScheduleOrigin = EnsureRoot<AccountId>;
+    type MaxScheduledPerBlock = ConstU32<100>;
+    type WeightInfo = ();
+    type OriginPrivilegeCmp = EqualPrivilegeOnly;
+    type Preimages = Preimage;
+}
+
+/// A single `root` track, enough to exercise submission and voting.
+pub struct TestTracksInfo;
+
+pub const ROOT_TRACK: u16 = 0;
+pub const DECISION_DEPOSIT: Balance = 10;
+pub const SUBMISSION_DEPOSIT: Balance = 2;
+
+impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TestTracksInfo {
+    type Id = u16;
+    type RuntimeOrigin = OriginCaller;
+
+    fn tracks() -> &'static [(Self::Id, TrackInfo<Balance, BlockNumber>)] {
+        static DATA: [(u16, TrackInfo<Balance, BlockNumber>); 1] = [(
+            ROOT_TRACK,
+            TrackInfo {
+                name: "root",
+                max_deciding: 1,
+                decision_deposit: DECISION_DEPOSIT,
+                prepare_period: 4,
+                decision_period: 4,
+                confirm_period: 2,
+                min_enactment_period: 4,
+                min_approval: Curve::LinearDecreasing {
+                    length: Perbill::from_percent(100),
+                    floor: Perbill::from_percent(50),
+                    ceil: Perbill::from_percent(100),
+                },
+                min_support: Curve::LinearDecreasing {
+                    length: Perbill::from_percent(100),
+                    floor: Perbill::from_percent(0),
+                    ceil: Perbill::from_percent(100),
+                },
+            },
+        )];
+        &DATA[..]
+    }
+
+    fn track_for(origin: &Self::RuntimeOrigin) -> Result<Self::Id, ()> {
+        match origin {
+            OriginCaller::system(frame_system::RawOrigin::Root) => Ok(ROOT_TRACK),
+            _ => Err(()),
+        }
+    }
+}
+
+pallet_referenda::impl_tracksinfo_get!(TestTracksInfo, Balance, BlockNumber);
+
+impl TrackOrigin<OriginCaller> for TestTracksInfo {
+    fn track_origin(track_id: u16) -> Option<OriginCaller> {
+        match track_id {
+            ROOT_TRACK => Some(frame_system::RawOrigin::Root.into()),
+            _ => None,
+        }
+    }
+}
+
+parameter_types! {
+    pub const SubmissionDeposit: Balance = SUBMISSION_DEPOSIT;
+}
+
+impl pallet_referenda::Config for Runtime {
+    type WeightInfo = ();
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type Scheduler = Scheduler;
+    type Currency = Balances;
+    type SubmitOrigin = EnsureSigned<AccountId>;
+    type CancelOrigin = EnsureRoot<AccountId>;
+    type KillOrigin = EnsureRoot<AccountId>;
+    type Slash = ();
+    type Votes = pallet_conviction_voting::VotesOf<Runtime>;
+    type Tally = pallet_conviction_voting::TallyOf<Runtime>;
+    type SubmissionDeposit = SubmissionDeposit;
+    type MaxQueued = ConstU32<3>;
+    type UndecidingTimeout = ConstU64<20>;
+    type AlarmInterval = ConstU64<1>;
+    type Tracks = TestTracksInfo;
+    type Preimages = Preimage;
+}
+
+impl pallet_conviction_voting::Config for Runtime {
+    type WeightInfo = ();
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type VoteLockingPeriod = ConstU64<3>;
+    type MaxVotes = ConstU32<3>;
+    type MaxTurnout = TotalIssuanceOf<Balances, AccountId>;
+    type Polls = Referenda;
+}
+
+parameter_types! {
+    pub const PrecompilesValue: TestPrecompileSet<Runtime> =
+        TestPrecompileSet(PhantomData);
+    pub const WeightPerGas: Weight = Weight::from_parts(1, 0);
+}
+
+impl pallet_evm::Config for Runtime {
+    type FeeCalculator = ();
+    type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+    type WeightPerGas = WeightPerGas;
+    type CallOrigin = EnsureAddressRoot<AccountId>;
+    type WithdrawOrigin = EnsureAddressNever<AccountId>;
+    type AddressMapping = MockAddressMapping;
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type Runner = pallet_evm::runner::stack::Runner<Self>;
+    type PrecompilesType = TestPrecompileSet<Self>;
+    type PrecompilesValue = PrecompilesValue;
+    type Timestamp = Timestamp;
+    type ChainId = ();
+    type OnChargeTransaction = ();
+    type BlockGasLimit = ();
+    type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+    type OnCreate = ();
+    type FindAuthor = ();
+    type WeightInfo = ();
+    type GasLimitPovSizeRatio = ConstU64<4>;
+}
+
+// Configure a mock runtime to test the pallet.
+construct_runtime! {
+    pub enum Runtime
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Evm: pallet_evm,
+        Timestamp: pallet_timestamp,
+        Preimage: pallet_preimage,
+        Scheduler: pallet_scheduler,
+        Referenda: pallet_referenda,
+        ConvictionVoting: pallet_conviction_voting,
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ExtBuilder {
+    balances: Vec<(AccountId, Balance)>,
+}
+
+impl ExtBuilder {
+    pub(crate) fn with_balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
+        self.balances = balances;
+        self
+    }
+
+    pub(crate) fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::<Runtime>::default()
+            .build_storage()
+            .expect("Frame system builds valid default genesis config");
+
+        pallet_balances::GenesisConfig::<Runtime> {
+            balances: self.balances,
+        }
+        .assimilate_storage(&mut t)
+        .expect("Pallet balances storage can be assimilated");
+
+        let mut ext = sp_io::TestExternalities::new(t);
+        ext.execute_with(|| System::set_block_number(1));
+        ext
+    }
+}
+
+/// Notes a `remark` call as preimage and returns its hash.
+pub(crate) fn note_preimage(who: AccountId) -> H256 {
+    use parity_scale_codec::Encode;
+    use sp_runtime::traits::Hash;
+
+    let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+    let encoded = call.encode();
+    Preimage::note_preimage(RuntimeOrigin::signed(who), encoded.clone())
+        .expect("Preimage can be noted");
+    BlakeTwo256::hash(&encoded)
+}
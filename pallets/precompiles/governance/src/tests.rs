@@ -0,0 +1,473 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::mock::*;
+use crate::*;
+
+use precompile_utils::testing::*;
+
+fn precompiles() -> TestPrecompileSet<Runtime> {
+    PrecompilesValue::get()
+}
+
+fn submit(hash: H256) {
+    precompiles()
+        .prepare_test(
+            Alice,
+            PRECOMPILE_ADDRESS,
+            EvmDataWriter::new_with_selector(Action::Submit)
+                .write(ROOT_TRACK)
+                .write(hash)
+                .write(5u32)
+                .build(),
+        )
+        .expect_log(LogsBuilder::new(PRECOMPILE_ADDRESS).log2(
+            SELECTOR_LOG_SUBMITTED,
+            H256::zero(),
+            EvmDataWriter::new().write(ROOT_TRACK).write(hash).build(),
+        ))
+        .execute_returns(EvmDataWriter::new().write(0u32).build());
+}
+
+fn vote(voter: impl Into<H160>, aye: bool, conviction: u8, amount: u128) {
+    precompiles()
+        .prepare_test(
+            voter,
+            PRECOMPILE_ADDRESS,
+            EvmDataWriter::new_with_selector(Action::Vote)
+                .write(0u32)
+                .write(aye)
+                .write(conviction)
+                .write(U256::from(amount))
+                .build(),
+        )
+        .execute_returns(EvmDataWriter::new().write(true).build());
+}
+
+#[test]
+fn selector_less_than_four_bytes() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Alice, PRECOMPILE_ADDRESS, vec![1u8, 2u8, 3u8])
+            .execute_reverts(|output| output == b"tried to parse selector out of bounds");
+    });
+}
+
+#[test]
+fn no_selector_exists_but_length_is_right() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Alice, PRECOMPILE_ADDRESS, vec![1u8, 2u8, 3u8, 4u8])
+            .execute_reverts(|output| output == b"unknown selector");
+    });
+}
+
+#[test]
+fn submit_works() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            let hash = note_preimage(account(Alice));
+            submit(hash);
+
+            assert_eq!(pallet_referenda::ReferendumCount::<Runtime>::get(), 1);
+            assert_eq!(
+                Balances::reserved_balance(account(Alice)),
+                SUBMISSION_DEPOSIT
+            );
+
+            precompiles()
+                .prepare_test(
+                    Bob,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::ReferendumStatus)
+                        .write(0u32)
+                        .build(),
+                )
+                .expect_no_logs()
+                .execute_returns(
+                    EvmDataWriter::new()
+                        .write(ReferendumStatus::Ongoing as u8)
+                        .build(),
+                );
+        });
+}
+
+#[test]
+fn submit_on_unknown_track_reverts() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            let hash = note_preimage(account(Alice));
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Submit)
+                        .write(7u16)
+                        .write(hash)
+                        .write(5u32)
+                        .build(),
+                )
+                .execute_reverts(|output| output == b"Unknown track");
+        });
+}
+
+#[test]
+fn submit_without_preimage_reverts() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Submit)
+                        .write(ROOT_TRACK)
+                        .write(H256::repeat_byte(0x11))
+                        .write(5u32)
+                        .build(),
+                )
+                .execute_reverts(|output| output == b"Preimage not found");
+        });
+}
+
+#[test]
+fn place_decision_deposit_works() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000), (account(Bob), 1000)])
+        .build()
+        .execute_with(|| {
+            let hash = note_preimage(account(Alice));
+            submit(hash);
+
+            precompiles()
+                .prepare_test(
+                    Bob,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::PlaceDecisionDeposit)
+                        .write(0u32)
+                        .build(),
+                )
+                .expect_log(LogsBuilder::new(PRECOMPILE_ADDRESS).log2(
+                    SELECTOR_LOG_DECISION_DEPOSIT_PLACED,
+                    H256::zero(),
+                    EvmDataWriter::new()
+                        .write(Address(Bob.into()))
+                        .write(U256::from(DECISION_DEPOSIT))
+                        .build(),
+                ))
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            assert_eq!(
+                Balances::reserved_balance(account(Bob)),
+                DECISION_DEPOSIT
+            );
+        });
+}
+
+#[test]
+fn vote_updates_tally() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            (account(Alice), 1000),
+            (account(Bob), 1000),
+            (account(Charlie), 1000),
+        ])
+        .build()
+        .execute_with(|| {
+            let hash = note_preimage(account(Alice));
+            submit(hash);
+
+            precompiles()
+                .prepare_test(
+                    Bob,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Vote)
+                        .write(0u32)
+                        .write(true)
+                        .write(1u8)
+                        .write(U256::from(100))
+                        .build(),
+                )
+                .expect_log(LogsBuilder::new(PRECOMPILE_ADDRESS).log2(
+                    SELECTOR_LOG_VOTED,
+                    H256::zero(),
+                    EvmDataWriter::new()
+                        .write(Address(Bob.into()))
+                        .write(true)
+                        .write(U256::from(100))
+                        .write(1u8)
+                        .build(),
+                ))
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            vote(Charlie, false, 2, 20);
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::ReferendumTally)
+                        .write(0u32)
+                        .build(),
+                )
+                .expect_no_logs()
+                .execute_returns(
+                    EvmDataWriter::new()
+                        .write(U256::from(100))
+                        .write(U256::from(40))
+                        .write(U256::from(100))
+                        .build(),
+                );
+        });
+}
+
+#[test]
+fn vote_with_invalid_conviction_reverts() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            let hash = note_preimage(account(Alice));
+            submit(hash);
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Vote)
+                        .write(0u32)
+                        .write(true)
+                        .write(7u8)
+                        .write(U256::from(100))
+                        .build(),
+                )
+                .execute_reverts(|output| output == b"Conviction must be between 0 and 6");
+        });
+}
+
+#[test]
+fn vote_on_unknown_referendum_fails() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Vote)
+                        .write(3u32)
+                        .write(true)
+                        .write(1u8)
+                        .write(U256::from(100))
+                        .build(),
+                )
+                .execute_reverts(|output| {
+                    output.starts_with(b"Dispatched call failed with error: ")
+                });
+        });
+}
+
+#[test]
+fn remove_vote_works() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000), (account(Bob), 1000)])
+        .build()
+        .execute_with(|| {
+            let hash = note_preimage(account(Alice));
+            submit(hash);
+            vote(Bob, true, 1, 100);
+
+            precompiles()
+                .prepare_test(
+                    Bob,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::RemoveVote)
+                        .write(0u32)
+                        .build(),
+                )
+                .expect_log(LogsBuilder::new(PRECOMPILE_ADDRESS).log2(
+                    SELECTOR_LOG_VOTE_REMOVED,
+                    H256::zero(),
+                    EvmDataWriter::new().write(Address(Bob.into())).build(),
+                ))
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::ReferendumTally)
+                        .write(0u32)
+                        .build(),
+                )
+                .execute_returns(
+                    EvmDataWriter::new()
+                        .write(U256::zero())
+                        .write(U256::zero())
+                        .write(U256::zero())
+                        .build(),
+                );
+        });
+}
+
+#[test]
+fn delegate_and_undelegate() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000), (account(Bob), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Delegate)
+                        .write(ROOT_TRACK)
+                        .write(Address(Bob.into()))
+                        .write(1u8)
+                        .write(U256::from(100))
+                        .build(),
+                )
+                .expect_log(LogsBuilder::new(PRECOMPILE_ADDRESS).log2(
+                    SELECTOR_LOG_DELEGATED,
+                    H256::zero(),
+                    EvmDataWriter::new()
+                        .write(Address(Alice.into()))
+                        .write(Address(Bob.into()))
+                        .write(U256::from(100))
+                        .write(1u8)
+                        .build(),
+                ))
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            assert!(matches!(
+                pallet_conviction_voting::VotingFor::<Runtime>::get(account(Alice), ROOT_TRACK),
+                pallet_conviction_voting::Voting::Delegating(..)
+            ));
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Undelegate)
+                        .write(ROOT_TRACK)
+                        .build(),
+                )
+                .expect_log(LogsBuilder::new(PRECOMPILE_ADDRESS).log2(
+                    SELECTOR_LOG_UNDELEGATED,
+                    H256::zero(),
+                    EvmDataWriter::new().write(Address(Alice.into())).build(),
+                ))
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            assert!(matches!(
+                pallet_conviction_voting::VotingFor::<Runtime>::get(account(Alice), ROOT_TRACK),
+                pallet_conviction_voting::Voting::Casting(..)
+            ));
+        });
+}
+
+#[test]
+fn undelegate_without_delegation_fails() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Undelegate)
+                        .write(ROOT_TRACK)
+                        .build(),
+                )
+                .execute_reverts(|output| {
+                    output.starts_with(b"Dispatched call failed with error: ")
+                });
+        });
+}
+
+#[test]
+fn referendum_status_of_unknown_referendum_reverts() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::ReferendumStatus)
+                    .write(0u32)
+                    .build(),
+            )
+            .execute_reverts(|output| output == b"Referendum not found");
+    });
+}
+
+#[test]
+fn referendum_tally_of_unknown_referendum_reverts() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::ReferendumTally)
+                    .write(0u32)
+                    .build(),
+            )
+            .execute_reverts(|output| output == b"Referendum is not ongoing");
+    });
+}
+
+#[test]
+fn track_info_works() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::TrackInfo)
+                    .write(ROOT_TRACK)
+                    .build(),
+            )
+            .expect_no_logs()
+            .execute_returns(
+                EvmDataWriter::new()
+                    .write::<Bytes>("root".into())
+                    .write(1u32)
+                    .write(U256::from(DECISION_DEPOSIT))
+                    .write(4u32)
+                    .write(4u32)
+                    .write(2u32)
+                    .write(4u32)
+                    .build(),
+            );
+
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::TrackInfo)
+                    .write(7u16)
+                    .build(),
+            )
+            .execute_reverts(|output| output == b"Unknown track");
+    });
+}
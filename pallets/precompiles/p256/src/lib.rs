@@ -0,0 +1,86 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RIP-7212 `P256VERIFY` precompile, verifying secp256r1 signatures of a message hash.
+//!
+//! The input is the 160 bytes `hash || r || s || x || y`. A valid signature returns
+//! `uint256(1)`, anything else, including malformed input, returns empty output.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use fp_evm::{ExitSucceed, PrecompileHandle, PrecompileOutput, PrecompileResult};
+use p256::{
+	ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey},
+	EncodedPoint, FieldBytes,
+};
+use pallet_evm::Precompile;
+use sp_std::{marker::PhantomData, prelude::*};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Gas charged for every call, valid or not.
+pub const P256_VERIFY_GAS: u64 = 3_450;
+
+/// Length of the `hash || r || s || x || y` input.
+const INPUT_LEN: usize = 160;
+
+/// A precompile verifying secp256r1 signatures.
+pub struct P256VerifyPrecompile<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for P256VerifyPrecompile<Runtime> {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(P256_VERIFY_GAS)?;
+
+		let output = if verify(handle.input()) {
+			let mut success = [0u8; 32];
+			success[31] = 1;
+			success.to_vec()
+		} else {
+			Vec::new()
+		};
+
+		Ok(PrecompileOutput { exit_status: ExitSucceed::Returned, output })
+	}
+}
+
+/// Whether `input` holds a valid signature of its hash. `r` and `s` must be in `[1, n)`, high `s`
+/// values are accepted, and the public key must be a point of the curve other than infinity.
+fn verify(input: &[u8]) -> bool {
+	if input.len() != INPUT_LEN {
+		return false
+	}
+
+	let (hash, input) = input.split_at(32);
+	let (r, input) = input.split_at(32);
+	let (s, public) = input.split_at(32);
+
+	let Ok(signature) =
+		Signature::from_scalars(*FieldBytes::from_slice(r), *FieldBytes::from_slice(s))
+	else {
+		return false
+	};
+	let point = EncodedPoint::from_affine_coordinates(
+		FieldBytes::from_slice(&public[..32]),
+		FieldBytes::from_slice(&public[32..]),
+		false,
+	);
+	let Ok(public) = VerifyingKey::from_encoded_point(&point) else { return false };
+
+	public.verify_prehash(hash, &signature).is_ok()
+}
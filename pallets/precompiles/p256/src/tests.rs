@@ -0,0 +1,103 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::mock::*;
+use crate::*;
+
+use hex_literal::hex;
+use precompile_utils::testing::*;
+
+fn precompiles() -> TestPrecompileSet<Runtime> {
+    PrecompilesValue::get()
+}
+
+const HASH: [u8; 32] = hex!("a78aa2145253267d4ed15e649d6bc32659677b00717e606ec11fcb4d754053b4");
+const R: [u8; 32] = hex!("78f71b28dddec5f4686d7b36ac0c9be4a79f2a5d67c2fd8f9ef670840f72a6ee");
+const S: [u8; 32] = hex!("3ca95606e445c9ab41952f1d8a57e3530cc26b0588df234bef56b5b75dae37dc");
+const X: [u8; 32] = hex!("471c3e758c4904285bba7e53118ed0f524adeb0757d25bd2f8e7b0d76dfa714c");
+const Y: [u8; 32] = hex!("dd520f7aca8a8b917acc37f51de8f0c9bbe3ad858382e702dc25a12d09f7a858");
+/// Order of the secp256r1 group.
+const N: [u8; 32] = hex!("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+
+fn input(hash: [u8; 32], r: [u8; 32], s: [u8; 32]) -> Vec<u8> {
+    [hash, r, s, X, Y].concat()
+}
+
+fn success() -> Vec<u8> {
+    let mut output = vec![0u8; 32];
+    output[31] = 1;
+    output
+}
+
+fn assert_verifies(input: Vec<u8>, valid: bool) {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Alice, PRECOMPILE_ADDRESS, input)
+            .expect_cost(P256_VERIFY_GAS)
+            .expect_no_logs()
+            .execute_returns_raw(if valid { success() } else { Vec::new() });
+    });
+}
+
+#[test]
+fn valid_signature_returns_one() {
+    // Signature of sha256("hello passkey").
+    assert_verifies(input(HASH, R, S), true);
+}
+
+#[test]
+fn high_s_signature_is_valid() {
+    // `n - s` also verifies, RIP-7212 does not require low `s`.
+    let high_s = hex!("c356a9f81bba3655be6ad0e275a81cacb0248fa81e387b390463150b9eb4ed75");
+    assert_verifies(input(HASH, R, high_s), true);
+}
+
+#[test]
+fn wrong_hash_returns_empty() {
+    let mut hash = HASH;
+    hash[0] ^= 1;
+    assert_verifies(input(hash, R, S), false);
+}
+
+#[test]
+fn wrong_input_length_returns_empty() {
+    let mut short = input(HASH, R, S);
+    short.pop();
+    assert_verifies(short, false);
+
+    let mut long = input(HASH, R, S);
+    long.push(0);
+    assert_verifies(long, false);
+}
+
+#[test]
+fn out_of_range_scalars_return_empty() {
+    assert_verifies(input(HASH, [0u8; 32], S), false);
+    assert_verifies(input(HASH, R, [0u8; 32]), false);
+    assert_verifies(input(HASH, N, S), false);
+    assert_verifies(input(HASH, R, N), false);
+}
+
+#[test]
+fn public_key_off_curve_returns_empty() {
+    let mut off_curve = input(HASH, R, S);
+    off_curve[159] ^= 1;
+    assert_verifies(off_curve, false);
+
+    let mut infinity = input(HASH, R, S);
+    infinity[96..].fill(0);
+    assert_verifies(infinity, false);
+}
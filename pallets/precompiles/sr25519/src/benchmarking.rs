@@ -0,0 +1,67 @@
+// This file is part of gpu.
+
+// Copyright (C) gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Benchmarks of the sr25519 precompile, verifying the signature of a message of `x` bytes.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use precompile_utils::benchmarking::BenchmarkHandle;
+use sp_core::{testing::SR25519, H160};
+use sp_io::crypto::{sr25519_generate, sr25519_sign};
+
+/// Longest message benchmarked, the weight of longer ones is extrapolated.
+const MAX_MESSAGE_LEN: u32 = 65_536;
+
+pub struct Pallet<T: Config>(PhantomData<T>);
+
+pub trait Config: pallet_evm::Config {}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn verify(x: Linear<0, MAX_MESSAGE_LEN>) {
+		let public = sr25519_generate(SR25519, None);
+		let message = vec![0xff; x as usize];
+		let signature = sr25519_sign(SR25519, &public, &message).expect("key is in the keystore");
+		let input = EvmDataWriter::new_with_selector(Action::Verify)
+			.write(H256::from(public.0))
+			.write(Bytes::from(AsRef::<[u8]>::as_ref(&signature)))
+			.write(Bytes(message))
+			.build();
+		let mut handle = BenchmarkHandle::new(H160::zero(), H160::zero(), input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute::<Sr25519Precompile<T>>();
+		}
+
+		assert_eq!(
+			result.map(|output| output.output),
+			Ok(EvmDataWriter::new().write(true).build())
+		);
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::ExtBuilder::default().build(),
+		crate::mock::Runtime
+	);
+}
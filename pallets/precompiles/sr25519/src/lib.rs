@@ -27,21 +27,30 @@ use precompile_utils::{
 	succeed, Bytes, EvmDataWriter, EvmResult, FunctionModifier, PrecompileHandleExt,
 };
 
-// #[cfg(test)]
-// mod mock;
-// #[cfg(test)]
-// mod tests;
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
 
 #[precompile_utils::generate_function_selector]
 #[derive(Debug, PartialEq)]
 pub enum Action {
+	#[view]
+	#[returns("bool")]
 	Verify = "verify(bytes32,bytes,bytes)",
 }
 
 /// A precompile to wrap substrate sr25519 functions.
-pub struct Sr25519Precompile<Runtime>(PhantomData<Runtime>);
+pub struct Sr25519Precompile<Runtime, Weights = ()>(PhantomData<(Runtime, Weights)>);
 
-impl<Runtime: pallet_evm::Config> Precompile for Sr25519Precompile<Runtime> {
+impl<Runtime: pallet_evm::Config, Weights: WeightInfo> Precompile
+	for Sr25519Precompile<Runtime, Weights>
+{
 	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		log::trace!(target: "sr25519-precompile", "In sr25519 precompile");
 
@@ -56,7 +65,7 @@ impl<Runtime: pallet_evm::Config> Precompile for Sr25519Precompile<Runtime> {
 	}
 }
 
-impl<Runtime: pallet_evm::Config> Sr25519Precompile<Runtime> {
+impl<Runtime: pallet_evm::Config, Weights: WeightInfo> Sr25519Precompile<Runtime, Weights> {
 	fn verify(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		let mut input = handle.read_input()?;
 		input.expect_arguments(3)?;
@@ -66,6 +75,8 @@ impl<Runtime: pallet_evm::Config> Sr25519Precompile<Runtime> {
 		let signature_bytes: Vec<u8> = input.read::<Bytes>()?.into();
 		let message: Vec<u8> = input.read::<Bytes>()?.into();
 
+		handle.record_weight::<Runtime>(Weights::verify(message.len() as u32))?;
+
 		// Parse signature
 		let signature_opt = sr25519::Signature::from_slice(&signature_bytes[..]);
 
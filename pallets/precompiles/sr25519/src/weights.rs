@@ -0,0 +1,50 @@
+// This file is part of gpu.
+
+// Copyright (C) gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_evm_precompile_sr25519`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_evm_precompile_sr25519.
+pub trait WeightInfo {
+	fn verify(x: u32, ) -> Weight;
+}
+
+/// Estimated weights for pallet_evm_precompile_sr25519.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// The range of component `x` is `[0, 65536]`.
+	fn verify(x: u32, ) -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(Weight::from_parts(2_600, 0).saturating_mul(x.into()))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// The range of component `x` is `[0, 65536]`.
+	fn verify(x: u32, ) -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(Weight::from_parts(2_600, 0).saturating_mul(x.into()))
+	}
+}
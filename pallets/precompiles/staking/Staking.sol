@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// Interface to the staking precompiled contract
+/// Predeployed at the address 0x0000000000000000000000000000000000005001
+
+/// @title Staking precompile
+/// @dev Bond, nominate and claim rewards through pallet-staking.
+/// The caller address is mapped to a Substrate account which acts both as stash and controller.
+/// Validators and reward accounts are native Substrate accounts, hence passed as bytes32.
+interface Staking {
+    /// @dev Reward destination, `account` is only used with `Account`.
+    /// 0 = Staked, 1 = Stash, 2 = Controller, 3 = Account, 4 = None
+
+    /// @dev Bond `value` from the caller account.
+    /// @param value Amount to bond.
+    /// @param payee Reward destination.
+    /// @param account Reward account, used when `payee` is `Account`.
+    function bond(uint256 value, uint8 payee, bytes32 account) external returns (bool);
+
+    /// @dev Add some extra amount to the bonded funds of the caller.
+    function bondExtra(uint256 maxAdditional) external returns (bool);
+
+    /// @dev Schedule a portion of the caller's bonded funds to be unlocked.
+    function unbond(uint256 value) external returns (bool);
+
+    /// @dev Remove any unlocked chunks from the caller's ledger.
+    /// @param numSlashingSpans Number of slashing spans of the caller, usually 0.
+    function withdrawUnbonded(uint32 numSlashingSpans) external returns (bool);
+
+    /// @dev Declare the desire to nominate `targets`.
+    function nominate(bytes32[] memory targets) external returns (bool);
+
+    /// @dev Declare no desire to either validate or nominate.
+    function chill() external returns (bool);
+
+    /// @dev Pay out all the stakers behind a single validator for a single era.
+    function payoutStakers(bytes32 validatorStash, uint32 era) external returns (bool);
+
+    /// @dev (Re-)set the reward destination of the caller.
+    function setPayee(uint8 payee, bytes32 account) external returns (bool);
+
+    /// @dev Bonded amounts of `stash`.
+    /// @return total Total bonded amount, including the unlocking chunks.
+    /// @return active Amount that is actively at stake.
+    /// @return unlocking Sum of all the chunks being unlocked.
+    function ledger(address stash)
+        external
+        view
+        returns (uint256 total, uint256 active, uint256 unlocking);
+
+    /// @dev Validators nominated by `stash`.
+    function nominations(address stash) external view returns (bytes32[] memory);
+
+    /// @dev Current era index.
+    function currentEra() external view returns (uint32);
+
+    /// @dev Minimum amount required to be a nominator.
+    function minNominatorBond() external view returns (uint256);
+}
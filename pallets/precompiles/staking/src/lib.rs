@@ -0,0 +1,392 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Precompile exposing `pallet_staking` to EVM accounts.
+//!
+//! The caller's H160 is mapped to a Substrate account through the runtime `AddressMapping`, and
+//! that account acts as both stash and controller. Validators and reward accounts are passed as
+//! `bytes32` since they usually are native Substrate accounts.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use fp_evm::{PrecompileHandle, PrecompileOutput};
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	sp_runtime::traits::StaticLookup,
+};
+use pallet_evm::{AddressMapping, Precompile};
+use pallet_staking::RewardDestination;
+use parity_scale_codec::MaxEncodedLen;
+use precompile_utils::{
+	revert, succeed, Address, EvmData, EvmDataWriter, EvmResult, FunctionModifier,
+	PrecompileHandleExt, RuntimeHelper,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::{
+	convert::{TryFrom, TryInto},
+	marker::PhantomData,
+	vec::Vec,
+};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Alias for the staking balance type of the provided Runtime.
+pub type BalanceOf<Runtime> = pallet_staking::BalanceOf<Runtime>;
+
+#[precompile_utils::generate_function_selector]
+#[derive(Debug, PartialEq)]
+pub enum Action {
+	#[returns("bool")]
+	Bond = "bond(uint256,uint8,bytes32)",
+	#[returns("bool")]
+	BondExtra = "bondExtra(uint256)",
+	#[returns("bool")]
+	Unbond = "unbond(uint256)",
+	#[returns("bool")]
+	WithdrawUnbonded = "withdrawUnbonded(uint32)",
+	#[returns("bool")]
+	Nominate = "nominate(bytes32[])",
+	#[returns("bool")]
+	Chill = "chill()",
+	#[returns("bool")]
+	PayoutStakers = "payoutStakers(bytes32,uint32)",
+	#[returns("bool")]
+	SetPayee = "setPayee(uint8,bytes32)",
+	#[view]
+	#[returns("uint256,uint256,uint256")]
+	Ledger = "ledger(address)",
+	#[view]
+	#[returns("bytes32[]")]
+	Nominations = "nominations(address)",
+	#[view]
+	#[returns("uint32")]
+	CurrentEra = "currentEra()",
+	#[view]
+	#[returns("uint256")]
+	MinNominatorBond = "minNominatorBond()",
+}
+
+/// Reward destination as encoded on the Solidity side.
+///
+/// `Account` is the only variant that makes use of the accompanying `bytes32` argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Payee {
+	Staked = 0,
+	Stash = 1,
+	Controller = 2,
+	Account = 3,
+	None = 4,
+}
+
+impl TryFrom<u8> for Payee {
+	type Error = ();
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(Payee::Staked),
+			1 => Ok(Payee::Stash),
+			2 => Ok(Payee::Controller),
+			3 => Ok(Payee::Account),
+			4 => Ok(Payee::None),
+			_ => Err(()),
+		}
+	}
+}
+
+/// A precompile to wrap the functionality from pallet-staking.
+pub struct StakingPrecompile<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for StakingPrecompile<Runtime>
+where
+	Runtime: pallet_staking::Config + pallet_evm::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<pallet_staking::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	Runtime::AccountId: From<[u8; 32]> + Into<[u8; 32]>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256> + EvmData,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		log::trace!(target: "staking-precompile", "In staking precompile");
+
+		let selector = handle.read_selector()?;
+
+		handle.check_function_modifier(match selector {
+			Action::Ledger |
+			Action::Nominations |
+			Action::CurrentEra |
+			Action::MinNominatorBond => FunctionModifier::View,
+			_ => FunctionModifier::NonPayable,
+		})?;
+
+		match selector {
+			// Dispatchables
+			Action::Bond => Self::bond(handle),
+			Action::BondExtra => Self::bond_extra(handle),
+			Action::Unbond => Self::unbond(handle),
+			Action::WithdrawUnbonded => Self::withdraw_unbonded(handle),
+			Action::Nominate => Self::nominate(handle),
+			Action::Chill => Self::chill(handle),
+			Action::PayoutStakers => Self::payout_stakers(handle),
+			Action::SetPayee => Self::set_payee(handle),
+			// Storage getters
+			Action::Ledger => Self::ledger(handle),
+			Action::Nominations => Self::nominations(handle),
+			Action::CurrentEra => Self::current_era(handle),
+			Action::MinNominatorBond => Self::min_nominator_bond(handle),
+		}
+	}
+}
+
+impl<Runtime> StakingPrecompile<Runtime>
+where
+	Runtime: pallet_staking::Config + pallet_evm::Config,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+	Runtime::RuntimeCall: From<pallet_staking::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	Runtime::AccountId: From<[u8; 32]> + Into<[u8; 32]>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256> + EvmData,
+{
+	fn bond(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+
+		let value = input.read::<BalanceOf<Runtime>>()?;
+		let payee = Self::parse_payee(input.read::<u8>()?, input.read::<H256>()?)?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_staking::Call::<Runtime>::bond { value, payee },
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn bond_extra(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let max_additional = input.read::<BalanceOf<Runtime>>()?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_staking::Call::<Runtime>::bond_extra { max_additional },
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn unbond(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let value = input.read::<BalanceOf<Runtime>>()?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_staking::Call::<Runtime>::unbond { value },
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn withdraw_unbonded(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let num_slashing_spans = input.read::<u32>()?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_staking::Call::<Runtime>::withdraw_unbonded { num_slashing_spans },
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn nominate(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let targets = input
+			.read::<Vec<H256>>()?
+			.into_iter()
+			.map(|target| Runtime::Lookup::unlookup(Self::to_account(target)))
+			.collect();
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_staking::Call::<Runtime>::nominate { targets },
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn chill(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_staking::Call::<Runtime>::chill {},
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn payout_stakers(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let validator_stash = Self::to_account(input.read::<H256>()?);
+		let era = input.read::<u32>()?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_staking::Call::<Runtime>::payout_stakers { validator_stash, era },
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn set_payee(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let payee = Self::parse_payee(input.read::<u8>()?, input.read::<H256>()?)?;
+
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_staking::Call::<Runtime>::set_payee { payee },
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn ledger(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		// Storage item: Bonded:
+		// Twox64(8) + AccountId(32) + AccountId(32)
+		handle.record_db_read::<Runtime>(72)?;
+		// Storage item: Ledger:
+		// Blake2_128(16) + AccountId(32) + StakingLedger
+		handle.record_db_read::<Runtime>(
+			48 + pallet_staking::StakingLedger::<Runtime>::max_encoded_len(),
+		)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let stash: H160 = input.read::<Address>()?.into();
+		let stash = Runtime::AddressMapping::into_account_id(stash);
+
+		let (total, active, unlocking) = pallet_staking::Pallet::<Runtime>::bonded(&stash)
+			.and_then(|controller| pallet_staking::Pallet::<Runtime>::ledger(&controller))
+			.map(|ledger| {
+				let unlocking =
+					ledger.unlocking.iter().fold(BalanceOf::<Runtime>::zero(), |acc, chunk| {
+						acc.saturating_add(chunk.value)
+					});
+				(ledger.total.into(), ledger.active.into(), unlocking.into())
+			})
+			.unwrap_or_default();
+
+		Ok(succeed(
+			EvmDataWriter::new()
+				.write::<U256>(total)
+				.write::<U256>(active)
+				.write::<U256>(unlocking)
+				.build(),
+		))
+	}
+
+	fn nominations(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		// Storage item: Nominators:
+		// Twox64(8) + AccountId(32) + Nominations
+		handle.record_db_read::<Runtime>(
+			40 + pallet_staking::Nominations::<Runtime>::max_encoded_len(),
+		)?;
+
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let stash: H160 = input.read::<Address>()?.into();
+		let stash = Runtime::AddressMapping::into_account_id(stash);
+
+		let targets: Vec<H256> = pallet_staking::Pallet::<Runtime>::nominators(&stash)
+			.map(|nominations| nominations.targets.into_iter().map(Self::from_account).collect())
+			.unwrap_or_default();
+
+		Ok(succeed(EvmDataWriter::new().write(targets).build()))
+	}
+
+	fn current_era(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		// Storage item: CurrentEra:
+		// EraIndex(4)
+		handle.record_db_read::<Runtime>(4)?;
+
+		let era = pallet_staking::Pallet::<Runtime>::current_era().unwrap_or_default();
+
+		Ok(succeed(EvmDataWriter::new().write(era).build()))
+	}
+
+	fn min_nominator_bond(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+		// Storage item: MinNominatorBond:
+		// Balance(16)
+		handle.record_db_read::<Runtime>(16)?;
+
+		let min_bond: U256 = pallet_staking::Pallet::<Runtime>::min_nominator_bond().into();
+
+		Ok(succeed(EvmDataWriter::new().write(min_bond).build()))
+	}
+
+	fn parse_payee(payee: u8, account: H256) -> EvmResult<RewardDestination<Runtime::AccountId>> {
+		let payee = match payee.try_into().map_err(|_| revert("Invalid reward destination"))? {
+			Payee::Staked => RewardDestination::Staked,
+			Payee::Stash => RewardDestination::Stash,
+			Payee::Controller => RewardDestination::Controller,
+			Payee::Account => RewardDestination::Account(Self::to_account(account)),
+			Payee::None => RewardDestination::None,
+		};
+
+		Ok(payee)
+	}
+
+	fn to_account(account: H256) -> Runtime::AccountId {
+		Runtime::AccountId::from(account.to_fixed_bytes())
+	}
+
+	fn from_account(account: Runtime::AccountId) -> H256 {
+		let bytes: [u8; 32] = account.into();
+		H256::from(bytes)
+	}
+}
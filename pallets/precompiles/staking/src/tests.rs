@@ -0,0 +1,383 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::mock::*;
+use crate::*;
+
+use precompile_utils::testing::*;
+
+fn precompiles() -> TestPrecompileSet<Runtime> {
+    PrecompilesValue::get()
+}
+
+fn bond(amount: u128) {
+    precompiles()
+        .prepare_test(
+            Alice,
+            PRECOMPILE_ADDRESS,
+            EvmDataWriter::new_with_selector(Action::Bond)
+                .write(U256::from(amount))
+                .write(Payee::Staked as u8)
+                .write(H256::zero())
+                .build(),
+        )
+        .expect_no_logs()
+        .execute_returns(EvmDataWriter::new().write(true).build());
+}
+
+#[test]
+fn selector_less_than_four_bytes() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Alice, PRECOMPILE_ADDRESS, vec![1u8, 2u8, 3u8])
+            .execute_reverts(|output| output == b"tried to parse selector out of bounds");
+    });
+}
+
+#[test]
+fn no_selector_exists_but_length_is_right() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(Alice, PRECOMPILE_ADDRESS, vec![1u8, 2u8, 3u8, 4u8])
+            .execute_reverts(|output| output == b"unknown selector");
+    });
+}
+
+#[test]
+fn bond_works() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            bond(100);
+
+            precompiles()
+                .prepare_test(
+                    Bob,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Ledger)
+                        .write(Address(Alice.into()))
+                        .build(),
+                )
+                .expect_no_logs()
+                .execute_returns(
+                    EvmDataWriter::new()
+                        .write(U256::from(100))
+                        .write(U256::from(100))
+                        .write(U256::zero())
+                        .build(),
+                );
+        });
+}
+
+#[test]
+fn bond_with_invalid_payee_reverts() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Bond)
+                        .write(U256::from(100))
+                        .write(5u8)
+                        .write(H256::zero())
+                        .build(),
+                )
+                .execute_reverts(|output| output == b"Invalid reward destination");
+        });
+}
+
+#[test]
+fn bond_to_account_sets_payee() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Bond)
+                        .write(U256::from(100))
+                        .write(Payee::Account as u8)
+                        .write(H256::from(VALIDATOR))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            assert_eq!(
+                pallet_staking::Payee::<Runtime>::get(account(Alice)),
+                RewardDestination::Account(AccountId::from(VALIDATOR))
+            );
+        });
+}
+
+#[test]
+fn bond_extra_and_unbond_update_ledger() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            bond(100);
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::BondExtra)
+                        .write(U256::from(50))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Unbond)
+                        .write(U256::from(30))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Ledger)
+                        .write(Address(Alice.into()))
+                        .build(),
+                )
+                .execute_returns(
+                    EvmDataWriter::new()
+                        .write(U256::from(150))
+                        .write(U256::from(120))
+                        .write(U256::from(30))
+                        .build(),
+                );
+        });
+}
+
+#[test]
+fn withdraw_unbonded_after_bonding_duration() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            bond(100);
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Unbond)
+                        .write(U256::from(40))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            pallet_staking::CurrentEra::<Runtime>::put(BondingDuration::get());
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::WithdrawUnbonded)
+                        .write(0u32)
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            let ledger = pallet_staking::Pallet::<Runtime>::ledger(account(Alice)).unwrap();
+            assert_eq!(ledger.total, 60);
+            assert!(ledger.unlocking.is_empty());
+        });
+}
+
+#[test]
+fn nominate_and_chill() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            bond(100);
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Nominate)
+                        .write(vec![H256::from(VALIDATOR)])
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            precompiles()
+                .prepare_test(
+                    Bob,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Nominations)
+                        .write(Address(Alice.into()))
+                        .build(),
+                )
+                .execute_returns(
+                    EvmDataWriter::new()
+                        .write(vec![H256::from(VALIDATOR)])
+                        .build(),
+                );
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Chill).build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            precompiles()
+                .prepare_test(
+                    Bob,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Nominations)
+                        .write(Address(Alice.into()))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(Vec::<H256>::new()).build());
+        });
+}
+
+#[test]
+fn nominate_without_bond_fails() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::Nominate)
+                        .write(vec![H256::from(VALIDATOR)])
+                        .build(),
+                )
+                .execute_reverts(|output| {
+                    output.starts_with(b"Dispatched call failed with error: ")
+                });
+        });
+}
+
+#[test]
+fn set_payee_works() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            bond(100);
+
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::SetPayee)
+                        .write(Payee::Stash as u8)
+                        .write(H256::zero())
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            assert_eq!(
+                pallet_staking::Payee::<Runtime>::get(account(Alice)),
+                RewardDestination::Stash
+            );
+        });
+}
+
+#[test]
+fn payout_stakers_for_unknown_era_fails() {
+    ExtBuilder::default()
+        .with_balances(vec![(account(Alice), 1000)])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Alice,
+                    PRECOMPILE_ADDRESS,
+                    EvmDataWriter::new_with_selector(Action::PayoutStakers)
+                        .write(H256::from(VALIDATOR))
+                        .write(0u32)
+                        .build(),
+                )
+                .execute_reverts(|output| {
+                    output.starts_with(b"Dispatched call failed with error: ")
+                });
+        });
+}
+
+#[test]
+fn ledger_of_unbonded_account_is_empty() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::Ledger)
+                    .write(Address(Charlie.into()))
+                    .build(),
+            )
+            .execute_returns(
+                EvmDataWriter::new()
+                    .write(U256::zero())
+                    .write(U256::zero())
+                    .write(U256::zero())
+                    .build(),
+            );
+    });
+}
+
+#[test]
+fn current_era_and_min_nominator_bond() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::CurrentEra).build(),
+            )
+            .execute_returns(EvmDataWriter::new().write(0u32).build());
+
+        pallet_staking::CurrentEra::<Runtime>::put(7);
+        pallet_staking::MinNominatorBond::<Runtime>::put(42);
+
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::CurrentEra).build(),
+            )
+            .execute_returns(EvmDataWriter::new().write(7u32).build());
+
+        precompiles()
+            .prepare_test(
+                Alice,
+                PRECOMPILE_ADDRESS,
+                EvmDataWriter::new_with_selector(Action::MinNominatorBond).build(),
+            )
+            .execute_returns(EvmDataWriter::new().write(U256::from(42)).build());
+    });
+}
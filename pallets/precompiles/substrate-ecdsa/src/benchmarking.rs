@@ -0,0 +1,68 @@
+// This file is part of gpu.
+
+// Copyright (C) gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Benchmarks of the substrate ecdsa precompile, verifying the signature of a message of `x`
+//! bytes.
+
+use super::*;
+use frame_benchmarking::v2::*;
+use precompile_utils::benchmarking::BenchmarkHandle;
+use sp_core::{testing::ECDSA, H160};
+use sp_io::crypto::{ecdsa_generate, ecdsa_sign};
+
+/// Longest message benchmarked, the weight of longer ones is extrapolated.
+const MAX_MESSAGE_LEN: u32 = 65_536;
+
+pub struct Pallet<T: Config>(PhantomData<T>);
+
+pub trait Config: pallet_evm::Config {}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn verify(x: Linear<0, MAX_MESSAGE_LEN>) {
+		let public = ecdsa_generate(ECDSA, None);
+		let message = vec![0xff; x as usize];
+		let signature = ecdsa_sign(ECDSA, &public, &message).expect("key is in the keystore");
+		let input = EvmDataWriter::new_with_selector(Action::Verify)
+			.write(Bytes::from(AsRef::<[u8]>::as_ref(&public)))
+			.write(Bytes::from(AsRef::<[u8]>::as_ref(&signature)))
+			.write(Bytes(message))
+			.build();
+		let mut handle = BenchmarkHandle::new(H160::zero(), H160::zero(), input);
+
+		let result;
+		#[block]
+		{
+			result = handle.execute::<SubstrateEcdsaPrecompile<T>>();
+		}
+
+		assert_eq!(
+			result.map(|output| output.output),
+			Ok(EvmDataWriter::new().write(true).build())
+		);
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::ExtBuilder::default().build(),
+		crate::mock::Runtime
+	);
+}
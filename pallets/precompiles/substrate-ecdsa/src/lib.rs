@@ -27,21 +27,30 @@ use precompile_utils::{
 	succeed, Bytes, EvmDataWriter, EvmResult, FunctionModifier, PrecompileHandleExt,
 };
 
-// #[cfg(test)]
-// mod mock;
-// #[cfg(test)]
-// mod tests;
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
 
 #[precompile_utils::generate_function_selector]
 #[derive(Debug, PartialEq)]
 pub enum Action {
+	#[view]
+	#[returns("bool")]
 	Verify = "verify(bytes,bytes,bytes)",
 }
 
 /// A precompile to wrap substrate ecdsa functions.
-pub struct SubstrateEcdsaPrecompile<Runtime>(PhantomData<Runtime>);
+pub struct SubstrateEcdsaPrecompile<Runtime, Weights = ()>(PhantomData<(Runtime, Weights)>);
 
-impl<Runtime: pallet_evm::Config> Precompile for SubstrateEcdsaPrecompile<Runtime> {
+impl<Runtime: pallet_evm::Config, Weights: WeightInfo> Precompile
+	for SubstrateEcdsaPrecompile<Runtime, Weights>
+{
 	fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		log::trace!(target: "substrate-ecdsa-precompile", "In SubstrateEcdsa precompile");
 
@@ -56,7 +65,7 @@ impl<Runtime: pallet_evm::Config> Precompile for SubstrateEcdsaPrecompile<Runtim
 	}
 }
 
-impl<Runtime: pallet_evm::Config> SubstrateEcdsaPrecompile<Runtime> {
+impl<Runtime: pallet_evm::Config, Weights: WeightInfo> SubstrateEcdsaPrecompile<Runtime, Weights> {
 	fn verify(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
 		let mut input = handle.read_input()?;
 		input.expect_arguments(3)?;
@@ -66,6 +75,8 @@ impl<Runtime: pallet_evm::Config> SubstrateEcdsaPrecompile<Runtime> {
 		let signature_bytes: Vec<u8> = input.read::<Bytes>()?.into();
 		let message: Vec<u8> = input.read::<Bytes>()?.into();
 
+		handle.record_weight::<Runtime>(Weights::verify(message.len() as u32))?;
+
 		// Parse public key
 		let public = if let Ok(public) = ecdsa::Public::try_from(&public_bytes[..]) {
 			public
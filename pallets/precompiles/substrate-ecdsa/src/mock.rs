@@ -34,15 +34,14 @@ use pallet_evm::{
     AddressMapping, EnsureAddressNever, EnsureAddressRoot, PrecompileResult, PrecompileSet,
 };
 use sp_core::{H160, H256};
+use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
 use sp_runtime::{
-    testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage,
 };
 
 pub type AccountId = TestAccount;
 pub type Balance = u128;
-pub type BlockNumber = u64;
-pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 pub type Block = frame_system::mocking::MockBlock<Runtime>;
 
 pub const PRECOMPILE_ADDRESS: H160 = H160::repeat_byte(0x3F);
@@ -116,7 +115,7 @@ impl frame_system::Config for Runtime {
     type DbWeight = ();
     type RuntimeOrigin = RuntimeOrigin;
     type Nonce = u64;
-    type Block = BlockNumber;
+    type Block = Block;
     type RuntimeCall = RuntimeCall;
     type Hash = H256;
     type Hashing = BlakeTwo256;
@@ -185,6 +184,7 @@ impl pallet_balances::Config for Runtime {
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
+    type RuntimeHoldReason = ();
     type FreezeIdentifier = ();
     type MaxHolds = ConstU32<0>;
     type MaxFreezes = ConstU32<0>;
@@ -221,8 +221,8 @@ impl pallet_evm::Config for Runtime {
 
 // Configure a mock runtime to test the pallet.
 construct_runtime! {
-	pub enum Runtime
-	{
+    pub enum Runtime
+    {
         System: frame_system,
         Balances: pallet_balances,
         Evm: pallet_evm,
@@ -235,12 +235,16 @@ pub(crate) struct ExtBuilder;
 
 impl ExtBuilder {
     pub(crate) fn build(self) -> sp_io::TestExternalities {
-        let t = frame_system::GenesisConfig::default()
-            .build_storage::<Runtime>()
+        let t = frame_system::GenesisConfig::<Runtime>::default()
+            .build_storage()
             .expect("Frame system builds valid default genesis config");
 
         let mut ext = sp_io::TestExternalities::new(t);
+        ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
         ext.execute_with(|| System::set_block_number(1));
         ext
     }
 }
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::benchmarking::Config for Runtime {}
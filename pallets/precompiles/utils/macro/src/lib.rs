@@ -23,11 +23,17 @@
 #![crate_type = "proc-macro"]
 extern crate proc_macro;
 
+mod signature;
+
 use proc_macro::TokenStream;
 use proc_macro2::Literal;
-use quote::{quote, quote_spanned};
+use quote::quote;
 use sha3::{Digest, Keccak256};
-use syn::{parse_macro_input, spanned::Spanned, Expr, ExprLit, Ident, ItemEnum, Lit, LitStr};
+use std::collections::HashMap;
+use syn::{
+	parse_macro_input, parse_quote, spanned::Spanned, Attribute, Data, DataStruct, DeriveInput,
+	Expr, ExprLit, Ident, Index, ItemEnum, Lit, LitStr, Member,
+};
 
 struct Bytes(Vec<u8>);
 
@@ -84,54 +90,317 @@ pub fn keccak256(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// Signatures are checked against the Solidity ABI grammar and must be canonical, without
+/// whitespace, as the selector is the hash of the exact string. Type aliases such as `uint` are
+/// expanded before hashing, and two variants sharing a selector are rejected.
+///
+/// When the calling crate is built with its `std` feature, the enum also implements
+/// `precompile_utils::solidity::SolidityInterface`, from which the Solidity interface and the ABI
+/// JSON of the precompile are rendered. Variants are `nonpayable` and return nothing unless told
+/// otherwise, and the events emitted by the precompile as well as the custom errors it reverts
+/// with are declared on the enum:
+///
+/// ```ignore
+/// #[generate_function_selector]
+/// #[event("Transfer(address indexed,address indexed,uint256)")]
+/// #[error("InsufficientBalance(uint256,uint256)")]
+/// enum Action {
+///     #[view]
+///     #[returns("uint256")]
+///     BalanceOf = "balanceOf(address)",
+///     #[returns("bool")]
+///     Transfer = "transfer(address,uint256)",
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn generate_function_selector(_: TokenStream, input: TokenStream) -> TokenStream {
 	let item = parse_macro_input!(input as ItemEnum);
 
+	match expand_function_selector(item) {
+		Ok(expanded) => expanded.into(),
+		Err(err) => err.to_compile_error().into(),
+	}
+}
+
+fn expand_function_selector(item: ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
 	let ItemEnum { attrs, vis, enum_token, ident, variants, .. } = item;
 
+	let mut errors: Option<syn::Error> = None;
+	let mut push_error = |error: syn::Error| match &mut errors {
+		Some(errors) => errors.combine(error),
+		None => errors = Some(error),
+	};
+
+	let mut selectors: HashMap<u32, (Ident, String)> = HashMap::new();
+	let mut attrs_expressions: Vec<Vec<Attribute>> = vec![];
 	let mut ident_expressions: Vec<Ident> = vec![];
 	let mut variant_expressions: Vec<Expr> = vec![];
+	let mut functions: Vec<proc_macro2::TokenStream> = vec![];
 	for variant in variants {
-		match variant.discriminant {
-			Some((_, Expr::Lit(ExprLit { lit, .. }))) =>
-				if let Lit::Str(lit_str) = lit {
-					let digest = Keccak256::digest(lit_str.value().as_bytes());
-					let selector = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
-
-					ident_expressions.push(variant.ident);
-					variant_expressions.push(Expr::Lit(ExprLit {
-						lit: Lit::Verbatim(Literal::u32_suffixed(selector)),
-						attrs: Default::default(),
-					}));
-				} else {
-					return quote_spanned! {
-						lit.span() => compile_error("Expected literal string");
-					}
-					.into()
-				},
-			Some((_eg, expr)) =>
-				return quote_spanned! {
-					expr.span() => compile_error("Expected literal");
-				}
-				.into(),
-			None =>
-				return quote_spanned! {
-					variant.span() => compile_error("Each variant must have a discriminant");
-				}
-				.into(),
+		let lit_str = match variant.discriminant {
+			Some((_, Expr::Lit(ExprLit { lit: Lit::Str(lit_str), .. }))) => lit_str,
+			Some((_, expr)) => {
+				push_error(syn::Error::new(expr.span(), "Expected a literal string"));
+				continue
+			},
+			None => {
+				push_error(syn::Error::new(
+					variant.span(),
+					"Each variant must have a discriminant",
+				));
+				continue
+			},
+		};
+
+		let function = match signature::parse_function(&lit_str.value()) {
+			Ok(function) => function,
+			Err(message) => {
+				push_error(syn::Error::new(lit_str.span(), message));
+				continue
+			},
+		};
+		let canonical = function.canonical();
+		let digest = Keccak256::digest(canonical.as_bytes());
+		let selector = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+
+		if let Some((other, other_canonical)) = selectors.get(&selector) {
+			push_error(syn::Error::new(
+				lit_str.span(),
+				format!(
+					"Selector {:#010x} of `{}` collides with `{}` (`{}`)",
+					selector, canonical, other, other_canonical
+				),
+			));
+			continue
 		}
+		selectors.insert(selector, (variant.ident.clone(), canonical));
+
+		let variant_attrs = match solidity_function(&function, selector, variant.attrs) {
+			Ok((metadata, variant_attrs)) => {
+				functions.push(metadata);
+				variant_attrs
+			},
+			Err(err) => {
+				push_error(err);
+				continue
+			},
+		};
+
+		attrs_expressions.push(variant_attrs);
+		ident_expressions.push(variant.ident);
+		variant_expressions.push(Expr::Lit(ExprLit {
+			lit: Lit::Verbatim(Literal::u32_suffixed(selector)),
+			attrs: Default::default(),
+		}));
 	}
 
-	(quote! {
+	let (events, attrs): (Vec<_>, Vec<_>) =
+		attrs.into_iter().partition(|attr| attr.path.is_ident("event"));
+	let events: Vec<_> = events
+		.iter()
+		.filter_map(|event| solidity_event(event).map_err(&mut push_error).ok())
+		.collect();
+	let (solidity_errors, attrs): (Vec<_>, Vec<_>) =
+		attrs.into_iter().partition(|attr| attr.path.is_ident("error"));
+	let solidity_errors: Vec<_> = solidity_errors
+		.iter()
+		.filter_map(|error| solidity_error(error).map_err(&mut push_error).ok())
+		.collect();
+
+	if let Some(errors) = errors {
+		return Err(errors)
+	}
+
+	Ok(quote! {
 		#(#attrs)*
 		#[derive(num_enum::TryFromPrimitive, num_enum::IntoPrimitive)]
 		#[repr(u32)]
 		#vis #enum_token #ident {
 			#(
+				#(#attrs_expressions)*
 				#ident_expressions = #variant_expressions,
 			)*
 		}
+
+		#[cfg(feature = "std")]
+		impl precompile_utils::solidity::SolidityInterface for #ident {
+			const FUNCTIONS: &'static [precompile_utils::solidity::Function] = &[#(#functions,)*];
+			const EVENTS: &'static [precompile_utils::solidity::Event] = &[#(#events,)*];
+			const ERRORS: &'static [precompile_utils::solidity::Error] = &[#(#solidity_errors,)*];
+		}
+	})
+}
+
+/// Builds the `Function` description of a variant out of its signature and of its `#[view]`,
+/// `#[payable]` and `#[returns(..)]` attributes, which are stripped from the returned attributes.
+fn solidity_function(
+	function: &signature::Function,
+	selector: u32,
+	attrs: Vec<Attribute>,
+) -> syn::Result<(proc_macro2::TokenStream, Vec<Attribute>)> {
+	let mut mutability = quote!(NonPayable);
+	let mut outputs = vec![];
+	let mut kept = vec![];
+	for attr in attrs {
+		if attr.path.is_ident("view") {
+			mutability = quote!(View);
+		} else if attr.path.is_ident("payable") {
+			mutability = quote!(Payable);
+		} else if attr.path.is_ident("returns") {
+			let returns = attr.parse_args::<LitStr>()?;
+			outputs = signature::parse_types(&returns.value())
+				.map_err(|message| syn::Error::new(returns.span(), message))?;
+		} else {
+			kept.push(attr);
+		}
+	}
+
+	let name = &function.name;
+	let inputs = &function.inputs;
+	let metadata = quote! {
+		precompile_utils::solidity::Function {
+			selector: #selector,
+			name: #name,
+			inputs: &[#(#inputs),*],
+			outputs: &[#(#outputs),*],
+			mutability: precompile_utils::solidity::StateMutability::#mutability,
+		}
+	};
+	Ok((metadata, kept))
+}
+
+/// Builds the `Event` description of an `#[event("Name(type indexed,type)")]` attribute.
+fn solidity_event(attr: &Attribute) -> syn::Result<proc_macro2::TokenStream> {
+	let signature = attr.parse_args::<LitStr>()?;
+	let event = signature::parse_event(&signature.value())
+		.map_err(|message| syn::Error::new(signature.span(), message))?;
+
+	let topic = Keccak256::digest(event.canonical().as_bytes()).to_vec();
+	let name = &event.name;
+	let (types, indexed): (Vec<_>, Vec<_>) = event.inputs.iter().cloned().unzip();
+
+	Ok(quote! {
+		precompile_utils::solidity::Event {
+			selector: [#(#topic),*],
+			name: #name,
+			inputs: &[#(
+				precompile_utils::solidity::EventParam { ty: #types, indexed: #indexed }
+			),*],
+		}
+	})
+}
+
+/// Builds the `Error` description of an `#[error("Name(type1,type2)")]` attribute.
+fn solidity_error(attr: &Attribute) -> syn::Result<proc_macro2::TokenStream> {
+	let signature = attr.parse_args::<LitStr>()?;
+	let error = signature::parse_function(&signature.value())
+		.map_err(|message| syn::Error::new(signature.span(), message))?;
+
+	let digest = Keccak256::digest(error.canonical().as_bytes());
+	let selector = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+	let name = &error.name;
+	let inputs = &error.inputs;
+
+	Ok(quote! {
+		precompile_utils::solidity::Error {
+			selector: #selector,
+			name: #name,
+			inputs: &[#(#inputs),*],
+		}
+	})
+}
+
+/// Implements `EvmData` for a struct, encoding it as the Solidity tuple of its fields in
+/// declaration order.
+///
+/// ```ignore
+/// #[derive(EvmData)]
+/// struct Ledger {
+///     total: U256,
+///     targets: Vec<H256>,
+/// }
+/// ```
+///
+/// is read and written as `(uint256,bytes32[])`.
+#[proc_macro_derive(EvmData)]
+pub fn derive_evm_data(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+
+	match expand_evm_data(input) {
+		Ok(expanded) => expanded.into(),
+		Err(err) => err.to_compile_error().into(),
+	}
+}
+
+/// Largest tuple `EvmData` is implemented for.
+const MAX_TUPLE_LEN: usize = 18;
+
+fn expand_evm_data(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+	let DeriveInput { ident, mut generics, data, .. } = input;
+
+	let fields = match data {
+		Data::Struct(DataStruct { fields, .. }) => fields,
+		_ => return Err(syn::Error::new(ident.span(), "EvmData can only be derived for structs")),
+	};
+	if fields.is_empty() || fields.len() > MAX_TUPLE_LEN {
+		return Err(syn::Error::new(
+			ident.span(),
+			format!("EvmData can only be derived for structs of 1 to {} fields", MAX_TUPLE_LEN),
+		))
+	}
+
+	let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+	let members: Vec<Member> = fields
+		.iter()
+		.enumerate()
+		.map(|(i, field)| match &field.ident {
+			Some(ident) => Member::Named(ident.clone()),
+			None => Member::Unnamed(Index::from(i)),
+		})
+		.collect();
+	// Decoding failures name the field they happened in.
+	let backtraces: Vec<_> = fields
+		.iter()
+		.enumerate()
+		.map(|(i, field)| match &field.ident {
+			Some(ident) => {
+				let name = ident.to_string();
+				quote!(in_field(#name))
+			},
+			None => quote!(in_tuple(#i)),
+		})
+		.collect();
+
+	if generics.type_params().next().is_some() {
+		let where_clause = generics.make_where_clause();
+		for ty in &types {
+			where_clause.predicates.push(parse_quote!(#ty: precompile_utils::data::EvmData));
+		}
+	}
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	Ok(quote! {
+		impl #impl_generics precompile_utils::data::EvmData for #ident #ty_generics #where_clause {
+			fn read(
+				reader: &mut precompile_utils::data::EvmDataReader,
+			) -> precompile_utils::revert::MayRevert<Self> {
+				use precompile_utils::revert::InjectBacktrace;
+
+				if !<Self as precompile_utils::data::EvmData>::has_static_size() {
+					let reader = &mut reader.read_pointer()?;
+					Ok(Self { #(#members: reader.read::<#types>().#backtraces?),* })
+				} else {
+					Ok(Self { #(#members: reader.read::<#types>().#backtraces?),* })
+				}
+			}
+
+			fn write(writer: &mut precompile_utils::data::EvmDataWriter, value: Self) {
+				precompile_utils::data::EvmData::write(writer, (#(value.#members,)*));
+			}
+
+			fn has_static_size() -> bool {
+				<(#(#types,)*) as precompile_utils::data::EvmData>::has_static_size()
+			}
+		}
 	})
-	.into()
 }
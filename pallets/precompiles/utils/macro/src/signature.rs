@@ -0,0 +1,259 @@
+// This file is part of gpu.
+
+// Copyright (C) gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Parser of the function and event signatures given to the macros, following the type grammar
+//! of the Solidity ABI specification.
+//!
+//! Types are normalized the way Solidity does before hashing a signature, so `uint` stands for
+//! `uint256`. Anything else that is not a canonical signature, whitespace included, is rejected
+//! since it would hash to a selector no caller ever sends.
+
+/// A function signature, in canonical form.
+pub struct Function {
+	pub name: String,
+	pub inputs: Vec<String>,
+}
+
+impl Function {
+	/// The string hashed into the function selector.
+	pub fn canonical(&self) -> String {
+		format!("{}({})", self.name, self.inputs.join(","))
+	}
+}
+
+/// An event signature, in canonical form.
+pub struct Event {
+	pub name: String,
+	/// Types of the parameters, along with whether they are indexed.
+	pub inputs: Vec<(String, bool)>,
+}
+
+impl Event {
+	/// The string hashed into the event topic.
+	pub fn canonical(&self) -> String {
+		let types: Vec<_> = self.inputs.iter().map(|(ty, _)| ty.as_str()).collect();
+		format!("{}({})", self.name, types.join(","))
+	}
+}
+
+/// Non-anonymous events spend their first topic on the signature.
+const MAX_INDEXED: usize = 3;
+
+/// Parses `name(type1,type2)`.
+pub fn parse_function(signature: &str) -> Result<Function, String> {
+	if signature.contains(char::is_whitespace) {
+		let compact: String = signature.split_whitespace().collect();
+		let hint = match parse_function(&compact) {
+			Ok(function) => format!(", expected `{}`", function.canonical()),
+			Err(_) => String::new(),
+		};
+		return Err(format!("Function signatures must not contain whitespace{}", hint))
+	}
+
+	let mut parser = Parser::new(signature);
+	let name = parser.identifier()?;
+	parser.expect('(')?;
+	let inputs = parser.list(')', |parser| parser.ty())?;
+	parser.end()?;
+	Ok(Function { name, inputs })
+}
+
+/// Parses `Name(type1 indexed,type2)`.
+pub fn parse_event(signature: &str) -> Result<Event, String> {
+	let mut parser = Parser::new(signature);
+	let name = parser.identifier()?;
+	parser.expect('(')?;
+	let inputs = parser.list(')', |parser| {
+		let ty = parser.ty()?;
+		Ok((ty, parser.eat(" indexed")))
+	})?;
+	parser.end()?;
+
+	if inputs.iter().filter(|(_, indexed)| *indexed).count() > MAX_INDEXED {
+		return Err(format!("Events have at most {} indexed parameters", MAX_INDEXED))
+	}
+	Ok(Event { name, inputs })
+}
+
+/// Parses the comma separated types of `#[returns(..)]`.
+pub fn parse_types(types: &str) -> Result<Vec<String>, String> {
+	let mut parser = Parser::new(types);
+	if parser.is_done() {
+		return Ok(vec![])
+	}
+	let mut result = vec![parser.ty()?];
+	while parser.eat(",") {
+		result.push(parser.ty()?);
+	}
+	parser.end()?;
+	Ok(result)
+}
+
+struct Parser<'a> {
+	input: &'a str,
+	pos: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn new(input: &'a str) -> Self {
+		Self { input, pos: 0 }
+	}
+
+	fn rest(&self) -> &'a str {
+		&self.input[self.pos..]
+	}
+
+	fn is_done(&self) -> bool {
+		self.rest().is_empty()
+	}
+
+	fn eat(&mut self, token: &str) -> bool {
+		let found = self.rest().starts_with(token);
+		if found {
+			self.pos += token.len();
+		}
+		found
+	}
+
+	fn unexpected(&self) -> String {
+		match self.rest().chars().next() {
+			Some(c) => format!("Unexpected `{}` at position {} of `{}`", c, self.pos, self.input),
+			None => format!("Unexpected end of `{}`", self.input),
+		}
+	}
+
+	fn expect(&mut self, token: char) -> Result<(), String> {
+		if self.rest().starts_with(token) {
+			self.pos += token.len_utf8();
+			Ok(())
+		} else {
+			Err(self.unexpected())
+		}
+	}
+
+	fn end(&self) -> Result<(), String> {
+		if self.is_done() {
+			Ok(())
+		} else {
+			Err(self.unexpected())
+		}
+	}
+
+	/// Takes the longest prefix whose characters match `pred`.
+	fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+		let rest = self.rest();
+		let len = rest.find(|c| !pred(c)).unwrap_or(rest.len());
+		self.pos += len;
+		&rest[..len]
+	}
+
+	fn identifier(&mut self) -> Result<String, String> {
+		let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '$';
+		let ident = self.take_while(is_ident);
+		match ident.chars().next() {
+			Some(c) if !c.is_ascii_digit() => Ok(ident.to_owned()),
+			_ => Err(format!("Expected a name at the start of `{}`", self.input)),
+		}
+	}
+
+	/// Parses the items of a list whose opening delimiter was consumed, up to `close`.
+	fn list<T>(
+		&mut self,
+		close: char,
+		mut item: impl FnMut(&mut Self) -> Result<T, String>,
+	) -> Result<Vec<T>, String> {
+		let mut items = vec![];
+		if self.rest().starts_with(close) {
+			self.pos += close.len_utf8();
+			return Ok(items)
+		}
+		loop {
+			items.push(item(self)?);
+			if self.eat(",") {
+				continue
+			}
+			self.expect(close)?;
+			return Ok(items)
+		}
+	}
+
+	fn ty(&mut self) -> Result<String, String> {
+		let mut ty = if self.eat("(") {
+			format!("({})", self.list(')', |parser| parser.ty())?.join(","))
+		} else {
+			let start = self.pos;
+			let word = self.take_while(|c| c.is_ascii_alphanumeric());
+			if word.is_empty() {
+				return Err(self.unexpected())
+			}
+			elementary(word).ok_or_else(|| {
+				format!(
+					"`{}` at position {} of `{}` is not a Solidity type",
+					word, start, self.input
+				)
+			})?
+		};
+
+		while self.eat("[") {
+			let len = self.take_while(|c| c.is_ascii_digit());
+			if len.starts_with('0') {
+				return Err(format!("Invalid array length `{}` in `{}`", len, self.input))
+			}
+			self.expect(']')?;
+			ty = format!("{}[{}]", ty, len);
+		}
+		Ok(ty)
+	}
+}
+
+/// Canonical form of an elementary type, if `word` is one.
+fn elementary(word: &str) -> Option<String> {
+	let canonical = match word {
+		"address" | "bool" | "string" | "bytes" | "function" => word.to_owned(),
+		"uint" | "int" => format!("{}256", word),
+		"fixed" | "ufixed" => format!("{}128x18", word),
+		_ => {
+			if let Some(len) = word.strip_prefix("bytes") {
+				number(len).filter(|len| (1..=32).contains(len))?;
+			} else if let Some(bits) =
+				word.strip_prefix("uint").or_else(|| word.strip_prefix("int"))
+			{
+				number(bits).filter(|bits| is_width(*bits))?;
+			} else {
+				let spec = word.strip_prefix("ufixed").or_else(|| word.strip_prefix("fixed"))?;
+				let (bits, decimals) = spec.split_once('x')?;
+				number(bits).filter(|bits| is_width(*bits))?;
+				number(decimals).filter(|decimals| (1..=80).contains(decimals))?;
+			}
+			word.to_owned()
+		},
+	};
+	Some(canonical)
+}
+
+fn is_width(bits: usize) -> bool {
+	(8..=256).contains(&bits) && bits % 8 == 0
+}
+
+/// Decimal number without leading zeros.
+fn number(digits: &str) -> Option<usize> {
+	if digits.starts_with('0') {
+		return None
+	}
+	digits.parse().ok()
+}
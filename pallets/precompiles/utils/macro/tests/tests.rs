@@ -29,6 +29,11 @@ pub enum Action {
 	Tata = "tata()",
 }
 
+#[precompile_utils_macro::generate_function_selector]
+pub enum AliasAction {
+	Deposit = "deposit(uint,int[2],(fixed,bytes32)[])",
+}
+
 #[test]
 fn test_keccak256() {
 	assert_eq!(&precompile_utils_macro::keccak256!(""), Keccak256::digest(b"").as_slice(),);
@@ -48,3 +53,17 @@ fn test_generate_function_selector() {
 	assert_eq!(&(Action::Tata as u32).to_be_bytes()[..], &Keccak256::digest(b"tata()")[0..4],);
 	assert_ne!(Action::Toto as u32, Action::Tata as u32);
 }
+
+#[test]
+fn test_generate_function_selector_normalizes_aliases() {
+	assert_eq!(
+		&(AliasAction::Deposit as u32).to_be_bytes()[..],
+		&Keccak256::digest(b"deposit(uint256,int256[2],(fixed128x18,bytes32)[])")[0..4],
+	);
+}
+
+#[test]
+fn test_generate_function_selector_rejects_invalid_signatures() {
+	let t = trybuild::TestCases::new();
+	t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+#[precompile_utils_macro::generate_function_selector]
+#[event("Moved(address indexed,address indexed,uint256 indexed,uint256 indexed)")]
+#[event("Approval(address, address, uint256)")]
+pub enum Action {
+	#[returns("uint256, bool")]
+	Transfer = "transfer(address,uint256)",
+}
+
+fn main() {}
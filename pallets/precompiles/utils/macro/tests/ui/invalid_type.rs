@@ -0,0 +1,9 @@
+#[precompile_utils_macro::generate_function_selector]
+pub enum Action {
+	Transfer = "transfer(adress,uint256)",
+	Mint = "mint(uint257)",
+	Hash = "hash(bytes33)",
+	Batch = "batch(address[0])",
+}
+
+fn main() {}
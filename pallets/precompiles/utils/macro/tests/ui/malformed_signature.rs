@@ -0,0 +1,10 @@
+#[precompile_utils_macro::generate_function_selector]
+pub enum Action {
+	Unclosed = "transfer(address,uint256",
+	NoParameters = "transfer",
+	NoName = "(address)",
+	TrailingComma = "transfer(address,)",
+	NotAString = 42,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[precompile_utils_macro::generate_function_selector]
+pub enum Action {
+	TransferFrom = "transferFrom(address,address,uint256)",
+	GasPrice = "gasprice_bit_ether(int128)",
+	Transfer = "transfer(address,uint256)",
+	TransferAgain = "transfer(address,uint)",
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+#[precompile_utils_macro::generate_function_selector]
+pub enum Action {
+	Transfer = "transfer(address, uint256)",
+}
+
+fn main() {}
@@ -0,0 +1,152 @@
+// This file is part of gpu.
+
+// Copyright (C) gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Harness to benchmark the precompiles with `frame-benchmarking`.
+//!
+//! A benchmark runs the precompile through a [`BenchmarkHandle`] in its `#[block]`, so the
+//! benchmarking CLI measures the ref time and the proof size of the precompile the same way it
+//! does for an extrinsic. The weights it generates are then charged by the precompile with
+//! [`PrecompileHandleExt::record_weight`](crate::PrecompileHandleExt::record_weight).
+//!
+//! The handle runs outside of the EVM. Subcalls succeed without running anything, their cost
+//! being charged by the EVM executing them and not by the precompile.
+
+use fp_evm::{
+	Context, ExitError, ExitReason, ExitSucceed, Log, Precompile, PrecompileHandle,
+	PrecompileResult, PrecompileSet, Transfer,
+};
+use sp_core::{H160, H256, U256};
+use sp_std::{vec, vec::Vec};
+
+/// Handle of a precompile call made by a benchmark, keeping the costs recorded by the precompile.
+pub struct BenchmarkHandle {
+	pub gas_limit: u64,
+	pub gas_used: u64,
+	/// Ref time recorded as an external cost.
+	pub ref_time: u64,
+	/// Proof size recorded as an external cost.
+	pub proof_size: u64,
+	pub logs: Vec<Log>,
+	/// Number of subcalls made by the precompile.
+	pub subcalls: usize,
+	pub code_address: H160,
+	pub input: Vec<u8>,
+	pub context: Context,
+	pub is_static: bool,
+}
+
+impl BenchmarkHandle {
+	/// A call of `input` by `caller` to the precompile at `code_address`, with no gas limit.
+	pub fn new(code_address: H160, caller: H160, input: Vec<u8>) -> Self {
+		Self {
+			gas_limit: u64::MAX,
+			gas_used: 0,
+			ref_time: 0,
+			proof_size: 0,
+			logs: vec![],
+			subcalls: 0,
+			code_address,
+			input,
+			context: Context { address: code_address, caller, apparent_value: U256::zero() },
+			is_static: false,
+		}
+	}
+
+	/// Runs the precompile `P`.
+	pub fn execute<P: Precompile>(&mut self) -> PrecompileResult {
+		P::execute(self)
+	}
+
+	/// Runs the precompile of `set` at the code address.
+	///
+	/// # Panics
+	///
+	/// If `set` has no precompile at the code address.
+	pub fn execute_in(&mut self, set: &impl PrecompileSet) -> PrecompileResult {
+		set.execute(self).expect("no precompile at the code address")
+	}
+}
+
+impl PrecompileHandle for BenchmarkHandle {
+	fn call(
+		&mut self,
+		_address: H160,
+		_transfer: Option<Transfer>,
+		_input: Vec<u8>,
+		_target_gas: Option<u64>,
+		_is_static: bool,
+		_context: &Context,
+	) -> (ExitReason, Vec<u8>) {
+		self.subcalls += 1;
+		(ExitReason::Succeed(ExitSucceed::Returned), vec![])
+	}
+
+	fn record_cost(&mut self, cost: u64) -> Result<(), ExitError> {
+		self.gas_used = self.gas_used.saturating_add(cost);
+
+		if self.gas_used > self.gas_limit {
+			Err(ExitError::OutOfGas)
+		} else {
+			Ok(())
+		}
+	}
+
+	fn record_external_cost(
+		&mut self,
+		ref_time: Option<u64>,
+		proof_size: Option<u64>,
+	) -> Result<(), ExitError> {
+		self.ref_time = self.ref_time.saturating_add(ref_time.unwrap_or_default());
+		self.proof_size = self.proof_size.saturating_add(proof_size.unwrap_or_default());
+		Ok(())
+	}
+
+	fn refund_external_cost(&mut self, ref_time: Option<u64>, proof_size: Option<u64>) {
+		self.ref_time = self.ref_time.saturating_sub(ref_time.unwrap_or_default());
+		self.proof_size = self.proof_size.saturating_sub(proof_size.unwrap_or_default());
+	}
+
+	fn remaining_gas(&self) -> u64 {
+		self.gas_limit.saturating_sub(self.gas_used)
+	}
+
+	fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) -> Result<(), ExitError> {
+		self.logs.push(Log { address, topics, data });
+		Ok(())
+	}
+
+	fn code_address(&self) -> H160 {
+		self.code_address
+	}
+
+	fn input(&self) -> &[u8] {
+		&self.input
+	}
+
+	fn context(&self) -> &Context {
+		&self.context
+	}
+
+	fn is_static(&self) -> bool {
+		self.is_static
+	}
+
+	fn gas_limit(&self) -> Option<u64> {
+		Some(self.gas_limit)
+	}
+}
@@ -89,18 +89,21 @@ impl<K, S: Get<u32>> BoundedBytesString<K, S> {
 }
 
 impl<K: Kind, S: Get<u32>> EvmData for BoundedBytesString<K, S> {
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		let mut inner_reader = reader.read_pointer()?;
 
 		// Read bytes/string size.
 		let array_size: usize = inner_reader
 			.read::<U256>()
-			.map_err(|_| revert("length, out of bounds"))?
+			.map_err(|_| Revert::new("tried to parse bytes/string length out of bounds"))?
 			.try_into()
-			.map_err(|_| revert("length, value too large"))?;
+			.map_err(|_| Revert::new("bytes/string length is too large"))?;
 
 		if array_size > S::get() as usize {
-			return Err(revert("length, value too large").into())
+			return Err(Revert::new(alloc::format!(
+				"bytes/string is longer than {} bytes",
+				S::get()
+			)))
 		}
 
 		let data = inner_reader.read_raw_bytes(array_size)?;
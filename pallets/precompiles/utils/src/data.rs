@@ -20,7 +20,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Utils.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{revert, EvmResult};
+use crate::revert::{InjectBacktrace, MayRevert, Revert};
 
 use alloc::borrow::ToOwned;
 use core::{any::type_name, marker::PhantomData, ops::Range};
@@ -83,27 +83,80 @@ impl From<Bytes> for Vec<u8> {
 	}
 }
 
+/// The `bytes1` to `bytes32` types of Solidity.
+/// Unlike `Bytes` the value is stored inline, left aligned in a single 32 bytes word.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedBytes<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> FixedBytes<N> {
+	const VALID_SIZE: () = assert!(N != 0 && N <= 32, "bytesN only exists for N from 1 to 32");
+}
+
+impl<const N: usize> From<[u8; N]> for FixedBytes<N> {
+	fn from(a: [u8; N]) -> Self {
+		Self(a)
+	}
+}
+
+impl<const N: usize> From<FixedBytes<N>> for [u8; N] {
+	fn from(a: FixedBytes<N>) -> [u8; N] {
+		a.0
+	}
+}
+
+/// The `int256` type of Solidity, held as its two's complement.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct I256(pub U256);
+
+impl I256 {
+	pub fn is_negative(&self) -> bool {
+		self.0.bit(255)
+	}
+
+	/// Returns the value if it fits in an `i128`.
+	pub fn to_i128(&self) -> Option<i128> {
+		if self.is_negative() {
+			let complement = !self.0;
+			(complement.bits() < 128).then_some(!(complement.low_u128() as i128))
+		} else {
+			(self.0.bits() < 128).then_some(self.0.low_u128() as i128)
+		}
+	}
+}
+
+impl From<i128> for I256 {
+	fn from(a: i128) -> Self {
+		if a < 0 {
+			I256(!U256::from(!a as u128))
+		} else {
+			I256(U256::from(a as u128))
+		}
+	}
+}
+
 /// Wrapper around an EVM input slice, helping to parse it.
 /// Provide functions to parse common types.
 #[derive(Clone, Copy, Debug)]
 pub struct EvmDataReader<'a> {
 	input: &'a [u8],
 	cursor: usize,
+	/// Position of the next argument, when reading the arguments of a call.
+	next_arg: Option<usize>,
 }
 
 impl<'a> EvmDataReader<'a> {
 	/// Create a new input parser.
 	pub fn new(input: &'a [u8]) -> Self {
-		Self { input, cursor: 0 }
+		Self { input, cursor: 0, next_arg: None }
 	}
 
 	/// Create a new input parser from a selector-initial input.
-	pub fn read_selector<T>(input: &'a [u8]) -> EvmResult<T>
+	pub fn read_selector<T>(input: &'a [u8]) -> MayRevert<T>
 	where
 		T: num_enum::TryFromPrimitive<Primitive = u32>,
 	{
 		if input.len() < 4 {
-			return Err(revert("tried to parse selector out of bounds"))
+			return Err(Revert::new("tried to parse selector out of bounds"))
 		}
 
 		let mut buffer = [0u8; 4];
@@ -114,72 +167,81 @@ impl<'a> EvmDataReader<'a> {
 				"Failed to match function selector for {}",
 				type_name::<T>()
 			);
-			revert("unknown selector")
+			Revert::new("unknown selector")
 		})?;
 
 		Ok(selector)
 	}
 
 	/// Create a new input parser from a selector-initial input.
-	pub fn new_skip_selector(input: &'a [u8]) -> EvmResult<Self> {
+	pub fn new_skip_selector(input: &'a [u8]) -> MayRevert<Self> {
 		if input.len() < 4 {
-			return Err(revert("input is too short"))
+			return Err(Revert::new("input is too short"))
 		}
 
-		Ok(Self::new(&input[4..]))
+		Ok(Self { next_arg: Some(0), ..Self::new(&input[4..]) })
 	}
 
 	/// Check the input has at least the correct amount of arguments before the end (32 bytes values).
-	pub fn expect_arguments(&self, args: usize) -> EvmResult {
+	pub fn expect_arguments(&self, args: usize) -> MayRevert {
 		if self.input.len() >= self.cursor + args * 32 {
 			Ok(())
 		} else {
-			Err(revert("input doesn't match expected length"))
+			Err(Revert::new("input doesn't match expected length"))
 		}
 	}
 
 	/// Read data from the input.
-	pub fn read<T: EvmData>(&mut self) -> EvmResult<T> {
-		T::read(self)
+	/// When reading the arguments of a call, a failure tells which argument could not be read.
+	pub fn read<T: EvmData>(&mut self) -> MayRevert<T> {
+		// Taking the position lets the values read by `T` itself go uncounted.
+		match self.next_arg.take() {
+			Some(arg) => {
+				let value = T::read(self).in_arg(arg);
+				self.next_arg = Some(arg + 1);
+				value
+			},
+			None => T::read(self),
+		}
 	}
 
 	/// Read raw bytes from the input.
 	/// Doesn't handle any alignment checks, prefer using `read` instead of possible.
 	/// Returns an error if trying to parse out of bounds.
-	pub fn read_raw_bytes(&mut self, len: usize) -> EvmResult<&[u8]> {
+	pub fn read_raw_bytes(&mut self, len: usize) -> MayRevert<&[u8]> {
 		let range = self.move_cursor(len)?;
 
 		let data = self
 			.input
 			.get(range)
-			.ok_or_else(|| revert("tried to parse raw bytes out of bounds"))?;
+			.ok_or_else(|| Revert::new("tried to parse raw bytes out of bounds"))?;
 
 		Ok(data)
 	}
 
 	/// Reads a pointer, returning a reader targetting the pointed location.
-	pub fn read_pointer(&mut self) -> EvmResult<Self> {
+	pub fn read_pointer(&mut self) -> MayRevert<Self> {
 		let offset: usize = self
 			.read::<U256>()
-			.map_err(|_| revert("tried to parse array offset out of bounds"))?
+			.map_err(|_| Revert::new("tried to parse array offset out of bounds"))?
 			.try_into()
-			.map_err(|_| revert("array offset is too large"))?;
+			.map_err(|_| Revert::new("array offset is too large"))?;
 
 		if offset >= self.input.len() {
-			return Err(revert("pointer points out of bounds"))
+			return Err(Revert::new("pointer points out of bounds"))
 		}
 
-		Ok(Self { input: &self.input[offset..], cursor: 0 })
+		Ok(Self::new(&self.input[offset..]))
 	}
 
 	/// Read remaining bytes
-	pub fn read_till_end(&mut self) -> EvmResult<&[u8]> {
+	pub fn read_till_end(&mut self) -> MayRevert<&[u8]> {
 		let range = self.move_cursor(self.input.len() - self.cursor)?;
 
 		let data = self
 			.input
 			.get(range)
-			.ok_or_else(|| revert("tried to parse raw bytes out of bounds"))?;
+			.ok_or_else(|| Revert::new("tried to parse raw bytes out of bounds"))?;
 
 		Ok(data)
 	}
@@ -187,12 +249,12 @@ impl<'a> EvmDataReader<'a> {
 	/// Move the reading cursor with provided length, and return a range from the previous cursor
 	/// location to the new one.
 	/// Checks cursor overflows.
-	fn move_cursor(&mut self, len: usize) -> EvmResult<Range<usize>> {
+	fn move_cursor(&mut self, len: usize) -> MayRevert<Range<usize>> {
 		let start = self.cursor;
 		let end = self
 			.cursor
 			.checked_add(len)
-			.ok_or_else(|| revert("data reading cursor overflow"))?;
+			.ok_or_else(|| Revert::new("data reading cursor overflow"))?;
 
 		self.cursor = end;
 
@@ -307,7 +369,7 @@ impl Default for EvmDataWriter {
 
 /// Data that can be converted from and to EVM data types.
 pub trait EvmData: Sized {
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self>;
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self>;
 	fn write(writer: &mut EvmDataWriter, value: Self);
 	fn has_static_size() -> bool;
 	fn is_explicit_tuple() -> bool {
@@ -334,18 +396,26 @@ pub fn encode_arguments<T: EvmData>(value: T) -> Vec<u8> {
 
 pub use self::{encode_arguments as encode_return_value, encode_arguments as encode_event_data};
 
+/// Reads the member of a tuple at `index`, moving `index` to the next one.
+fn read_member<T: EvmData>(reader: &mut EvmDataReader, index: &mut usize) -> MayRevert<T> {
+	let value = reader.read().in_tuple(*index);
+	*index += 1;
+	value
+}
+
 #[impl_for_tuples(1, 18)]
 impl EvmData for Tuple {
 	fn has_static_size() -> bool {
 		for_tuples!(#( Tuple::has_static_size() )&*)
 	}
 
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+		let mut index = 0;
 		if !Self::has_static_size() {
 			let reader = &mut reader.read_pointer()?;
-			Ok(for_tuples!( ( #( reader.read::<Tuple>()? ),* ) ))
+			Ok(for_tuples!( ( #( read_member::<Tuple>(reader, &mut index)? ),* ) ))
 		} else {
-			Ok(for_tuples!( ( #( reader.read::<Tuple>()? ),* ) ))
+			Ok(for_tuples!( ( #( read_member::<Tuple>(reader, &mut index)? ),* ) ))
 		}
 	}
 
@@ -361,13 +431,13 @@ impl EvmData for Tuple {
 }
 
 impl EvmData for H256 {
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		let range = reader.move_cursor(32)?;
 
 		let data = reader
 			.input
 			.get(range)
-			.ok_or_else(|| revert("tried to parse H256 out of bounds"))?;
+			.ok_or_else(|| Revert::new("tried to parse H256 out of bounds"))?;
 
 		Ok(H256::from_slice(data))
 	}
@@ -382,13 +452,13 @@ impl EvmData for H256 {
 }
 
 impl EvmData for Address {
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		let range = reader.move_cursor(32)?;
 
 		let data = reader
 			.input
 			.get(range)
-			.ok_or_else(|| revert("tried to parse H160 out of bounds"))?;
+			.ok_or_else(|| Revert::new("tried to parse H160 out of bounds"))?;
 
 		Ok(H160::from_slice(&data[12..32]).into())
 	}
@@ -403,13 +473,13 @@ impl EvmData for Address {
 }
 
 impl EvmData for U256 {
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		let range = reader.move_cursor(32)?;
 
 		let data = reader
 			.input
 			.get(range)
-			.ok_or_else(|| revert("tried to parse U256 out of bounds"))?;
+			.ok_or_else(|| Revert::new("tried to parse U256 out of bounds"))?;
 
 		Ok(U256::from_big_endian(data))
 	}
@@ -429,13 +499,13 @@ macro_rules! impl_evmdata_for_uints {
 	($($uint:ty, )*) => {
 		$(
 			impl EvmData for $uint {
-				fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+				fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 					let range = reader.move_cursor(32)?;
 
 					let data = reader
 						.input
 						.get(range)
-						.ok_or_else(|| revert(alloc::format!(
+						.ok_or_else(|| Revert::new(alloc::format!(
 							"tried to parse {} out of bounds", core::any::type_name::<Self>()
 						)))?;
 
@@ -462,13 +532,13 @@ impl_evmdata_for_uints!(u16, u32, u64, u128,);
 
 // The implementation for u8 is specific, for performance reasons.
 impl EvmData for u8 {
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		let range = reader.move_cursor(32)?;
 
 		let data = reader
 			.input
 			.get(range)
-			.ok_or_else(|| revert("tried to parse u64 out of bounds"))?;
+			.ok_or_else(|| Revert::new("tried to parse u64 out of bounds"))?;
 
 		Ok(data[31])
 	}
@@ -485,9 +555,105 @@ impl EvmData for u8 {
 	}
 }
 
+macro_rules! impl_evmdata_for_ints {
+	($($int:ty, )*) => {
+		$(
+			impl EvmData for $int {
+				fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+					let range = reader.move_cursor(32)?;
+
+					let data = reader
+						.input
+						.get(range)
+						.ok_or_else(|| Revert::new(alloc::format!(
+							"tried to parse {} out of bounds", core::any::type_name::<Self>()
+						)))?;
+
+					let size = core::mem::size_of::<Self>();
+					let mut buffer = [0u8; core::mem::size_of::<Self>()];
+					buffer.copy_from_slice(&data[32 - size..]);
+					let value = Self::from_be_bytes(buffer);
+
+					// The upper bytes must be the sign extension of the value.
+					let extension = if value < 0 { 0xff } else { 0 };
+					if data[..32 - size].iter().any(|byte| *byte != extension) {
+						return Err(Revert::new(alloc::format!(
+							"value is out of range for {}", core::any::type_name::<Self>()
+						)))
+					}
+
+					Ok(value)
+				}
+
+				fn write(writer: &mut EvmDataWriter, value: Self) {
+					let size = core::mem::size_of::<Self>();
+					let mut buffer = if value < 0 { [0xffu8; 32] } else { [0u8; 32] };
+					buffer[32 - size..].copy_from_slice(&value.to_be_bytes());
+					writer.data.extend_from_slice(&buffer);
+				}
+
+				fn has_static_size() -> bool {
+					true
+				}
+			}
+		)*
+	};
+}
+
+impl_evmdata_for_ints!(i8, i16, i32, i64, i128,);
+
+impl EvmData for I256 {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+		let value =
+			U256::read(reader).map_err(|_| Revert::new("tried to parse I256 out of bounds"))?;
+
+		Ok(I256(value))
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		U256::write(writer, value.0);
+	}
+
+	fn has_static_size() -> bool {
+		true
+	}
+}
+
+impl<const N: usize> EvmData for FixedBytes<N> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+		let () = Self::VALID_SIZE;
+		let range = reader.move_cursor(32)?;
+
+		let data = reader.input.get(range).ok_or_else(|| {
+			Revert::new(alloc::format!("tried to parse bytes{} out of bounds", N))
+		})?;
+
+		if data[N..].iter().any(|byte| *byte != 0) {
+			return Err(Revert::new(alloc::format!("bytes{} is not right padded with zeros", N)))
+		}
+
+		let mut value = [0u8; N];
+		value.copy_from_slice(&data[..N]);
+		Ok(Self(value))
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		let () = Self::VALID_SIZE;
+		let mut buffer = [0u8; 32];
+		buffer[..N].copy_from_slice(&value.0);
+
+		writer.data.extend_from_slice(&buffer);
+	}
+
+	fn has_static_size() -> bool {
+		true
+	}
+}
+
 impl EvmData for bool {
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
-		let h256 = H256::read(reader).map_err(|_| revert("tried to parse bool out of bounds"))?;
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+		let h256 =
+			H256::read(reader).map_err(|_| Revert::new("tried to parse bool out of bounds"))?;
 
 		Ok(!h256.is_zero())
 	}
@@ -507,14 +673,14 @@ impl EvmData for bool {
 }
 
 impl<T: EvmData> EvmData for Vec<T> {
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		let mut inner_reader = reader.read_pointer()?;
 
 		let array_size: usize = inner_reader
 			.read::<U256>()
-			.map_err(|_| revert("tried to parse array length out of bounds"))?
+			.map_err(|_| Revert::new("tried to parse array length out of bounds"))?
 			.try_into()
-			.map_err(|_| revert("array length is too large"))?;
+			.map_err(|_| Revert::new("array length is too large"))?;
 
 		let mut array = vec![];
 
@@ -522,12 +688,13 @@ impl<T: EvmData> EvmData for Vec<T> {
 			input: inner_reader
 				.input
 				.get(32..)
-				.ok_or_else(|| revert("try to read array items out of bound"))?,
+				.ok_or_else(|| Revert::new("try to read array items out of bound"))?,
 			cursor: 0,
+			next_arg: None,
 		};
 
-		for _ in 0..array_size {
-			array.push(item_reader.read()?);
+		for i in 0..array_size {
+			array.push(item_reader.read().in_array(i)?);
 		}
 
 		Ok(array)
@@ -560,16 +727,54 @@ impl<T: EvmData> EvmData for Vec<T> {
 	}
 }
 
+// `T[N]` is encoded in place like a tuple of `N` items, behind an offset when `T` is dynamic.
+impl<T: EvmData, const N: usize> EvmData for [T; N] {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
+		let mut items = Vec::with_capacity(N);
+
+		if Self::has_static_size() {
+			for i in 0..N {
+				items.push(reader.read().in_array(i)?);
+			}
+		} else {
+			let mut inner_reader = reader.read_pointer()?;
+			for i in 0..N {
+				items.push(inner_reader.read().in_array(i)?);
+			}
+		}
+
+		items.try_into().map_err(|_| Revert::new("fixed size array length mismatch"))
+	}
+
+	fn write(writer: &mut EvmDataWriter, value: Self) {
+		if Self::has_static_size() {
+			for item in value {
+				T::write(writer, item);
+			}
+		} else {
+			let mut inner_writer = EvmDataWriter::new();
+			for item in value {
+				T::write(&mut inner_writer, item);
+			}
+			writer.write_pointer(inner_writer.build());
+		}
+	}
+
+	fn has_static_size() -> bool {
+		T::has_static_size()
+	}
+}
+
 impl EvmData for Bytes {
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		let mut inner_reader = reader.read_pointer()?;
 
 		// Read bytes/string size.
 		let array_size: usize = inner_reader
 			.read::<U256>()
-			.map_err(|_| revert("tried to parse bytes/string length out of bounds"))?
+			.map_err(|_| Revert::new("tried to parse bytes/string length out of bounds"))?
 			.try_into()
-			.map_err(|_| revert("bytes/string length is too large"))?;
+			.map_err(|_| Revert::new("bytes/string length is too large"))?;
 
 		// Get valid range over the bytes data.
 		let range = inner_reader.move_cursor(array_size)?;
@@ -577,7 +782,7 @@ impl EvmData for Bytes {
 		let data = inner_reader
 			.input
 			.get(range)
-			.ok_or_else(|| revert("tried to parse bytes/string out of bounds"))?;
+			.ok_or_else(|| Revert::new("tried to parse bytes/string out of bounds"))?;
 
 		let bytes = Self(data.to_owned());
 
@@ -617,17 +822,17 @@ pub struct BoundedVec<T, S> {
 }
 
 impl<T: EvmData, S: Get<u32>> EvmData for BoundedVec<T, S> {
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		let mut inner_reader = reader.read_pointer()?;
 
 		let array_size: usize = inner_reader
 			.read::<U256>()
-			.map_err(|_| revert("out of bounds: length of array"))?
+			.map_err(|_| Revert::new("tried to parse array length out of bounds"))?
 			.try_into()
-			.map_err(|_| revert("value too large : Array has more than max items allowed"))?;
+			.map_err(|_| Revert::new("array length is too large"))?;
 
 		if array_size > S::get() as usize {
-			return Err(revert("value too large : Array has more than max items allowed").into())
+			return Err(Revert::new(alloc::format!("array has more than {} items", S::get())))
 		}
 
 		let mut array = vec![];
@@ -636,12 +841,13 @@ impl<T: EvmData, S: Get<u32>> EvmData for BoundedVec<T, S> {
 			input: inner_reader
 				.input
 				.get(32..)
-				.ok_or_else(|| revert("read out of bounds: array content"))?,
+				.ok_or_else(|| Revert::new("try to read array items out of bound"))?,
 			cursor: 0,
+			next_arg: None,
 		};
 
-		for _ in 0..array_size {
-			array.push(item_reader.read()?);
+		for i in 0..array_size {
+			array.push(item_reader.read().in_array(i)?);
 		}
 
 		Ok(BoundedVec { inner: array, _phantom: PhantomData })
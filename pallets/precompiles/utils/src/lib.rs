@@ -23,12 +23,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
+// Lets the code generated by `#[generate_function_selector]` name this crate from within it.
+extern crate self as precompile_utils;
 
 use crate::alloc::borrow::ToOwned;
 pub use alloc::string::String;
 use fp_evm::{
-	Context, ExitError, ExitRevert, ExitSucceed, PrecompileFailure, PrecompileHandle,
-	PrecompileOutput,
+	Context, ExitError, ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileOutput,
 };
 use frame_support::{
 	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
@@ -39,11 +40,17 @@ use pallet_evm::{GasWeightMapping, Log};
 use sp_core::{H160, H256, U256};
 use sp_std::{marker::PhantomData, vec, vec::Vec};
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
 pub mod bytes;
 pub mod data;
+pub mod revert;
+#[cfg(feature = "std")]
+pub mod solidity;
 
-pub use data::{Address, Bytes, EvmData, EvmDataReader, EvmDataWriter};
-pub use precompile_utils_macro::{generate_function_selector, keccak256};
+pub use data::{Address, Bytes, EvmData, EvmDataReader, EvmDataWriter, FixedBytes, I256};
+pub use precompile_utils_macro::{generate_function_selector, keccak256, EvmData};
+pub use revert::{CustomError, InjectBacktrace, MayRevert, Revert};
 
 #[cfg(feature = "testing")]
 pub mod testing;
@@ -293,6 +300,14 @@ pub trait PrecompileHandleExt: PrecompileHandle {
 		&mut self,
 		data_length: usize,
 	) -> Result<(), ExitError>;
+
+	/// Record the cost of a benchmarked weight, its ref time as gas and its proof size as an
+	/// external cost.
+	#[must_use]
+	fn record_weight<Runtime: pallet_evm::Config>(
+		&mut self,
+		weight: Weight,
+	) -> Result<(), ExitError>;
 }
 
 pub fn log_costs(topics: usize, data_len: usize) -> EvmResult<u64> {
@@ -413,13 +428,13 @@ impl<T: PrecompileHandle> PrecompileHandleExt for T {
 	where
 		S: num_enum::TryFromPrimitive<Primitive = u32>,
 	{
-		EvmDataReader::read_selector(self.input())
+		Ok(EvmDataReader::read_selector(self.input())?)
 	}
 
 	#[must_use]
 	/// Returns a reader of the input, skipping the selector.
 	fn read_input(&self) -> EvmResult<EvmDataReader> {
-		EvmDataReader::new_skip_selector(self.input())
+		Ok(EvmDataReader::new_skip_selector(self.input())?)
 	}
 
 	#[must_use]
@@ -430,14 +445,21 @@ impl<T: PrecompileHandle> PrecompileHandleExt for T {
 		self.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
 		self.record_external_cost(None, Some(data_length as u64))
 	}
+
+	#[must_use]
+	fn record_weight<Runtime: pallet_evm::Config>(
+		&mut self,
+		weight: Weight,
+	) -> Result<(), ExitError> {
+		self.record_cost(Runtime::GasWeightMapping::weight_to_gas(weight))?;
+		self.record_external_cost(None, Some(weight.proof_size()))
+	}
 }
 
+/// Revert with `Error(string)`, the way `revert("reason")` does in Solidity.
 #[must_use]
-pub fn revert(output: impl AsRef<[u8]>) -> PrecompileFailure {
-	PrecompileFailure::Revert {
-		exit_status: ExitRevert::Reverted,
-		output: output.as_ref().to_owned(),
-	}
+pub fn revert(message: impl Into<String>) -> PrecompileFailure {
+	Revert::new(message).into()
 }
 
 #[must_use]
@@ -0,0 +1,211 @@
+// This file is part of gpu.
+
+// Copyright (C) gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reverts of the precompiles, encoded the way Solidity encodes errors.
+//!
+//! A [`Revert`] either carries a message, sent as `Error(string)` like `require(cond, "reason")`
+//! does, or a custom error built with [`CustomError`] that callers can `try/catch` on. When a value
+//! of the input fails to decode, the message is prefixed with the path to that value, such as
+//! `arg 2[1]: value is too large`, which the `EvmData` implementations build through
+//! [`InjectBacktrace`].
+
+use crate::data::{Bytes, EvmData, EvmDataWriter};
+use alloc::string::{String, ToString};
+use fp_evm::{ExitRevert, PrecompileFailure};
+use sp_std::{fmt, vec::Vec};
+
+/// Selector of `Error(string)`, the error raised by `revert("reason")` and `require`.
+pub const ERROR_SELECTOR: u32 = 0x08c379a0;
+
+/// Alias for Result returning a revert, the error of the input decoding functions.
+pub type MayRevert<T = ()> = Result<T, Revert>;
+
+/// Part of the path to a value of the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BacktracePart {
+	/// Argument of the called function, by position.
+	Arg(usize),
+	/// Named field of a struct.
+	Field(&'static str),
+	/// Member of a tuple.
+	Tuple(usize),
+	/// Item of an array.
+	Array(usize),
+}
+
+/// Path to a value of the input.
+///
+/// Parts are pushed while the error bubbles up the decoding functions, so they are stored from
+/// the innermost to the outermost.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Backtrace(Vec<BacktracePart>);
+
+impl Backtrace {
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+}
+
+impl fmt::Display for Backtrace {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, part) in self.0.iter().rev().enumerate() {
+			match part {
+				BacktracePart::Arg(index) if i == 0 => write!(f, "arg {}", index)?,
+				BacktracePart::Arg(index) => write!(f, ".arg {}", index)?,
+				BacktracePart::Field(name) if i == 0 => write!(f, "{}", name)?,
+				BacktracePart::Field(name) => write!(f, ".{}", name)?,
+				BacktracePart::Tuple(index) if i == 0 => write!(f, "{}", index)?,
+				BacktracePart::Tuple(index) => write!(f, ".{}", index)?,
+				BacktracePart::Array(index) => write!(f, "[{}]", index)?,
+			}
+		}
+		Ok(())
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Reason {
+	Message(String),
+	/// Selector and arguments of a custom error.
+	Custom(Vec<u8>),
+}
+
+/// Reason of a precompile revert, converted into the `PrecompileFailure` of the call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Revert {
+	reason: Reason,
+	backtrace: Backtrace,
+}
+
+impl Revert {
+	/// Revert with `Error(string)`.
+	pub fn new(message: impl Into<String>) -> Self {
+		Self { reason: Reason::Message(message.into()), backtrace: Backtrace::default() }
+	}
+
+	pub fn backtrace(&self) -> &Backtrace {
+		&self.backtrace
+	}
+
+	/// Output of the reverting precompile. The backtrace only appears in messages, custom errors
+	/// being sent as is.
+	pub fn to_encoded_bytes(&self) -> Vec<u8> {
+		match &self.reason {
+			Reason::Message(_) => EvmDataWriter::new_with_selector(ERROR_SELECTOR)
+				.write(Bytes::from(self.to_string().as_str()))
+				.build(),
+			Reason::Custom(output) => output.clone(),
+		}
+	}
+}
+
+impl fmt::Display for Revert {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if !self.backtrace.is_empty() {
+			write!(f, "{}: ", self.backtrace)?;
+		}
+		match &self.reason {
+			Reason::Message(message) => write!(f, "{}", message),
+			Reason::Custom(output) => {
+				write!(f, "custom error 0x")?;
+				output.iter().take(4).try_for_each(|byte| write!(f, "{:02x}", byte))
+			},
+		}
+	}
+}
+
+impl From<Revert> for PrecompileFailure {
+	fn from(revert: Revert) -> Self {
+		PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: revert.to_encoded_bytes(),
+		}
+	}
+}
+
+/// Builder of a Solidity custom error, such as
+/// `error InsufficientBalance(uint256 balance, uint256 needed)`.
+///
+/// ```ignore
+/// pub const ERROR_INSUFFICIENT_BALANCE: [u8; 32] =
+///     keccak256!("InsufficientBalance(uint256,uint256)");
+///
+/// return Err(CustomError::new(ERROR_INSUFFICIENT_BALANCE).write(balance).write(needed).into())
+/// ```
+#[derive(Clone, Debug)]
+pub struct CustomError(EvmDataWriter);
+
+impl CustomError {
+	/// Starts the error whose signature hashes to `signature`, the first 4 bytes of the hash being
+	/// its selector.
+	pub fn new(signature: [u8; 32]) -> Self {
+		let selector = u32::from_be_bytes([signature[0], signature[1], signature[2], signature[3]]);
+		Self(EvmDataWriter::new_with_selector(selector))
+	}
+
+	/// Appends the next argument of the error.
+	pub fn write<T: EvmData>(self, value: T) -> Self {
+		Self(self.0.write(value))
+	}
+}
+
+impl From<CustomError> for Revert {
+	fn from(error: CustomError) -> Self {
+		Self { reason: Reason::Custom(error.0.build()), backtrace: Backtrace::default() }
+	}
+}
+
+impl From<CustomError> for PrecompileFailure {
+	fn from(error: CustomError) -> Self {
+		Revert::from(error).into()
+	}
+}
+
+/// Records where in the input a revert happened.
+pub trait InjectBacktrace: Sized {
+	fn in_part(self, part: BacktracePart) -> Self;
+
+	fn in_arg(self, index: usize) -> Self {
+		self.in_part(BacktracePart::Arg(index))
+	}
+
+	fn in_field(self, name: &'static str) -> Self {
+		self.in_part(BacktracePart::Field(name))
+	}
+
+	fn in_tuple(self, index: usize) -> Self {
+		self.in_part(BacktracePart::Tuple(index))
+	}
+
+	fn in_array(self, index: usize) -> Self {
+		self.in_part(BacktracePart::Array(index))
+	}
+}
+
+impl InjectBacktrace for Revert {
+	fn in_part(mut self, part: BacktracePart) -> Self {
+		self.backtrace.0.push(part);
+		self
+	}
+}
+
+impl<T> InjectBacktrace for MayRevert<T> {
+	fn in_part(self, part: BacktracePart) -> Self {
+		self.map_err(|revert| revert.in_part(part))
+	}
+}
@@ -0,0 +1,284 @@
+// This file is part of gpu.
+
+// Copyright (C) gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Solidity interface and ABI JSON of the precompiles.
+//!
+//! `#[generate_function_selector]` implements [`SolidityInterface`] for the `Action` enums it
+//! expands, describing the functions with the signatures of the variants, and the events and
+//! custom errors with the `#[event(..)]` and `#[error(..)]` attributes of the enum. [`interface`]
+//! and [`abi_json`] render that description in the formats consumed by the dApps.
+
+use std::fmt::Write;
+
+/// State mutability of a precompile function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateMutability {
+	View,
+	NonPayable,
+	Payable,
+}
+
+impl StateMutability {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::View => "view",
+			Self::NonPayable => "nonpayable",
+			Self::Payable => "payable",
+		}
+	}
+}
+
+/// A precompile function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Function {
+	pub selector: u32,
+	pub name: &'static str,
+	pub inputs: &'static [&'static str],
+	pub outputs: &'static [&'static str],
+	pub mutability: StateMutability,
+}
+
+/// A parameter of a precompile event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventParam {
+	pub ty: &'static str,
+	pub indexed: bool,
+}
+
+/// An event emitted by a precompile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Event {
+	/// Topic of the event, the Keccak of its signature.
+	pub selector: [u8; 32],
+	pub name: &'static str,
+	pub inputs: &'static [EventParam],
+}
+
+/// A custom error a precompile reverts with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Error {
+	pub selector: u32,
+	pub name: &'static str,
+	pub inputs: &'static [&'static str],
+}
+
+/// Functions, events and custom errors of a precompile.
+pub trait SolidityInterface {
+	const FUNCTIONS: &'static [Function];
+	const EVENTS: &'static [Event];
+	const ERRORS: &'static [Error];
+}
+
+/// Renders the Solidity interface `name` of a precompile.
+///
+/// # Panics
+///
+/// Tuple parameters have no Solidity spelling without a named struct and are not supported.
+pub fn interface<T: SolidityInterface>(name: &str) -> String {
+	let mut out = String::new();
+	writeln!(out, "// SPDX-License-Identifier: GPL-3.0-only").unwrap();
+	writeln!(out, "pragma solidity >=0.8.0;").unwrap();
+	writeln!(out).unwrap();
+	writeln!(out, "// This file is generated from the precompile sources, do not edit it.")
+		.unwrap();
+	writeln!(out, "interface {} {{", name).unwrap();
+
+	for event in T::EVENTS {
+		let params: Vec<_> = event
+			.inputs
+			.iter()
+			.map(|param| {
+				let ty = solidity_type(param.ty);
+				if param.indexed {
+					format!("{} indexed", ty)
+				} else {
+					ty.to_owned()
+				}
+			})
+			.collect();
+		writeln!(out, "    /// @custom:selector {}", hex(&event.selector)).unwrap();
+		writeln!(out, "    event {}({});", event.name, params.join(", ")).unwrap();
+		writeln!(out).unwrap();
+	}
+
+	for error in T::ERRORS {
+		let params: Vec<_> = error.inputs.iter().map(|ty| solidity_type(ty)).collect();
+		writeln!(out, "    /// @custom:selector {:08x}", error.selector).unwrap();
+		writeln!(out, "    error {}({});", error.name, params.join(", ")).unwrap();
+		writeln!(out).unwrap();
+	}
+
+	for (i, function) in T::FUNCTIONS.iter().enumerate() {
+		if i > 0 {
+			writeln!(out).unwrap();
+		}
+		let inputs: Vec<_> =
+			function.inputs.iter().map(|ty| with_location(ty, "calldata")).collect();
+		writeln!(out, "    /// @custom:selector {:08x}", function.selector).unwrap();
+		write!(out, "    function {}({}) external", function.name, inputs.join(", ")).unwrap();
+		match function.mutability {
+			StateMutability::NonPayable => (),
+			mutability => write!(out, " {}", mutability.as_str()).unwrap(),
+		}
+		if !function.outputs.is_empty() {
+			let outputs: Vec<_> =
+				function.outputs.iter().map(|ty| with_location(ty, "memory")).collect();
+			write!(out, " returns ({})", outputs.join(", ")).unwrap();
+		}
+		writeln!(out, ";").unwrap();
+	}
+
+	writeln!(out, "}}").unwrap();
+	out
+}
+
+/// Renders the ABI JSON of a precompile, functions first, then events and errors.
+pub fn abi_json<T: SolidityInterface>() -> String {
+	let functions = T::FUNCTIONS.iter().map(|function| {
+		Json::Object(vec![
+			("type", Json::Str("function".into())),
+			("name", Json::Str(function.name.into())),
+			("inputs", Json::Array(function.inputs.iter().map(|ty| abi_param(ty, None)).collect())),
+			(
+				"outputs",
+				Json::Array(function.outputs.iter().map(|ty| abi_param(ty, None)).collect()),
+			),
+			("stateMutability", Json::Str(function.mutability.as_str().into())),
+		])
+	});
+	let events = T::EVENTS.iter().map(|event| {
+		Json::Object(vec![
+			("type", Json::Str("event".into())),
+			("name", Json::Str(event.name.into())),
+			(
+				"inputs",
+				Json::Array(
+					event
+						.inputs
+						.iter()
+						.map(|param| abi_param(param.ty, Some(param.indexed)))
+						.collect(),
+				),
+			),
+			("anonymous", Json::Bool(false)),
+		])
+	});
+	let errors = T::ERRORS.iter().map(|error| {
+		Json::Object(vec![
+			("type", Json::Str("error".into())),
+			("name", Json::Str(error.name.into())),
+			("inputs", Json::Array(error.inputs.iter().map(|ty| abi_param(ty, None)).collect())),
+		])
+	});
+
+	let mut out = String::new();
+	Json::Array(functions.chain(events).chain(errors).collect()).write(&mut out, 0);
+	out.push('\n');
+	out
+}
+
+/// ABI JSON entry of a parameter, tuples being described by their components.
+fn abi_param(ty: &str, indexed: Option<bool>) -> Json {
+	let mut fields = vec![("name", Json::Str(String::new()))];
+	match ty.strip_prefix('(').and_then(|ty| ty.rsplit_once(')')) {
+		Some((components, suffix)) => {
+			fields.push(("type", Json::Str(format!("tuple{}", suffix))));
+			fields.push((
+				"components",
+				Json::Array(split_types(components).map(|ty| abi_param(ty, None)).collect()),
+			));
+		},
+		None => fields.push(("type", Json::Str(ty.into()))),
+	}
+	if let Some(indexed) = indexed {
+		fields.push(("indexed", Json::Bool(indexed)));
+	}
+	Json::Object(fields)
+}
+
+/// Splits the comma separated components of a tuple, leaving nested tuples whole.
+fn split_types(types: &str) -> impl Iterator<Item = &str> {
+	let mut depth = 0usize;
+	types
+		.split(move |c| {
+			match c {
+				'(' => depth += 1,
+				')' => depth -= 1,
+				_ => (),
+			}
+			c == ',' && depth == 0
+		})
+		.filter(|ty| !ty.is_empty())
+}
+
+/// Solidity spelling of an ABI type.
+fn solidity_type(ty: &str) -> &str {
+	assert!(!ty.starts_with('('), "tuple type `{}` has no Solidity spelling", ty);
+	ty
+}
+
+/// Solidity spelling of a function parameter, with a data location for the dynamic types.
+fn with_location(ty: &str, location: &str) -> String {
+	let ty = solidity_type(ty);
+	if ty == "bytes" || ty == "string" || ty.ends_with(']') {
+		format!("{} {}", ty, location)
+	} else {
+		ty.to_owned()
+	}
+}
+
+fn hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Just enough JSON to print an ABI the way `JSON.stringify(abi, null, 2)` does.
+enum Json {
+	Str(String),
+	Bool(bool),
+	Array(Vec<Json>),
+	Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+	fn write(&self, out: &mut String, indent: usize) {
+		let pad = |depth: usize| "  ".repeat(depth);
+		match self {
+			Json::Str(value) => write!(out, "\"{}\"", value).unwrap(),
+			Json::Bool(value) => write!(out, "{}", value).unwrap(),
+			Json::Array(items) if items.is_empty() => out.push_str("[]"),
+			Json::Array(items) => {
+				out.push_str("[\n");
+				for (i, item) in items.iter().enumerate() {
+					out.push_str(&pad(indent + 1));
+					item.write(out, indent + 1);
+					out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+				}
+				write!(out, "{}]", pad(indent)).unwrap();
+			},
+			Json::Object(fields) => {
+				out.push_str("{\n");
+				for (i, (key, value)) in fields.iter().enumerate() {
+					write!(out, "{}\"{}\": ", pad(indent + 1), key).unwrap();
+					value.write(out, indent + 1);
+					out.push_str(if i + 1 < fields.len() { ",\n" } else { "\n" });
+				}
+				write!(out, "{}}}", pad(indent)).unwrap();
+			},
+		}
+	}
+}
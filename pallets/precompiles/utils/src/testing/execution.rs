@@ -209,13 +209,14 @@ impl<'p, P: PrecompileSet> PrecompilesTester<'p, P> {
 	}
 
 	/// Execute the precompile set and check if it reverts.
-	/// Take a closure allowing to perform custom matching on the output.
+	/// Take a closure allowing to perform custom matching on the output, which is given the
+	/// message of `Error(string)` reverts and the raw output of other ones.
 	pub fn execute_reverts(mut self, check: impl Fn(&[u8]) -> bool) {
 		let res = self.execute();
 		assert_matches!(
 			res,
 			Some(Err(PrecompileFailure::Revert { output, ..}))
-				if check(&output)
+				if check(decode_revert_message(&output))
 		);
 		self.assert_optionals();
 	}
@@ -29,17 +29,19 @@ pub use execution::*;
 pub use handle::*;
 
 use fp_evm::Log;
+use sp_core::U256;
 
+/// Message of an `Error(string)` revert output. Other outputs, such as custom errors or the raw
+/// output of a subcall, are returned as is.
 pub fn decode_revert_message(encoded: &[u8]) -> &[u8] {
-	let encoded_len = encoded.len();
 	// selector 4 + offset 32 + string length 32
-	if encoded_len > 68 {
-		let message_len = encoded[36..68].iter().sum::<u8>();
-		if encoded_len >= 68 + message_len as usize {
-			return &encoded[68..68 + message_len as usize]
+	if encoded.len() >= 68 && encoded[..4] == crate::revert::ERROR_SELECTOR.to_be_bytes() {
+		let message_len = U256::from_big_endian(&encoded[36..68]);
+		if message_len <= U256::from(encoded.len() - 68) {
+			return &encoded[68..68 + message_len.as_usize()]
 		}
 	}
-	b"decode_revert_message: error"
+	encoded
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -370,10 +370,7 @@ fn read_address_array_size_too_big() {
 
 	match reader.read::<Vec<Address>>() {
 		Ok(_) => panic!("should not parse correctly"),
-		Err(PrecompileFailure::Revert { output: err, .. }) => {
-			assert_eq!(err, b"tried to parse H160 out of bounds")
-		},
-		Err(_) => panic!("unexpected error"),
+		Err(err) => assert_eq!(err.to_string(), "[5]: tried to parse H160 out of bounds"),
 	}
 }
 
@@ -652,7 +649,7 @@ struct MultiLocation {
 }
 
 impl EvmData for MultiLocation {
-	fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+	fn read(reader: &mut EvmDataReader) -> MayRevert<Self> {
 		let (parents, interior) = reader.read()?;
 		Ok(MultiLocation { parents, interior })
 	}
@@ -729,3 +726,455 @@ fn read_complex_solidity_function() {
 	// weight
 	assert_eq!(reader.read::<U256>().unwrap(), 100u32.into());
 }
+
+#[crate::generate_function_selector]
+#[event("Transfer(address indexed,address indexed,uint256)")]
+#[derive(Debug, PartialEq)]
+pub enum TokenAction {
+	#[view]
+	#[returns("uint256")]
+	BalanceOf = "balanceOf(address)",
+	#[returns("bool")]
+	Transfer = "transfer(address,uint256)",
+	#[payable]
+	Deposit = "deposit(bytes[])",
+}
+
+#[test]
+fn solidity_interface_of_action() {
+	use crate::solidity::{interface, SolidityInterface};
+
+	assert_eq!(TokenAction::EVENTS[0].selector, keccak256!("Transfer(address,address,uint256)"));
+	assert_eq!(
+		interface::<TokenAction>("IToken"),
+		"// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+// This file is generated from the precompile sources, do not edit it.
+interface IToken {
+    /// @custom:selector ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef
+    event Transfer(address indexed, address indexed, uint256);
+
+    /// @custom:selector 70a08231
+    function balanceOf(address) external view returns (uint256);
+
+    /// @custom:selector a9059cbb
+    function transfer(address, uint256) external returns (bool);
+
+    /// @custom:selector 703a8b96
+    function deposit(bytes[] calldata) external payable;
+}
+"
+	);
+}
+
+#[test]
+fn solidity_abi_json_describes_tuples() {
+	let abi = crate::solidity::abi_json::<Action>();
+
+	assert!(abi.contains(
+		r#"{
+        "name": "",
+        "type": "tuple",
+        "components": [
+          {
+            "name": "",
+            "type": "uint8"
+          },
+          {
+            "name": "",
+            "type": "bytes[]"
+          }
+        ]
+      }"#
+	));
+	assert!(abi.contains(r#""stateMutability": "nonpayable""#));
+}
+
+/// Checks that `value` encodes as `ethabi` encodes `token`, and that both decode back to the
+/// original value.
+fn assert_ethabi_round_trip<T>(value: T, kind: ethabi::ParamType, token: ethabi::Token)
+where
+	T: EvmData + Clone + PartialEq + core::fmt::Debug,
+{
+	let encoded = EvmDataWriter::new().write(value.clone()).build();
+	assert_eq!(encoded, ethabi::encode(&[token.clone()]));
+	assert_eq!(ethabi::decode(&[kind], &encoded).unwrap(), vec![token]);
+	assert_eq!(EvmDataReader::new(&encoded).read::<T>().unwrap(), value);
+}
+
+fn ethabi_int(value: i128) -> ethabi::Token {
+	let mut bytes = if value < 0 { [0xffu8; 32] } else { [0u8; 32] };
+	bytes[16..].copy_from_slice(&value.to_be_bytes());
+	ethabi::Token::Int(ethabi::Int::from_big_endian(&bytes))
+}
+
+fn ethabi_uint(value: u64) -> ethabi::Token {
+	ethabi::Token::Uint(value.into())
+}
+
+fn ethabi_address(value: H160) -> ethabi::Token {
+	ethabi::Token::Address(ethabi::Address::from_slice(value.as_bytes()))
+}
+
+#[test]
+fn ethabi_round_trip_signed_ints() {
+	use ethabi::ParamType::Int;
+
+	assert_ethabi_round_trip(-1i8, Int(8), ethabi_int(-1));
+	assert_ethabi_round_trip(i8::MIN, Int(8), ethabi_int(i8::MIN.into()));
+	assert_ethabi_round_trip(1234i16, Int(16), ethabi_int(1234));
+	assert_ethabi_round_trip(-70_000i32, Int(32), ethabi_int(-70_000));
+	assert_ethabi_round_trip(i64::MIN, Int(64), ethabi_int(i64::MIN.into()));
+	assert_ethabi_round_trip(i128::MAX, Int(128), ethabi_int(i128::MAX));
+	assert_ethabi_round_trip(I256::from(-5), Int(256), ethabi_int(-5));
+	assert_ethabi_round_trip(I256(U256::MAX >> 1), Int(256), ethabi::Token::Int(U256::MAX >> 1));
+}
+
+#[test]
+fn read_signed_int_out_of_range() {
+	let data = EvmDataWriter::new().write(255u8).build();
+	assert!(EvmDataReader::new(&data).read::<i8>().is_err());
+
+	let data = EvmDataWriter::new().write(-129i16).build();
+	assert!(EvmDataReader::new(&data).read::<i8>().is_err());
+	assert_eq!(EvmDataReader::new(&data).read::<i16>().unwrap(), -129);
+}
+
+#[test]
+fn i256_to_i128() {
+	assert_eq!(I256::from(i128::MIN).to_i128(), Some(i128::MIN));
+	assert_eq!(I256::from(i128::MAX).to_i128(), Some(i128::MAX));
+	assert!(I256::from(-1).is_negative());
+	assert_eq!(I256(U256::one() << 200).to_i128(), None);
+	assert_eq!(I256(!(U256::one() << 200)).to_i128(), None);
+}
+
+#[test]
+fn ethabi_round_trip_fixed_bytes() {
+	use ethabi::{ParamType::FixedBytes as Kind, Token};
+
+	assert_ethabi_round_trip(
+		FixedBytes([1, 2, 3, 4]),
+		Kind(4),
+		Token::FixedBytes(vec![1, 2, 3, 4]),
+	);
+	assert_ethabi_round_trip(FixedBytes([0xaa; 31]), Kind(31), Token::FixedBytes(vec![0xaa; 31]));
+
+	let mut data = EvmDataWriter::new().write(FixedBytes([1, 2, 3, 4])).build();
+	data[4] = 5;
+	assert!(EvmDataReader::new(&data).read::<FixedBytes<4>>().is_err());
+}
+
+#[test]
+fn ethabi_round_trip_fixed_arrays() {
+	use ethabi::{ParamType, Token};
+
+	assert_ethabi_round_trip(
+		[1u32, 2, 3],
+		ParamType::FixedArray(Box::new(ParamType::Uint(32)), 3),
+		Token::FixedArray(vec![ethabi_uint(1), ethabi_uint(2), ethabi_uint(3)]),
+	);
+	assert_ethabi_round_trip(
+		[Bytes::from("gpu"), Bytes::from(&[0x11; 40][..])],
+		ParamType::FixedArray(Box::new(ParamType::Bytes), 2),
+		Token::FixedArray(vec![Token::Bytes(b"gpu".to_vec()), Token::Bytes(vec![0x11; 40])]),
+	);
+	assert_ethabi_round_trip(
+		vec![[Address(H160::repeat_byte(0x11)), Address(H160::repeat_byte(0x22))]],
+		ParamType::Array(Box::new(ParamType::FixedArray(Box::new(ParamType::Address), 2))),
+		Token::Array(vec![Token::FixedArray(vec![
+			ethabi_address(H160::repeat_byte(0x11)),
+			ethabi_address(H160::repeat_byte(0x22)),
+		])]),
+	);
+}
+
+#[derive(Clone, Debug, PartialEq, EvmData)]
+struct Vote {
+	poll: u32,
+	aye: bool,
+	conviction: i8,
+	balance: U256,
+}
+
+#[derive(Clone, Debug, PartialEq, EvmData)]
+struct Referendum {
+	name: Bytes,
+	votes: Vec<Vote>,
+	tally: [U256; 3],
+}
+
+#[derive(Clone, Debug, PartialEq, EvmData)]
+struct Delegation(Address, Bytes);
+
+#[derive(Clone, Debug, PartialEq, EvmData)]
+struct Wrapper<T> {
+	inner: T,
+}
+
+#[test]
+fn ethabi_round_trip_derived_structs() {
+	use ethabi::{ParamType, Token};
+
+	let vote = Vote { poll: 7, aye: true, conviction: -3, balance: 1_000.into() };
+	let vote_kind = ParamType::Tuple(vec![
+		ParamType::Uint(32),
+		ParamType::Bool,
+		ParamType::Int(8),
+		ParamType::Uint(256),
+	]);
+	let vote_token =
+		Token::Tuple(vec![ethabi_uint(7), Token::Bool(true), ethabi_int(-3), ethabi_uint(1_000)]);
+	assert_ethabi_round_trip(vote.clone(), vote_kind.clone(), vote_token.clone());
+
+	assert_ethabi_round_trip(
+		Referendum {
+			name: Bytes::from("root"),
+			votes: vec![vote.clone(), vote],
+			tally: [1.into(), 2.into(), 3.into()],
+		},
+		ParamType::Tuple(vec![
+			ParamType::Bytes,
+			ParamType::Array(Box::new(vote_kind)),
+			ParamType::FixedArray(Box::new(ParamType::Uint(256)), 3),
+		]),
+		Token::Tuple(vec![
+			Token::Bytes(b"root".to_vec()),
+			Token::Array(vec![vote_token.clone(), vote_token]),
+			Token::FixedArray(vec![ethabi_uint(1), ethabi_uint(2), ethabi_uint(3)]),
+		]),
+	);
+
+	assert_ethabi_round_trip(
+		Delegation(Address(H160::repeat_byte(0x33)), Bytes::from("proxy")),
+		ParamType::Tuple(vec![ParamType::Address, ParamType::Bytes]),
+		Token::Tuple(vec![
+			ethabi_address(H160::repeat_byte(0x33)),
+			Token::Bytes(b"proxy".to_vec()),
+		]),
+	);
+
+	assert_ethabi_round_trip(
+		Wrapper { inner: 5u64 },
+		ParamType::Tuple(vec![ParamType::Uint(64)]),
+		Token::Tuple(vec![ethabi_uint(5)]),
+	);
+}
+
+#[test]
+fn revert_encodes_error_string() {
+	let output = match revert("Permit expired") {
+		PrecompileFailure::Revert { output, .. } => output,
+		other => panic!("unexpected failure: {:?}", other),
+	};
+
+	let mut expected = revert::ERROR_SELECTOR.to_be_bytes().to_vec();
+	expected.extend(ethabi::encode(&[ethabi::Token::String("Permit expired".into())]));
+	assert_eq!(output, expected);
+}
+
+#[test]
+fn custom_error_encodes_selector_and_arguments() {
+	let failure: PrecompileFailure =
+		CustomError::new(keccak256!("InsufficientBalance(uint256,uint256)"))
+			.write(U256::from(5))
+			.write(U256::from(7))
+			.into();
+	let output = match failure {
+		PrecompileFailure::Revert { output, .. } => output,
+		other => panic!("unexpected failure: {:?}", other),
+	};
+
+	let error = ethabi::AbiError {
+		name: "InsufficientBalance".into(),
+		inputs: vec![
+			ethabi::Param {
+				name: "balance".into(),
+				kind: ethabi::ParamType::Uint(256),
+				internal_type: None,
+			},
+			ethabi::Param {
+				name: "needed".into(),
+				kind: ethabi::ParamType::Uint(256),
+				internal_type: None,
+			},
+		],
+	};
+	assert_eq!(output, error.encode(&[ethabi_uint(5), ethabi_uint(7)]).unwrap());
+}
+
+#[test]
+fn read_failure_reports_argument() {
+	let input = EvmDataWriter::new_with_selector(0x12345678u32)
+		.write(Address(H160::repeat_byte(0x11)))
+		.write(300u16)
+		.build();
+
+	let mut reader = EvmDataReader::new_skip_selector(&input).unwrap();
+	reader.read::<Address>().unwrap();
+	let err = reader.read::<i8>().unwrap_err();
+	assert_eq!(err.to_string(), "arg 1: value is out of range for i8");
+	assert_eq!(err.backtrace().to_string(), "arg 1");
+
+	// Readers of a plain buffer have no arguments.
+	let err = EvmDataReader::new(&input[36..]).read::<i8>().unwrap_err();
+	assert_eq!(err.to_string(), "value is out of range for i8");
+}
+
+#[test]
+fn read_failure_reports_path_in_argument() {
+	let referendum = Referendum {
+		name: Bytes::from("root"),
+		votes: vec![
+			Vote { poll: 1, aye: true, conviction: 0, balance: 1.into() },
+			Vote { poll: 2, aye: false, conviction: 0, balance: 1.into() },
+		],
+		tally: [0.into(), 0.into(), 0.into()],
+	};
+	// Words before the conviction of the second vote: the offset of the referendum, its head,
+	// its name, the length of the votes and the first vote, then the poll and aye of the second.
+	let conviction = 32 * (1 + 5 + 2 + 1 + 4 + 2);
+
+	let mut data = EvmDataWriter::new().write(referendum.clone()).build();
+	data[conviction..conviction + 32].copy_from_slice(&[0x11; 32]);
+	let err = EvmDataReader::new(&data).read::<Referendum>().unwrap_err();
+	assert_eq!(err.to_string(), "votes[1].conviction: value is out of range for i8");
+
+	let mut input = EvmDataWriter::new_with_selector(0x12345678u32)
+		.write(true)
+		.write(referendum)
+		.build();
+	let conviction = 4 + 32 + conviction;
+	input[conviction..conviction + 32].copy_from_slice(&[0x11; 32]);
+	let mut reader = EvmDataReader::new_skip_selector(&input).unwrap();
+	reader.read::<bool>().unwrap();
+	let err = reader.read::<Referendum>().unwrap_err();
+	assert_eq!(err.to_string(), "arg 1.votes[1].conviction: value is out of range for i8");
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmark_handle {
+	use crate::benchmarking::BenchmarkHandle;
+	use fp_evm::{
+		ExitError, ExitReason, ExitSucceed, IsPrecompileResult, Log, Precompile, PrecompileHandle,
+		PrecompileOutput, PrecompileResult, PrecompileSet,
+	};
+	use sp_core::{H160, U256};
+
+	const PRECOMPILE: H160 = H160::repeat_byte(0x01);
+	const CALLER: H160 = H160::repeat_byte(0x02);
+
+	/// Precompile paying 10 gas to log and return its input.
+	struct Echo;
+
+	impl Precompile for Echo {
+		fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+			handle.record_cost(10)?;
+			let input = handle.input().to_vec();
+			handle.log(handle.code_address(), vec![], input.clone())?;
+			Ok(PrecompileOutput { exit_status: ExitSucceed::Returned, output: input })
+		}
+	}
+
+	struct EchoSet;
+
+	impl PrecompileSet for EchoSet {
+		fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+			(handle.code_address() == PRECOMPILE).then(|| Echo::execute(handle))
+		}
+
+		fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
+			IsPrecompileResult::Answer { is_precompile: address == PRECOMPILE, extra_cost: 0 }
+		}
+	}
+
+	#[test]
+	fn new_calls_the_precompile_with_no_gas_limit() {
+		let handle = BenchmarkHandle::new(PRECOMPILE, CALLER, vec![1, 2, 3]);
+
+		assert_eq!(handle.code_address(), PRECOMPILE);
+		assert_eq!(handle.input(), &[1, 2, 3]);
+		assert_eq!(handle.context().address, PRECOMPILE);
+		assert_eq!(handle.context().caller, CALLER);
+		assert_eq!(handle.context().apparent_value, U256::zero());
+		assert!(!handle.is_static());
+		assert_eq!(handle.gas_limit(), Some(u64::MAX));
+		assert_eq!(handle.remaining_gas(), u64::MAX);
+	}
+
+	#[test]
+	fn execute_keeps_the_cost_and_logs_of_the_precompile() {
+		let mut handle = BenchmarkHandle::new(PRECOMPILE, CALLER, vec![1, 2, 3]);
+
+		let output = handle.execute::<Echo>().expect("echo succeeds").output;
+
+		assert_eq!(output, vec![1, 2, 3]);
+		assert_eq!(handle.gas_used, 10);
+		assert_eq!(
+			handle.logs,
+			vec![Log { address: PRECOMPILE, topics: vec![], data: vec![1, 2, 3] }]
+		);
+	}
+
+	#[test]
+	fn execute_in_runs_the_precompile_at_the_code_address() {
+		let mut handle = BenchmarkHandle::new(PRECOMPILE, CALLER, vec![4]);
+
+		let output = handle.execute_in(&EchoSet).expect("echo succeeds").output;
+
+		assert_eq!(output, vec![4]);
+		assert_eq!(handle.gas_used, 10);
+	}
+
+	#[test]
+	#[should_panic(expected = "no precompile at the code address")]
+	fn execute_in_panics_without_a_precompile_at_the_code_address() {
+		let mut handle = BenchmarkHandle::new(CALLER, CALLER, vec![]);
+
+		let _ = handle.execute_in(&EchoSet);
+	}
+
+	#[test]
+	fn record_cost_fails_over_the_gas_limit() {
+		let mut handle = BenchmarkHandle::new(PRECOMPILE, CALLER, vec![]);
+		handle.gas_limit = 100;
+
+		assert_eq!(handle.record_cost(60), Ok(()));
+		assert_eq!(handle.remaining_gas(), 40);
+		assert_eq!(handle.record_cost(40), Ok(()));
+		assert_eq!(handle.remaining_gas(), 0);
+		assert_eq!(handle.record_cost(1), Err(ExitError::OutOfGas));
+		assert_eq!(handle.remaining_gas(), 0);
+	}
+
+	#[test]
+	fn external_costs_are_recorded_and_refunded() {
+		let mut handle = BenchmarkHandle::new(PRECOMPILE, CALLER, vec![]);
+
+		assert_eq!(handle.record_external_cost(Some(10), Some(20)), Ok(()));
+		assert_eq!(handle.record_external_cost(Some(5), None), Ok(()));
+		handle.refund_external_cost(None, Some(15));
+		assert_eq!((handle.ref_time, handle.proof_size), (15, 5));
+
+		handle.refund_external_cost(Some(20), Some(20));
+		assert_eq!((handle.ref_time, handle.proof_size), (0, 0));
+		assert_eq!(handle.gas_used, 0);
+	}
+
+	#[test]
+	fn subcalls_are_counted_and_succeed() {
+		let mut handle = BenchmarkHandle::new(PRECOMPILE, CALLER, vec![]);
+		let context = handle.context().clone();
+
+		for _ in 0..2 {
+			let (reason, output) =
+				handle.call(CALLER, None, vec![0xff], Some(1_000), false, &context);
+			assert_eq!(reason, ExitReason::Succeed(ExitSucceed::Returned));
+			assert!(output.is_empty());
+		}
+
+		assert_eq!(handle.subcalls, 2);
+		assert_eq!(handle.gas_used, 0);
+	}
+}
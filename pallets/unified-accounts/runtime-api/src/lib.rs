@@ -0,0 +1,39 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API of the unified accounts pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::Codec;
+use sp_core::H160;
+
+sp_api::decl_runtime_apis! {
+	/// Lets wallets find the account an EVM address maps to, and the other way round.
+	pub trait UnifiedAccountsApi<AccountId: Codec> {
+		/// EVM address of `account`: the claimed one, or the default one if none was claimed.
+		fn evm_address(account: AccountId) -> H160;
+
+		/// Native account of `address`: the claiming one, or the default one if none claimed it.
+		fn native_account(address: H160) -> AccountId;
+
+		/// EVM address claimed by `account`, if any.
+		fn claimed_evm_address(account: AccountId) -> Option<H160>;
+
+		/// Native account which claimed `address`, if any.
+		fn claimed_native_account(address: H160) -> Option<AccountId>;
+	}
+}
@@ -0,0 +1,65 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Benchmarks of claiming an EVM address.
+
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::fungible::{Inspect, Mutate};
+use frame_system::RawOrigin;
+use sp_core::testing::ECDSA;
+use sp_io::crypto::{ecdsa_generate, ecdsa_sign_prehashed};
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn claim_evm_address() {
+		let caller: T::AccountId = whitelisted_caller();
+		let public = ecdsa_generate(ECDSA, None);
+		let digest = Pallet::<T>::claim_digest(&caller);
+		let mut signature: EvmSignature = [0u8; 65];
+		signature.copy_from_slice(
+			ecdsa_sign_prehashed(ECDSA, &public, &digest)
+				.expect("key is in the keystore")
+				.as_ref(),
+		);
+		let evm_address =
+			Pallet::<T>::recover_signer(&caller, &signature).expect("signature is well formed");
+
+		// Worst case: funds were sent to the EVM address before the claim, and transactions from
+		// it raised the nonce of its default account.
+		let default_account = T::DefaultEvmToNative::into_account_id(evm_address);
+		let leftover = T::Currency::minimum_balance() * 100u32.into();
+		T::Currency::set_balance(&default_account, leftover);
+		frame_system::Pallet::<T>::inc_account_nonce(&default_account);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), evm_address, signature);
+
+		assert_eq!(EvmToNative::<T>::get(evm_address), Some(caller.clone()));
+		assert_eq!(T::Currency::balance(&caller), leftover);
+		assert_eq!(frame_system::Pallet::<T>::account_nonce(&caller), 1u32.into());
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::ExtBuilder::default().build(),
+		crate::mock::Runtime
+	);
+}
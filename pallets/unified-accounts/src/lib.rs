@@ -0,0 +1,255 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Unified Accounts Pallet
+//!
+//! Lets a native account claim an EVM address by proving ownership of its private key with an
+//! EIP-712 signature. Once claimed, the EVM address and the native account map onto each other
+//! in both directions, so balances and precompile calls from either side reach the same account.
+//!
+//! Addresses that were never claimed keep resolving through the configured default mappings.
+//! The pallet implements [`AddressMapping`] so it can be plugged in as
+//! `pallet_evm::Config::AddressMapping` directly.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::traits::Get;
+use pallet_evm::AddressMapping;
+use parity_scale_codec::Encode;
+use sp_core::{Hasher, H160, H256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::{Convert, Zero};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// EIP-712 signature sent along with a claim.
+pub type EvmSignature = [u8; 65];
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			fungible::{Inspect, Mutate},
+			tokens::{Fortitude, Preservation},
+		},
+	};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency trait, used to move the funds left on the default account of a claimed
+		/// EVM address.
+		type Currency: Mutate<Self::AccountId>;
+
+		/// Mapping of EVM addresses which were not claimed.
+		type DefaultEvmToNative: AddressMapping<Self::AccountId>;
+
+		/// Mapping of native accounts which did not claim an EVM address.
+		type DefaultNativeToEvm: Convert<Self::AccountId, H160>;
+
+		/// EVM chain id, part of the EIP-712 domain of claim signatures.
+		#[pallet::constant]
+		type ChainId: Get<u64>;
+
+		/// Weight information for the extrinsics of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The native account already claimed an EVM address.
+		AlreadyMapped,
+		/// The EVM address was already claimed by another account.
+		EvmAddressAlreadyMapped,
+		/// Signature was not produced by the key of the claimed EVM address.
+		InvalidSignature,
+		/// Funds of the default account could not be moved to the claiming account.
+		FundsUnavailable,
+		/// The default account of the EVM address holds assets, locks, reserves or code, which
+		/// can't be moved to the claiming account.
+		DefaultAccountInUse,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A native account claimed an EVM address.
+		AccountClaimed { account_id: T::AccountId, evm_address: H160 },
+	}
+
+	/// Native account which claimed a given EVM address.
+	#[pallet::storage]
+	#[pallet::getter(fn native_account_of)]
+	pub type EvmToNative<T: Config> = StorageMap<_, Blake2_128Concat, H160, T::AccountId>;
+
+	/// EVM address claimed by a given native account.
+	#[pallet::storage]
+	#[pallet::getter(fn evm_address_of)]
+	pub type NativeToEvm<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, H160>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Claim `evm_address` for the signed origin.
+		///
+		/// `signature` is an EIP-712 signature over the [`Pallet::claim_digest`] of the origin,
+		/// made with the key of `evm_address`. The balance of the default native account of
+		/// `evm_address` is moved to the origin, whose nonce is raised to the nonce of the default
+		/// account. Fails if the default account holds anything but a balance.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::claim_evm_address())]
+		pub fn claim_evm_address(
+			origin: OriginFor<T>,
+			evm_address: H160,
+			signature: EvmSignature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!NativeToEvm::<T>::contains_key(&who), Error::<T>::AlreadyMapped);
+			ensure!(
+				!EvmToNative::<T>::contains_key(evm_address),
+				Error::<T>::EvmAddressAlreadyMapped
+			);
+			ensure!(
+				Self::recover_signer(&who, &signature) == Some(evm_address),
+				Error::<T>::InvalidSignature
+			);
+
+			// Funds sent to the EVM address before the claim would be out of reach afterwards.
+			// Only the balance can be moved, so the claim is refused while anything else refers
+			// to the default account: assets, locks, reserves or contract code.
+			let default_account = T::DefaultEvmToNative::into_account_id(evm_address);
+			ensure!(
+				frame_system::Pallet::<T>::consumers(&default_account).is_zero() &&
+					frame_system::Pallet::<T>::sufficients(&default_account).is_zero(),
+				Error::<T>::DefaultAccountInUse
+			);
+
+			// The EVM nonce of `evm_address` becomes the nonce of `who`, it must not go back or
+			// transactions already signed by the key of `evm_address` could be replayed.
+			let default_nonce = frame_system::Pallet::<T>::account_nonce(&default_account);
+			if frame_system::Pallet::<T>::account_nonce(&who) < default_nonce {
+				frame_system::Account::<T>::mutate(&who, |account| account.nonce = default_nonce);
+			}
+
+			let leftover = T::Currency::reducible_balance(
+				&default_account,
+				Preservation::Expendable,
+				Fortitude::Polite,
+			);
+			if !leftover.is_zero() {
+				T::Currency::transfer(&default_account, &who, leftover, Preservation::Expendable)
+					.map_err(|_| Error::<T>::FundsUnavailable)?;
+			}
+
+			EvmToNative::<T>::insert(evm_address, &who);
+			NativeToEvm::<T>::insert(&who, evm_address);
+
+			Self::deposit_event(Event::AccountClaimed { account_id: who, evm_address });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// EIP-712 domain separator of claim signatures.
+		///
+		/// The genesis hash is used as salt so a signature can't be replayed on another chain
+		/// sharing the same chain id.
+		pub fn domain_separator() -> [u8; 32] {
+			let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+
+			let mut encoded = Vec::with_capacity(5 * 32);
+			encoded.extend_from_slice(&keccak_256(
+				b"EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)",
+			));
+			encoded.extend_from_slice(&keccak_256(b"Unified Accounts"));
+			encoded.extend_from_slice(&keccak_256(b"1"));
+			let mut chain_id = [0u8; 32];
+			sp_core::U256::from(T::ChainId::get()).to_big_endian(&mut chain_id);
+			encoded.extend_from_slice(&chain_id);
+			encoded.extend_from_slice(genesis_hash.as_ref());
+			keccak_256(&encoded)
+		}
+
+		/// EIP-712 digest to sign to claim an EVM address for `account`.
+		pub fn claim_digest(account: &T::AccountId) -> [u8; 32] {
+			let mut claim = Vec::with_capacity(2 * 32);
+			claim.extend_from_slice(&keccak_256(b"Claim(bytes substrateAddress)"));
+			claim.extend_from_slice(&keccak_256(&account.encode()));
+
+			let mut digest = Vec::with_capacity(2 + 2 * 32);
+			digest.extend_from_slice(b"\x19\x01");
+			digest.extend_from_slice(&Self::domain_separator());
+			digest.extend_from_slice(&keccak_256(&claim));
+			keccak_256(&digest)
+		}
+
+		/// EVM address which signed the claim of `account`, if the signature is well formed.
+		pub fn recover_signer(account: &T::AccountId, signature: &EvmSignature) -> Option<H160> {
+			let pubkey =
+				sp_io::crypto::secp256k1_ecdsa_recover(signature, &Self::claim_digest(account))
+					.ok()?;
+			Some(H160::from(H256::from(keccak_256(&pubkey))))
+		}
+
+		/// EVM address of `account`, claimed or default.
+		pub fn evm_address(account: &T::AccountId) -> H160 {
+			NativeToEvm::<T>::get(account)
+				.unwrap_or_else(|| T::DefaultNativeToEvm::convert(account.clone()))
+		}
+
+		/// Native account of `address`, claiming or default.
+		pub fn native_account(address: H160) -> T::AccountId {
+			EvmToNative::<T>::get(address)
+				.unwrap_or_else(|| T::DefaultEvmToNative::into_account_id(address))
+		}
+	}
+}
+
+impl<T: Config> AddressMapping<T::AccountId> for Pallet<T> {
+	fn into_account_id(address: H160) -> T::AccountId {
+		Self::native_account(address)
+	}
+}
+
+/// Default EVM address of a native account: the first 20 bytes of the hash of its encoding.
+pub struct HashedAccountMapping<H>(PhantomData<H>);
+
+impl<AccountId: Encode, H: Hasher<Out = H256>> Convert<AccountId, H160>
+	for HashedAccountMapping<H>
+{
+	fn convert(account: AccountId) -> H160 {
+		H160::from_slice(&H::hash(&account.encode())[0..20])
+	}
+}
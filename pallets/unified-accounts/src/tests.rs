@@ -0,0 +1,248 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Currency, LockableCurrency, WithdrawReasons},
+};
+use hex_literal::hex;
+use sp_runtime::traits::BlakeTwo256;
+
+/// Address of the well known development key `Alith`.
+const ALITH: H160 = H160(hex!("f24ff3a9cf04c71dbc94d0b566f7a27b94566cac"));
+
+/// `Alith` signature over the claim digest of `ALICE`.
+const ALITH_CLAIM_ALICE: EvmSignature = hex!(
+    "c879ec89922ce2bc0ca1a501ca362383bce81a755a5e2257b80730770607346819b3acace10136994c7a8031e43d80a5a045fa377d92a25cf962b7f54c29dba31b"
+);
+
+#[test]
+fn claim_digest_matches_eip712() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(
+            UnifiedAccounts::claim_digest(&ALICE),
+            hex!("07aa26918ffcb0ee6592e5b8486eea018640dd0754025ec6ae0b729745324751")
+        );
+        assert_eq!(
+            UnifiedAccounts::recover_signer(&ALICE, &ALITH_CLAIM_ALICE),
+            Some(ALITH)
+        );
+    });
+}
+
+#[test]
+fn claim_stores_bidirectional_mapping() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(UnifiedAccounts::claim_evm_address(
+            RuntimeOrigin::signed(ALICE),
+            ALITH,
+            ALITH_CLAIM_ALICE
+        ));
+
+        assert_eq!(UnifiedAccounts::native_account_of(ALITH), Some(ALICE));
+        assert_eq!(UnifiedAccounts::evm_address_of(ALICE), Some(ALITH));
+        assert_eq!(UnifiedAccounts::evm_address(&ALICE), ALITH);
+        assert_eq!(
+            <UnifiedAccounts as AddressMapping<AccountId>>::into_account_id(ALITH),
+            ALICE
+        );
+
+        System::assert_last_event(RuntimeEvent::UnifiedAccounts(Event::AccountClaimed {
+            account_id: ALICE,
+            evm_address: ALITH,
+        }));
+    });
+}
+
+#[test]
+fn unclaimed_accounts_use_default_mappings() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(
+            <UnifiedAccounts as AddressMapping<AccountId>>::into_account_id(ALITH),
+            pallet_evm::HashedAddressMapping::<BlakeTwo256>::into_account_id(ALITH)
+        );
+        assert_eq!(
+            UnifiedAccounts::evm_address(&ALICE),
+            HashedAccountMapping::<BlakeTwo256>::convert(ALICE)
+        );
+        assert_eq!(UnifiedAccounts::native_account_of(ALITH), None);
+        assert_eq!(UnifiedAccounts::evm_address_of(ALICE), None);
+    });
+}
+
+#[test]
+fn claim_moves_funds_of_default_account() {
+    let default_account = pallet_evm::HashedAddressMapping::<BlakeTwo256>::into_account_id(ALITH);
+
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, 1_000), (default_account.clone(), 500)])
+        .build()
+        .execute_with(|| {
+            assert_ok!(UnifiedAccounts::claim_evm_address(
+                RuntimeOrigin::signed(ALICE),
+                ALITH,
+                ALITH_CLAIM_ALICE
+            ));
+
+            assert_eq!(Balances::free_balance(&ALICE), 1_500);
+            assert_eq!(Balances::free_balance(&default_account), 0);
+        });
+}
+
+#[test]
+fn claim_fails_if_default_account_has_locked_funds() {
+    let default_account = pallet_evm::HashedAddressMapping::<BlakeTwo256>::into_account_id(ALITH);
+
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, 1_000), (default_account.clone(), 500)])
+        .build()
+        .execute_with(|| {
+            Balances::set_lock(*b"locked__", &default_account, 200, WithdrawReasons::all());
+
+            assert_noop!(
+                UnifiedAccounts::claim_evm_address(
+                    RuntimeOrigin::signed(ALICE),
+                    ALITH,
+                    ALITH_CLAIM_ALICE
+                ),
+                Error::<Runtime>::DefaultAccountInUse
+            );
+        });
+}
+
+#[test]
+fn claim_fails_if_default_account_holds_assets() {
+    let default_account = pallet_evm::HashedAddressMapping::<BlakeTwo256>::into_account_id(ALITH);
+
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, 1_000)])
+        .build()
+        .execute_with(|| {
+            // What `pallet_assets` does for a holder of a sufficient asset.
+            System::inc_sufficients(&default_account);
+
+            assert_noop!(
+                UnifiedAccounts::claim_evm_address(
+                    RuntimeOrigin::signed(ALICE),
+                    ALITH,
+                    ALITH_CLAIM_ALICE
+                ),
+                Error::<Runtime>::DefaultAccountInUse
+            );
+        });
+}
+
+#[test]
+fn claim_keeps_evm_transactions_from_being_replayed() {
+    let default_account = pallet_evm::HashedAddressMapping::<BlakeTwo256>::into_account_id(ALITH);
+
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, 1_000), (default_account.clone(), 500)])
+        .build()
+        .execute_with(|| {
+            // `ALITH` already sent 3 transactions from the default account.
+            for _ in 0..3 {
+                System::inc_account_nonce(&default_account);
+            }
+            System::inc_account_nonce(&ALICE);
+
+            assert_ok!(UnifiedAccounts::claim_evm_address(
+                RuntimeOrigin::signed(ALICE),
+                ALITH,
+                ALITH_CLAIM_ALICE
+            ));
+
+            // The EVM nonce of `ALITH` is now the nonce of `ALICE` and didn't go back.
+            let account = <UnifiedAccounts as AddressMapping<AccountId>>::into_account_id(ALITH);
+            assert_eq!(account, ALICE);
+            assert_eq!(System::account_nonce(&account), 3);
+        });
+}
+
+#[test]
+fn claim_keeps_higher_native_nonce() {
+    let default_account = pallet_evm::HashedAddressMapping::<BlakeTwo256>::into_account_id(ALITH);
+
+    ExtBuilder::default()
+        .with_balances(vec![(ALICE, 1_000), (default_account.clone(), 500)])
+        .build()
+        .execute_with(|| {
+            System::inc_account_nonce(&default_account);
+            for _ in 0..5 {
+                System::inc_account_nonce(&ALICE);
+            }
+
+            assert_ok!(UnifiedAccounts::claim_evm_address(
+                RuntimeOrigin::signed(ALICE),
+                ALITH,
+                ALITH_CLAIM_ALICE
+            ));
+
+            assert_eq!(System::account_nonce(&ALICE), 5);
+        });
+}
+
+#[test]
+fn claim_signed_for_another_account_fails() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            UnifiedAccounts::claim_evm_address(RuntimeOrigin::signed(BOB), ALITH, ALITH_CLAIM_ALICE),
+            Error::<Runtime>::InvalidSignature
+        );
+    });
+}
+
+#[test]
+fn malformed_signature_fails() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            UnifiedAccounts::claim_evm_address(RuntimeOrigin::signed(ALICE), ALITH, [0u8; 65]),
+            Error::<Runtime>::InvalidSignature
+        );
+    });
+}
+
+#[test]
+fn account_cannot_claim_twice() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(UnifiedAccounts::claim_evm_address(
+            RuntimeOrigin::signed(ALICE),
+            ALITH,
+            ALITH_CLAIM_ALICE
+        ));
+
+        assert_noop!(
+            UnifiedAccounts::claim_evm_address(RuntimeOrigin::signed(ALICE), ALITH, ALITH_CLAIM_ALICE),
+            Error::<Runtime>::AlreadyMapped
+        );
+    });
+}
+
+#[test]
+fn evm_address_cannot_be_claimed_twice() {
+    ExtBuilder::default().build().execute_with(|| {
+        EvmToNative::<Runtime>::insert(ALITH, BOB);
+        NativeToEvm::<Runtime>::insert(BOB, ALITH);
+
+        assert_noop!(
+            UnifiedAccounts::claim_evm_address(RuntimeOrigin::signed(ALICE), ALITH, ALITH_CLAIM_ALICE),
+            Error::<Runtime>::EvmAddressAlreadyMapped
+        );
+    });
+}
@@ -0,0 +1,174 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tracer building the tree of calls made by a transaction.
+
+use crate::{snapshot, CallTrace, CallType, Tracer, TransactionTrace};
+use core::fmt::Write;
+use evm::{tracing::Event, CreateScheme, ExitError, ExitReason};
+use sp_core::{H160, U256};
+use sp_std::vec::Vec;
+
+/// Builds a [`CallTrace`] out of the calls, creates and self-destructs of a transaction.
+#[derive(Default)]
+pub struct CallTracer {
+	/// Frames being executed, innermost last.
+	stack: Vec<CallTrace>,
+	/// The frame opened by a transact event is entered again by the call or create that
+	/// follows it, which must not open a second frame.
+	entering_transaction: bool,
+	/// Outermost frame, once exited.
+	root: Option<CallTrace>,
+}
+
+impl CallTracer {
+	/// The trace of the transaction, if it reached the EVM.
+	pub fn into_trace(mut self) -> Option<TransactionTrace> {
+		// A transaction failing before its execution starts doesn't exit its frame.
+		while !self.stack.is_empty() {
+			self.exit(None, &[]);
+		}
+		self.root.map(TransactionTrace::Call)
+	}
+
+	fn transact(&mut self, frame: CallTrace) {
+		self.stack.push(frame);
+		self.entering_transaction = true;
+	}
+
+	fn enter(&mut self, frame: CallTrace) {
+		if core::mem::take(&mut self.entering_transaction) {
+			return
+		}
+		self.stack.push(frame);
+	}
+
+	fn exit(&mut self, error: Option<Vec<u8>>, output: &[u8]) {
+		if let Some(mut frame) = self.stack.pop() {
+			frame.output = output.to_vec();
+			frame.error = error;
+			match self.stack.last_mut() {
+				Some(parent) => parent.calls.push(frame),
+				None => self.root = Some(frame),
+			}
+		}
+	}
+}
+
+fn frame(
+	call_type: CallType,
+	from: H160,
+	to: H160,
+	value: U256,
+	gas: u64,
+	input: &[u8],
+) -> CallTrace {
+	CallTrace {
+		call_type,
+		from,
+		to,
+		value,
+		gas: gas.into(),
+		gas_used: U256::zero(),
+		input: input.to_vec(),
+		output: Vec::new(),
+		error: None,
+		calls: Vec::new(),
+	}
+}
+
+/// Error message of a frame, worded as geth does for the most common ones.
+fn error(reason: &ExitReason) -> Option<Vec<u8>> {
+	let mut writer = sp_std::Writer::default();
+	let _ = match reason {
+		ExitReason::Succeed(_) => return None,
+		ExitReason::Revert(_) => return Some(b"execution reverted".to_vec()),
+		ExitReason::Error(ExitError::OutOfGas) => return Some(b"out of gas".to_vec()),
+		ExitReason::Error(error) => write!(writer, "{:?}", error),
+		ExitReason::Fatal(fatal) => write!(writer, "{:?}", fatal),
+	};
+	Some(writer.into_inner())
+}
+
+impl Tracer for CallTracer {
+	fn evm_event(&mut self, event: Event) {
+		match event {
+			Event::TransactCall { caller, address, value, data, gas_limit } =>
+				self.transact(frame(CallType::Call, caller, address, value, gas_limit, data)),
+			Event::TransactCreate { caller, value, init_code, gas_limit, address } =>
+				self.transact(frame(CallType::Create, caller, address, value, gas_limit, init_code)),
+			Event::TransactCreate2 { caller, value, init_code, gas_limit, address, .. } => self
+				.transact(frame(CallType::Create2, caller, address, value, gas_limit, init_code)),
+			Event::Call { code_address, transfer, input, target_gas, is_static, context } => {
+				let call_type = if is_static {
+					CallType::StaticCall
+				} else if context.address == code_address {
+					CallType::Call
+				} else if context.caller == context.address {
+					CallType::CallCode
+				} else {
+					CallType::DelegateCall
+				};
+				let (from, to) = match call_type {
+					CallType::DelegateCall | CallType::CallCode => (context.address, code_address),
+					_ => (context.caller, context.address),
+				};
+				let value = transfer.as_ref().map(|transfer| transfer.value).unwrap_or_default();
+				self.enter(frame(
+					call_type,
+					from,
+					to,
+					value,
+					target_gas.unwrap_or_default(),
+					input,
+				));
+			},
+			Event::Create { caller, address, scheme, value, init_code, target_gas } => {
+				let call_type = match scheme {
+					CreateScheme::Create2 { .. } => CallType::Create2,
+					_ => CallType::Create,
+				};
+				self.enter(frame(
+					call_type,
+					caller,
+					address,
+					value,
+					target_gas.unwrap_or_default(),
+					init_code,
+				));
+			},
+			Event::Suicide { address, target, balance } =>
+				if let Some(current) = self.stack.last_mut() {
+					current.calls.push(frame(
+						CallType::SelfDestruct,
+						address,
+						target,
+						balance,
+						0,
+						&[],
+					));
+				},
+			Event::Exit { reason, return_value } => self.exit(error(reason), return_value),
+			_ => {},
+		}
+	}
+
+	fn gasometer_event(&mut self, event: evm_gasometer::tracing::Event) {
+		if let (Some(snapshot), Some(current)) = (snapshot(event), self.stack.last_mut()) {
+			current.gas_used = snapshot.used_gas.saturating_add(snapshot.memory_gas).into();
+		}
+	}
+}
@@ -0,0 +1,258 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! EVM transaction tracing.
+//!
+//! The tracers in this crate listen to the events emitted by the EVM while a transaction is
+//! executed and build the traces served by the `debug_*` and `trace_*` RPC methods:
+//!
+//! - [`CallTracer`]: tree of the calls made by a transaction, as geth's `callTracer`.
+//! - [`PrestateTracer`]: accounts and storage slots touched by a transaction, as geth's
+//!   `prestateTracer`. The state itself is read by the runtime, see [`Touched`].
+//! - [`RawTracer`]: one entry per executed opcode, as geth's default struct logger.
+//!
+//! The runtime exposes them through [`EvmTracingApi`], which replays a block on top of the
+//! state of its parent.
+//!
+//! The EVM only emits the events with the `evm-tracing` feature, which also builds the tracers.
+//! The runtime deployed on chain is built without it; tracing nodes run a runtime built with it
+//! instead. Without it the types and the runtime API are still available, for the RPC.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::Serialize;
+use sp_core::{H160, H256, U256};
+use sp_runtime::DispatchError;
+#[cfg(feature = "evm-tracing")]
+use sp_std::{cell::RefCell, rc::Rc};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+#[cfg(feature = "evm-tracing")]
+mod call;
+#[cfg(feature = "evm-tracing")]
+mod opcodes;
+#[cfg(feature = "evm-tracing")]
+mod prestate;
+#[cfg(feature = "evm-tracing")]
+mod raw;
+#[cfg(feature = "std")]
+mod serialization;
+#[cfg(all(test, feature = "evm-tracing"))]
+mod tests;
+
+#[cfg(feature = "evm-tracing")]
+pub use call::CallTracer;
+#[cfg(feature = "evm-tracing")]
+pub use prestate::{PrestateTracer, Touched};
+#[cfg(feature = "evm-tracing")]
+pub use raw::RawTracer;
+
+/// Tracer to replay a transaction with.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, sp_core::RuntimeDebug)]
+pub enum TraceType {
+	/// Tree of calls, see [`CallTracer`].
+	Call,
+	/// State touched by the transaction, see [`PrestateTracer`].
+	Prestate,
+	/// Executed opcodes, see [`RawTracer`].
+	Raw { disable_storage: bool, disable_memory: bool, disable_stack: bool },
+}
+
+/// Kind of a frame of a [`CallTrace`].
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, sp_core::RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize), serde(rename_all = "UPPERCASE"))]
+pub enum CallType {
+	Call,
+	StaticCall,
+	DelegateCall,
+	CallCode,
+	Create,
+	Create2,
+	SelfDestruct,
+}
+
+/// A call and the calls it made, in the format of geth's `callTracer`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, sp_core::RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize), serde(rename_all = "camelCase"))]
+pub struct CallTrace {
+	#[cfg_attr(feature = "std", serde(rename = "type"))]
+	pub call_type: CallType,
+	pub from: H160,
+	pub to: H160,
+	pub value: U256,
+	pub gas: U256,
+	pub gas_used: U256,
+	#[cfg_attr(feature = "std", serde(serialize_with = "sp_core::bytes::serialize"))]
+	pub input: Vec<u8>,
+	#[cfg_attr(feature = "std", serde(serialize_with = "sp_core::bytes::serialize"))]
+	pub output: Vec<u8>,
+	#[cfg_attr(
+		feature = "std",
+		serde(
+			skip_serializing_if = "Option::is_none",
+			serialize_with = "serialization::option_string"
+		)
+	)]
+	pub error: Option<Vec<u8>>,
+	#[cfg_attr(feature = "std", serde(skip_serializing_if = "Vec::is_empty"))]
+	pub calls: Vec<CallTrace>,
+}
+
+/// State of an account before a transaction, in the format of geth's `prestateTracer`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, sp_core::RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub struct PrestateAccount {
+	pub balance: U256,
+	pub nonce: U256,
+	#[cfg_attr(feature = "std", serde(serialize_with = "sp_core::bytes::serialize"))]
+	pub code: Vec<u8>,
+	pub storage: BTreeMap<H256, H256>,
+}
+
+/// An executed opcode, in the format of geth's struct logger.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, sp_core::RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize), serde(rename_all = "camelCase"))]
+pub struct StructLog {
+	pub depth: u32,
+	pub gas: u64,
+	pub gas_cost: u64,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialization::string"))]
+	pub op: Vec<u8>,
+	pub pc: u64,
+	#[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+	pub stack: Option<Vec<H256>>,
+	#[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+	pub memory: Option<Vec<H256>>,
+	#[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// Opcode level trace of a transaction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, sp_core::RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize), serde(rename_all = "camelCase"))]
+pub struct RawTrace {
+	pub gas: u64,
+	pub failed: bool,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialization::hex_without_prefix"))]
+	pub return_value: Vec<u8>,
+	pub struct_logs: Vec<StructLog>,
+}
+
+/// Trace of a transaction, depending on the [`TraceType`] it was replayed with.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, sp_core::RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize), serde(untagged))]
+pub enum TransactionTrace {
+	Call(CallTrace),
+	Prestate(BTreeMap<H160, PrestateAccount>),
+	Raw(RawTrace),
+}
+
+/// Receives the events of the EVM while a transaction is executed.
+#[cfg(feature = "evm-tracing")]
+pub trait Tracer {
+	fn evm_event(&mut self, _event: evm::tracing::Event) {}
+	fn runtime_event(&mut self, _event: evm_runtime::tracing::Event) {}
+	fn gasometer_event(&mut self, _event: evm_gasometer::tracing::Event) {}
+}
+
+/// Gas state after a gasometer event, if the gasometer still has gas left.
+#[cfg(feature = "evm-tracing")]
+pub(crate) fn snapshot(
+	event: evm_gasometer::tracing::Event,
+) -> Option<evm_gasometer::tracing::Snapshot> {
+	use evm_gasometer::tracing::Event;
+
+	match event {
+		Event::RecordCost { snapshot, .. } |
+		Event::RecordRefund { snapshot, .. } |
+		Event::RecordStipend { snapshot, .. } |
+		Event::RecordDynamicCost { snapshot, .. } |
+		Event::RecordTransaction { snapshot, .. } => snapshot,
+	}
+}
+
+/// Forwards the events of the three EVM layers to a single [`Tracer`].
+#[cfg(feature = "evm-tracing")]
+struct Proxy<T>(Rc<RefCell<T>>);
+
+#[cfg(feature = "evm-tracing")]
+impl<T: Tracer> evm::tracing::EventListener for Proxy<T> {
+	fn event(&mut self, event: evm::tracing::Event) {
+		self.0.borrow_mut().evm_event(event);
+	}
+}
+
+#[cfg(feature = "evm-tracing")]
+impl<T: Tracer> evm_runtime::tracing::EventListener for Proxy<T> {
+	fn event(&mut self, event: evm_runtime::tracing::Event) {
+		self.0.borrow_mut().runtime_event(event);
+	}
+}
+
+#[cfg(feature = "evm-tracing")]
+impl<T: Tracer> evm_gasometer::tracing::EventListener for Proxy<T> {
+	fn event(&mut self, event: evm_gasometer::tracing::Event) {
+		self.0.borrow_mut().gasometer_event(event);
+	}
+}
+
+/// Execute `f` with `tracer` receiving the EVM events, and give the tracer back.
+#[cfg(feature = "evm-tracing")]
+pub fn using<T: Tracer + 'static, R>(tracer: T, f: impl FnOnce() -> R) -> (T, R) {
+	let tracer = Rc::new(RefCell::new(tracer));
+	let mut evm_proxy = Proxy(tracer.clone());
+	let mut runtime_proxy = Proxy(tracer.clone());
+	let mut gasometer_proxy = Proxy(tracer.clone());
+
+	let result = evm::tracing::using(&mut evm_proxy, || {
+		evm_runtime::tracing::using(&mut runtime_proxy, || {
+			evm_gasometer::tracing::using(&mut gasometer_proxy, f)
+		})
+	});
+
+	drop((evm_proxy, runtime_proxy, gasometer_proxy));
+	let tracer = Rc::try_unwrap(tracer)
+		.map(RefCell::into_inner)
+		.unwrap_or_else(|_| unreachable!("All the proxies were dropped; qed"));
+	(tracer, result)
+}
+
+sp_api::decl_runtime_apis! {
+	/// Replays blocks with an EVM tracer.
+	///
+	/// Must be called on the parent of the block whose `header` and `extrinsics` are given.
+	/// Every extrinsic is applied so later Ethereum transactions run on the right state, but
+	/// only Ethereum transactions are traced. A runtime built without the `evm-tracing` feature
+	/// returns an error.
+	pub trait EvmTracingApi {
+		/// Trace the Ethereum transaction `transaction_hash`, if it is part of the block.
+		fn trace_transaction(
+			header: &Block::Header,
+			extrinsics: Vec<Block::Extrinsic>,
+			transaction_hash: H256,
+			trace_type: TraceType,
+		) -> Result<Option<TransactionTrace>, DispatchError>;
+
+		/// Trace all the Ethereum transactions of the block, in order.
+		fn trace_block(
+			header: &Block::Header,
+			extrinsics: Vec<Block::Extrinsic>,
+			trace_type: TraceType,
+		) -> Result<Vec<(H256, TransactionTrace)>, DispatchError>;
+	}
+}
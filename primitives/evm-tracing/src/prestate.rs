@@ -0,0 +1,76 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tracer collecting the state touched by a transaction.
+
+use crate::Tracer;
+use evm::tracing::Event;
+use sp_core::{H160, H256};
+use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
+
+/// Accounts touched by a transaction, with the storage slots it read or wrote.
+pub type Touched = BTreeMap<H160, BTreeSet<H256>>;
+
+/// Collects the accounts and storage slots touched by a transaction.
+///
+/// The EVM only reports the state as it changes, so the runtime executes the transaction once
+/// with this tracer, reverts it, and reads the [`Touched`] state before applying it for good.
+#[derive(Default)]
+pub struct PrestateTracer {
+	touched: Touched,
+}
+
+impl PrestateTracer {
+	pub fn into_touched(self) -> Touched {
+		self.touched
+	}
+
+	fn touch(&mut self, address: H160) {
+		self.touched.entry(address).or_default();
+	}
+}
+
+impl Tracer for PrestateTracer {
+	fn evm_event(&mut self, event: Event) {
+		match event {
+			Event::TransactCall { caller, address, .. } |
+			Event::TransactCreate { caller, address, .. } |
+			Event::TransactCreate2 { caller, address, .. } |
+			Event::Create { caller, address, .. } => {
+				self.touch(caller);
+				self.touch(address);
+			},
+			Event::Call { code_address, context, .. } => {
+				self.touch(context.caller);
+				self.touch(context.address);
+				self.touch(code_address);
+			},
+			Event::Suicide { address, target, .. } => {
+				self.touch(address);
+				self.touch(target);
+			},
+			_ => {},
+		}
+	}
+
+	fn runtime_event(&mut self, event: evm_runtime::tracing::Event) {
+		use evm_runtime::tracing::Event;
+
+		if let Event::SLoad { address, index, .. } | Event::SStore { address, index, .. } = event {
+			self.touched.entry(address).or_default().insert(index);
+		}
+	}
+}
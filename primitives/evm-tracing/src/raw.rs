@@ -0,0 +1,163 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tracer logging every opcode executed by a transaction.
+
+use crate::{opcodes, snapshot, RawTrace, StructLog, Tracer, TransactionTrace};
+use evm::tracing::Event;
+use sp_core::{H160, H256};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// Builds a [`RawTrace`] with one [`StructLog`] per executed opcode.
+pub struct RawTracer {
+	disable_storage: bool,
+	disable_memory: bool,
+	disable_stack: bool,
+	/// Depth of the frame being executed, the outermost one being `1`.
+	depth: u32,
+	/// Gas left in the frame being executed.
+	gas_left: u64,
+	/// Gas left in the frames waiting for a subcall to return.
+	suspended_gas_left: Vec<u64>,
+	/// Gas used by the outermost frame.
+	gas_used: u64,
+	/// Whether the last log still accounts the cost of its opcode.
+	step_open: bool,
+	/// Storage slots read or written so far, per contract.
+	storage: BTreeMap<H160, BTreeMap<H256, H256>>,
+	failed: bool,
+	return_value: Vec<u8>,
+	struct_logs: Vec<StructLog>,
+}
+
+impl RawTracer {
+	pub fn new(disable_storage: bool, disable_memory: bool, disable_stack: bool) -> Self {
+		Self {
+			disable_storage,
+			disable_memory,
+			disable_stack,
+			depth: 0,
+			gas_left: 0,
+			suspended_gas_left: Vec::new(),
+			gas_used: 0,
+			step_open: false,
+			storage: BTreeMap::new(),
+			failed: false,
+			return_value: Vec::new(),
+			struct_logs: Vec::new(),
+		}
+	}
+
+	pub fn into_trace(self) -> TransactionTrace {
+		TransactionTrace::Raw(RawTrace {
+			gas: self.gas_used,
+			failed: self.failed,
+			return_value: self.return_value,
+			struct_logs: self.struct_logs,
+		})
+	}
+
+	fn enter(&mut self, target_gas: Option<u64>) {
+		self.depth += 1;
+		self.suspended_gas_left.push(self.gas_left);
+		if let Some(target_gas) = target_gas {
+			self.gas_left = target_gas;
+		}
+		self.step_open = false;
+	}
+
+	fn exit(&mut self) {
+		self.depth = self.depth.saturating_sub(1);
+		self.gas_left = self.suspended_gas_left.pop().unwrap_or_default();
+		self.step_open = false;
+	}
+}
+
+impl Tracer for RawTracer {
+	fn evm_event(&mut self, event: Event) {
+		match event {
+			Event::Call { target_gas, .. } | Event::Create { target_gas, .. } =>
+				self.enter(target_gas),
+			Event::Exit { reason, return_value } => {
+				if self.depth == 1 {
+					self.failed = !reason.is_succeed();
+					self.return_value = return_value.to_vec();
+				}
+				self.exit();
+			},
+			_ => {},
+		}
+	}
+
+	fn runtime_event(&mut self, event: evm_runtime::tracing::Event) {
+		use evm_runtime::tracing::Event;
+
+		match event {
+			Event::Step { context: _, opcode, position, stack, memory } => {
+				let stack = (!self.disable_stack).then(|| stack.data().clone());
+				let memory = (!self.disable_memory).then(|| {
+					memory
+						.data()
+						.chunks(32)
+						.map(|chunk| {
+							let mut word = H256::zero();
+							word[..chunk.len()].copy_from_slice(chunk);
+							word
+						})
+						.collect()
+				});
+
+				self.struct_logs.push(StructLog {
+					depth: self.depth,
+					gas: self.gas_left,
+					gas_cost: 0,
+					op: opcodes::name(opcode.0).as_bytes().to_vec(),
+					pc: position.as_ref().map(|pc| *pc as u64).unwrap_or_default(),
+					stack,
+					memory,
+					storage: None,
+				});
+				self.step_open = true;
+			},
+			Event::SLoad { address, index, value } | Event::SStore { address, index, value } => {
+				let storage = self.storage.entry(address).or_default();
+				storage.insert(index, value);
+				if !self.disable_storage {
+					if let Some(log) = self.struct_logs.last_mut() {
+						log.storage = Some(storage.clone());
+					}
+				}
+			},
+			_ => {},
+		}
+	}
+
+	fn gasometer_event(&mut self, event: evm_gasometer::tracing::Event) {
+		if let Some(snapshot) = snapshot(event) {
+			let used = snapshot.used_gas.saturating_add(snapshot.memory_gas);
+			let gas_left = snapshot.gas_limit.saturating_sub(used);
+			if self.step_open {
+				if let Some(log) = self.struct_logs.last_mut() {
+					log.gas_cost = log.gas.saturating_sub(gas_left);
+				}
+			}
+			if self.depth <= 1 {
+				self.gas_used = used;
+			}
+			self.gas_left = gas_left;
+		}
+	}
+}
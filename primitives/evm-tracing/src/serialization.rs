@@ -0,0 +1,37 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Serialization of the traces into the JSON expected by Ethereum tooling.
+
+use serde::Serializer;
+
+pub fn string<S: Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&String::from_utf8_lossy(value))
+}
+
+pub fn option_string<S: Serializer>(
+	value: &Option<Vec<u8>>,
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	match value {
+		Some(value) => string(value, serializer),
+		None => serializer.serialize_none(),
+	}
+}
+
+pub fn hex_without_prefix<S: Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&value.iter().map(|byte| format!("{byte:02x}")).collect::<String>())
+}
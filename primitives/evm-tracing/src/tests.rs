@@ -0,0 +1,181 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use evm::{tracing::Event, Context, ExitReason, ExitRevert, ExitSucceed, Transfer};
+use serde_json::json;
+
+const ALICE: H160 = H160::repeat_byte(0xAA);
+const BOB: H160 = H160::repeat_byte(0xBB);
+const CHARLIE: H160 = H160::repeat_byte(0xCC);
+
+fn call(tracer: &mut CallTracer, context: &Context, code_address: H160, target_gas: u64) {
+    tracer.evm_event(Event::Call {
+        code_address,
+        transfer: &None,
+        input: &[1, 2],
+        target_gas: Some(target_gas),
+        is_static: false,
+        context,
+    });
+}
+
+#[test]
+fn opcode_names() {
+    assert_eq!(opcodes::name(0x00), "STOP");
+    assert_eq!(opcodes::name(0x60), "PUSH1");
+    assert_eq!(opcodes::name(0x7f), "PUSH32");
+    assert_eq!(opcodes::name(0x8f), "DUP16");
+    assert_eq!(opcodes::name(0x90), "SWAP1");
+    assert_eq!(opcodes::name(0xa4), "LOG4");
+    assert_eq!(opcodes::name(0xf4), "DELEGATECALL");
+    assert_eq!(opcodes::name(0xef), "UNKNOWN");
+}
+
+#[test]
+fn call_tracer_builds_call_tree() {
+    let mut tracer = CallTracer::default();
+    let transfer = Some(Transfer { source: ALICE, target: BOB, value: 5.into() });
+
+    tracer.evm_event(Event::TransactCall {
+        caller: ALICE,
+        address: BOB,
+        value: 5.into(),
+        data: &[1, 2],
+        gas_limit: 100_000,
+    });
+    // The call entering the transaction doesn't open a second frame.
+    tracer.evm_event(Event::Call {
+        code_address: BOB,
+        transfer: &transfer,
+        input: &[1, 2],
+        target_gas: None,
+        is_static: false,
+        context: &Context { address: BOB, caller: ALICE, apparent_value: 5.into() },
+    });
+    // BOB delegates to CHARLIE, which reverts.
+    call(
+        &mut tracer,
+        &Context { address: BOB, caller: ALICE, apparent_value: 5.into() },
+        CHARLIE,
+        50_000,
+    );
+    tracer.evm_event(Event::Exit {
+        reason: &ExitReason::Revert(ExitRevert::Reverted),
+        return_value: &[],
+    });
+    tracer.evm_event(Event::Exit {
+        reason: &ExitReason::Succeed(ExitSucceed::Returned),
+        return_value: &[3],
+    });
+
+    let Some(TransactionTrace::Call(trace)) = tracer.into_trace() else {
+        panic!("a call trace is expected");
+    };
+    assert_eq!(trace.call_type, CallType::Call);
+    assert_eq!((trace.from, trace.to), (ALICE, BOB));
+    assert_eq!(trace.output, vec![3]);
+    assert_eq!(trace.error, None);
+    assert_eq!(trace.calls.len(), 1);
+
+    let delegated = &trace.calls[0];
+    assert_eq!(delegated.call_type, CallType::DelegateCall);
+    assert_eq!((delegated.from, delegated.to), (BOB, CHARLIE));
+    assert_eq!(delegated.gas, 50_000.into());
+    assert_eq!(delegated.error, Some(b"execution reverted".to_vec()));
+}
+
+#[test]
+fn call_tracer_closes_frames_not_exited() {
+    let mut tracer = CallTracer::default();
+    tracer.evm_event(Event::TransactCall {
+        caller: ALICE,
+        address: BOB,
+        value: 0.into(),
+        data: &[],
+        gas_limit: 21_000,
+    });
+
+    assert!(matches!(tracer.into_trace(), Some(TransactionTrace::Call(_))));
+    assert!(CallTracer::default().into_trace().is_none());
+}
+
+#[test]
+fn call_trace_serializes_as_geth() {
+    let trace = TransactionTrace::Call(CallTrace {
+        call_type: CallType::StaticCall,
+        from: ALICE,
+        to: BOB,
+        value: 0.into(),
+        gas: 0x5208.into(),
+        gas_used: 0x100.into(),
+        input: vec![0x12, 0x34],
+        output: vec![],
+        error: Some(b"out of gas".to_vec()),
+        calls: vec![],
+    });
+
+    assert_eq!(
+        serde_json::to_value(trace).unwrap(),
+        json!({
+            "type": "STATICCALL",
+            "from": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "to": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+            "value": "0x0",
+            "gas": "0x5208",
+            "gasUsed": "0x100",
+            "input": "0x1234",
+            "output": "0x",
+            "error": "out of gas",
+        })
+    );
+}
+
+#[test]
+fn raw_trace_serializes_as_geth() {
+    let trace = TransactionTrace::Raw(RawTrace {
+        gas: 21_000,
+        failed: false,
+        return_value: vec![0xab],
+        struct_logs: vec![StructLog {
+            depth: 1,
+            gas: 100,
+            gas_cost: 3,
+            op: b"PUSH1".to_vec(),
+            pc: 0,
+            stack: Some(vec![]),
+            memory: None,
+            storage: None,
+        }],
+    });
+
+    assert_eq!(
+        serde_json::to_value(trace).unwrap(),
+        json!({
+            "gas": 21_000,
+            "failed": false,
+            "returnValue": "ab",
+            "structLogs": [{
+                "depth": 1,
+                "gas": 100,
+                "gasCost": 3,
+                "op": "PUSH1",
+                "pc": 0,
+                "stack": [],
+            }],
+        })
+    );
+}
@@ -7,7 +7,7 @@ use pallet_assets::AssetsCallback;
 use pallet_evm_precompile_assets_erc20::AddressToAssetId;
 
 /// Revert opt code. It's inserted at the precompile addresses, to make them functional in EVM.
-pub const EVM_REVERT_CODE: &[u8] = &[0x60, 0x00, 0x60, 0x00, 0xfd];
+pub use pallet_evm_precompile_assets_erc20::EVM_REVERT_CODE;
 
 /// Handler for automatic revert code registration.
 ///
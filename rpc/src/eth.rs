@@ -5,6 +5,7 @@ use jsonrpsee::RpcModule;
 use sc_client_api::{
 	backend::{Backend, StorageProvider},
 	client::BlockchainEvents,
+	BlockBackend,
 };
 use sc_network::NetworkService;
 use sc_network_sync::SyncingService;
@@ -18,9 +19,13 @@ use sp_runtime::traits::Block as BlockT;
 use txpool_api::TransactionPool;
 // Frontier
 pub use fc_rpc::{EthBlockDataCacheTask, EthConfig, OverrideHandle, StorageOverride};
+use fc_rpc::{EthDevSigner, EthSigner};
 pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
 pub use fc_storage::overrides_handle;
 use fp_rpc::{ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi};
+// Local
+use crate::tracing::{DebugApiServer, EvmTracing, EvmTracingApi, TraceApiServer};
+use precompile_utils::testing::{alith_secret_key, baltathar_secret_key, charleth_secret_key};
 
 /// Extra dependencies for Ethereum compatibility.
 pub struct EthDeps<C, P, A: ChainApi, CT, B: BlockT> {
@@ -57,6 +62,16 @@ pub struct EthDeps<C, P, A: ChainApi, CT, B: BlockT> {
 	pub execute_gas_limit_multiplier: u64,
 	/// Mandated parent hashes for a given block hash.
 	pub forced_parent_hashes: Option<BTreeMap<H256, H256>>,
+	/// Whether to serve the `debug_trace*` methods.
+	pub enable_debug: bool,
+	/// Whether to serve the `trace_filter` method.
+	pub enable_trace: bool,
+	/// Maximum number of traces returned by a single `trace_filter` call.
+	pub trace_max_count: u32,
+	/// Whether to sign `eth_sendTransaction` with the development keys.
+	pub enable_dev_signer: bool,
+	/// Secret keys the dev signer holds besides the well-known development accounts.
+	pub dev_signer_keys: Vec<[u8; 32]>,
 }
 
 impl<C, P, A: ChainApi, CT: Clone, B: BlockT> Clone for EthDeps<C, P, A, CT, B> {
@@ -78,10 +93,28 @@ impl<C, P, A: ChainApi, CT: Clone, B: BlockT> Clone for EthDeps<C, P, A, CT, B>
 			fee_history_cache_limit: self.fee_history_cache_limit,
 			execute_gas_limit_multiplier: self.execute_gas_limit_multiplier,
 			forced_parent_hashes: self.forced_parent_hashes.clone(),
+			enable_debug: self.enable_debug,
+			enable_trace: self.enable_trace,
+			trace_max_count: self.trace_max_count,
+			enable_dev_signer: self.enable_dev_signer,
+			dev_signer_keys: self.dev_signer_keys.clone(),
 		}
 	}
 }
 
+/// Builds the dev signer from the keys of the well-known development accounts Alith, Baltathar
+/// and Charleth followed by `extra_keys`.
+fn dev_signer(extra_keys: Vec<[u8; 32]>) -> Result<EthDevSigner, String> {
+	let keys = [alith_secret_key(), baltathar_secret_key(), charleth_secret_key()]
+		.into_iter()
+		.chain(extra_keys)
+		.map(|key| libsecp256k1::SecretKey::parse(&key))
+		.collect::<Result<_, _>>()
+		.map_err(|e| format!("Invalid dev signer key: {:?}", e))?;
+
+	Ok(EthDevSigner::with_keys(keys))
+}
+
 /// Instantiate Ethereum-compatible RPC extensions.
 pub fn create_eth<C, BE, P, A, CT, B, EC: EthConfig<B, C>>(
 	mut io: RpcModule<()>,
@@ -96,8 +129,11 @@ pub fn create_eth<C, BE, P, A, CT, B, EC: EthConfig<B, C>>(
 where
 	B: BlockT<Hash = sp_core::H256>,
 	C: CallApiAt<B> + ProvideRuntimeApi<B>,
-	C::Api: BlockBuilderApi<B> + ConvertTransactionRuntimeApi<B> + EthereumRuntimeRPCApi<B>,
-	C: BlockchainEvents<B> + 'static,
+	C::Api: BlockBuilderApi<B>
+		+ ConvertTransactionRuntimeApi<B>
+		+ EthereumRuntimeRPCApi<B>
+		+ EvmTracingApi<B>,
+	C: BlockchainEvents<B> + BlockBackend<B> + Send + Sync + 'static,
 	C: HeaderBackend<B> + HeaderMetadata<B, Error = BlockChainError> + StorageProvider<B, BE>,
 	BE: Backend<B> + 'static,
 	P: TransactionPool<Block = B> + 'static,
@@ -128,9 +164,27 @@ where
 		fee_history_cache_limit,
 		execute_gas_limit_multiplier,
 		forced_parent_hashes,
+		enable_debug,
+		enable_trace,
+		trace_max_count,
+		enable_dev_signer,
+		dev_signer_keys,
 	} = deps;
 
-	let signers = Vec::new();
+	if enable_debug || enable_trace {
+		let tracing = EvmTracing::new(client.clone(), frontier_backend.clone(), trace_max_count);
+		if enable_debug {
+			io.merge(DebugApiServer::into_rpc(tracing.clone()))?;
+		}
+		if enable_trace {
+			io.merge(TraceApiServer::into_rpc(tracing))?;
+		}
+	}
+
+	let mut signers = Vec::new();
+	if enable_dev_signer {
+		signers.push(Box::new(dev_signer(dev_signer_keys)?) as Box<dyn EthSigner>);
+	}
 
 	io.merge(
 		Eth::new(
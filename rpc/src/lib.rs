@@ -32,16 +32,18 @@
 
 use std::sync::Arc;
 
+use futures::channel::mpsc;
+use gpu_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Nonce};
 use jsonrpsee::RpcModule;
 use sc_client_api::{client::BlockchainEvents, AuxStore, Backend, StorageProvider};
 use sc_consensus_babe::BabeWorkerHandle;
 use sc_consensus_grandpa::{
 	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
 };
+use sc_consensus_manual_seal::EngineCommand;
 use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool::ChainApi;
-use gpu_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Nonce};
 use sp_api::{BlockT, CallApiAt, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
@@ -51,6 +53,7 @@ use sp_keystore::KeystorePtr;
 use txpool_api::TransactionPool;
 
 mod eth;
+mod tracing;
 pub use self::eth::{create_eth, overrides_handle, EthDeps};
 
 /// Extra dependencies for BABE.
@@ -95,6 +98,8 @@ pub struct FullDeps<C, P, SC, B, A: ChainApi, CT> {
 	pub backend: Arc<B>,
 	/// Ethereum-compatibility specific dependencies.
 	pub eth: EthDeps<C, P, A, CT, Block>,
+	/// Manual seal command sink, set when the node seals blocks itself.
+	pub command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
 }
 
 /// Instantiate all Full RPC extensions.
@@ -109,6 +114,7 @@ pub fn create_full<C, P, SC, B, A, CT>(
 		grandpa,
 		backend,
 		eth,
+		command_sink,
 	}: FullDeps<C, P, SC, B, A, CT>,
 	subscription_task_executor: SubscriptionTaskExecutor,
 	pubsub_notification_sinks: Arc<
@@ -136,6 +142,7 @@ where
 	C::Api: BlockBuilder<Block>,
 	C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
 	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	C::Api: gpu_evm_tracing::EvmTracingApi<Block>,
 	P: TransactionPool<Block = Block> + 'static,
 	SC: SelectChain<Block> + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
@@ -147,6 +154,7 @@ where
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
+	use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
 	use sc_rpc::dev::{Dev, DevApiServer};
 	use sc_rpc_spec_v2::chain_spec::{ChainSpec, ChainSpecApiServer};
 	use sc_sync_state_rpc::{SyncState, SyncStateApiServer};
@@ -193,6 +201,10 @@ where
 	io.merge(StateMigration::new(client.clone(), backend, deny_unsafe).into_rpc())?;
 	io.merge(Dev::new(client, deny_unsafe).into_rpc())?;
 
+	if let Some(command_sink) = command_sink {
+		io.merge(ManualSeal::new(command_sink).into_rpc())?;
+	}
+
 	// Ethereum compatibility RPCs
 	let io = create_eth::<_, _, _, _, _, _, DefaultEthConfig<C, B>>(
 		io,
@@ -0,0 +1,607 @@
+//! Ethereum tracing RPC: geth's `debug_trace*` methods and OpenEthereum's `trace_filter`.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+// Substrate
+use sc_client_api::BlockBackend;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H160, H256};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, One, UniqueSaturatedInto};
+// Frontier
+use fc_rpc::{frontier_backend_client, internal_err};
+use fc_rpc_core::types::BlockNumber;
+use fp_rpc::EthereumRuntimeRPCApi;
+// Local
+use gpu_evm_tracing::{CallTrace, CallType};
+pub use gpu_evm_tracing::{EvmTracingApi, TraceType, TransactionTrace};
+
+/// Maximum number of blocks replayed by a single `trace_filter` call.
+const MAX_FILTER_BLOCK_RANGE: u32 = 1_000;
+
+/// Options of the `debug_trace*` methods.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceOptions {
+	/// `callTracer`, `prestateTracer`, or none for the opcode level struct logger.
+	pub tracer: Option<String>,
+	#[serde(default)]
+	pub disable_storage: bool,
+	#[serde(default)]
+	pub disable_memory: bool,
+	#[serde(default)]
+	pub disable_stack: bool,
+}
+
+impl TraceOptions {
+	fn trace_type(self) -> RpcResult<TraceType> {
+		match self.tracer.as_deref() {
+			None => Ok(TraceType::Raw {
+				disable_storage: self.disable_storage,
+				disable_memory: self.disable_memory,
+				disable_stack: self.disable_stack,
+			}),
+			Some("callTracer") => Ok(TraceType::Call),
+			Some("prestateTracer") => Ok(TraceType::Prestate),
+			Some(tracer) => Err(internal_err(format!("tracer {} is not supported", tracer))),
+		}
+	}
+}
+
+/// Trace of one of the transactions of a block.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTransactionTrace {
+	pub tx_hash: H256,
+	pub result: TransactionTrace,
+}
+
+/// Filter of the `trace_filter` method.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+	pub from_block: Option<BlockNumber>,
+	pub to_block: Option<BlockNumber>,
+	pub from_address: Option<Vec<H160>>,
+	pub to_address: Option<Vec<H160>>,
+	pub after: Option<u32>,
+	pub count: Option<u32>,
+}
+
+/// Geth compatible `debug` RPC methods.
+#[rpc(server)]
+pub trait DebugApi {
+	/// Replay a transaction with the tracer given in `options`.
+	#[method(name = "debug_traceTransaction")]
+	async fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+		options: Option<TraceOptions>,
+	) -> RpcResult<TransactionTrace>;
+
+	/// Replay all the transactions of a block with the tracer given in `options`.
+	#[method(name = "debug_traceBlockByNumber")]
+	async fn trace_block_by_number(
+		&self,
+		number: BlockNumber,
+		options: Option<TraceOptions>,
+	) -> RpcResult<Vec<BlockTransactionTrace>>;
+
+	/// Replay all the transactions of a block with the tracer given in `options`.
+	#[method(name = "debug_traceBlockByHash")]
+	async fn trace_block_by_hash(
+		&self,
+		hash: H256,
+		options: Option<TraceOptions>,
+	) -> RpcResult<Vec<BlockTransactionTrace>>;
+}
+
+/// OpenEthereum compatible `trace` RPC methods.
+#[rpc(server)]
+pub trait TraceApi {
+	/// Calls made in a range of blocks, flattened and filtered by sender and recipient.
+	#[method(name = "trace_filter")]
+	async fn filter(&self, filter: TraceFilter) -> RpcResult<Vec<Value>>;
+}
+
+/// Serves the tracing RPC by replaying blocks through [`EvmTracingApi`].
+pub struct EvmTracing<B: BlockT, C> {
+	client: Arc<C>,
+	backend: Arc<dyn fc_db::BackendReader<B> + Send + Sync>,
+	max_count: u32,
+	_marker: PhantomData<B>,
+}
+
+impl<B: BlockT, C> EvmTracing<B, C> {
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<dyn fc_db::BackendReader<B> + Send + Sync>,
+		max_count: u32,
+	) -> Self {
+		Self { client, backend, max_count, _marker: PhantomData }
+	}
+}
+
+impl<B: BlockT, C> Clone for EvmTracing<B, C> {
+	fn clone(&self) -> Self {
+		Self::new(self.client.clone(), self.backend.clone(), self.max_count)
+	}
+}
+
+impl<B, C> EvmTracing<B, C>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + BlockBackend<B> + HeaderBackend<B> + 'static,
+	C::Api: EvmTracingApi<B> + EthereumRuntimeRPCApi<B>,
+{
+	/// Substrate hash of the block with the given Ethereum number.
+	async fn block_by_number(&self, number: BlockNumber) -> RpcResult<B::Hash> {
+		let id = frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number),
+		)
+		.await?
+		.ok_or_else(|| internal_err("block not found"))?;
+		self.client
+			.expect_block_hash_from_id(&id)
+			.map_err(|err| internal_err(format!("{:?}", err)))
+	}
+
+	/// Substrate hash of the block with the given Ethereum hash.
+	async fn block_by_hash(&self, hash: H256) -> RpcResult<B::Hash> {
+		frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			hash,
+		)
+		.await?
+		.ok_or_else(|| internal_err("block not found"))
+	}
+
+	/// Replay the block `hash`, tracing either all its Ethereum transactions or only `only`.
+	fn replay(
+		&self,
+		hash: B::Hash,
+		trace_type: TraceType,
+		only: Option<H256>,
+	) -> RpcResult<Vec<(H256, TransactionTrace)>> {
+		let header = self
+			.client
+			.header(hash)
+			.map_err(|err| internal_err(format!("{:?}", err)))?
+			.ok_or_else(|| internal_err("block header not found"))?;
+		let extrinsics = self
+			.client
+			.block_body(hash)
+			.map_err(|err| internal_err(format!("{:?}", err)))?
+			.ok_or_else(|| internal_err("block body not found"))?;
+
+		// The block is replayed on top of the state it was built on.
+		let api = self.client.runtime_api();
+		let parent = *header.parent_hash();
+		match only {
+			Some(transaction) => api
+				.trace_transaction(parent, &header, extrinsics, transaction, trace_type)
+				.map(|trace| {
+					trace.map(|trace| trace.map(|trace| (transaction, trace)).into_iter().collect())
+				}),
+			None => api.trace_block(parent, &header, extrinsics, trace_type),
+		}
+		.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+		.map_err(|err| internal_err(format!("failed to trace: {:?}", err)))
+	}
+
+	fn block_traces(
+		&self,
+		hash: B::Hash,
+		options: Option<TraceOptions>,
+	) -> RpcResult<Vec<BlockTransactionTrace>> {
+		let trace_type = options.unwrap_or_default().trace_type()?;
+		Ok(self
+			.replay(hash, trace_type, None)?
+			.into_iter()
+			.map(|(tx_hash, result)| BlockTransactionTrace { tx_hash, result })
+			.collect())
+	}
+}
+
+#[async_trait]
+impl<B, C> DebugApiServer for EvmTracing<B, C>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + BlockBackend<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: EvmTracingApi<B> + EthereumRuntimeRPCApi<B>,
+{
+	async fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+		options: Option<TraceOptions>,
+	) -> RpcResult<TransactionTrace> {
+		let trace_type = options.unwrap_or_default().trace_type()?;
+		let (block_hash, _) = frontier_backend_client::load_transactions::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			transaction_hash,
+			true,
+		)
+		.await?
+		.ok_or_else(|| internal_err("transaction not found"))?;
+		let hash = self.block_by_hash(block_hash).await?;
+
+		self.replay(hash, trace_type, Some(transaction_hash))?
+			.pop()
+			.map(|(_, trace)| trace)
+			.ok_or_else(|| internal_err("transaction not found in its block"))
+	}
+
+	async fn trace_block_by_number(
+		&self,
+		number: BlockNumber,
+		options: Option<TraceOptions>,
+	) -> RpcResult<Vec<BlockTransactionTrace>> {
+		let hash = self.block_by_number(number).await?;
+		self.block_traces(hash, options)
+	}
+
+	async fn trace_block_by_hash(
+		&self,
+		hash: H256,
+		options: Option<TraceOptions>,
+	) -> RpcResult<Vec<BlockTransactionTrace>> {
+		let hash = self.block_by_hash(hash).await?;
+		self.block_traces(hash, options)
+	}
+}
+
+#[async_trait]
+impl<B, C> TraceApiServer for EvmTracing<B, C>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + BlockBackend<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: EvmTracingApi<B> + EthereumRuntimeRPCApi<B>,
+{
+	async fn filter(&self, filter: TraceFilter) -> RpcResult<Vec<Value>> {
+		let from_hash = self.block_by_number(filter.from_block.unwrap_or_default()).await?;
+		let to_hash = self.block_by_number(filter.to_block.unwrap_or_default()).await?;
+		let number_of = |hash| {
+			self.client
+				.number(hash)
+				.map_err(|err| internal_err(format!("{:?}", err)))?
+				.ok_or_else(|| internal_err("block not found"))
+		};
+		let (from, to): (NumberFor<B>, NumberFor<B>) = (number_of(from_hash)?, number_of(to_hash)?);
+		if to < from || to - from >= MAX_FILTER_BLOCK_RANGE.into() {
+			return Err(internal_err(format!(
+				"block range must be increasing and span less than {} blocks",
+				MAX_FILTER_BLOCK_RANGE
+			)))
+		}
+
+		let after = filter.after.unwrap_or_default() as usize;
+		let count = filter.count.unwrap_or(self.max_count).min(self.max_count) as usize;
+		let matches = |addresses: &Option<Vec<H160>>, address: &H160| {
+			addresses.as_ref().map_or(true, |addresses| addresses.contains(address))
+		};
+
+		let mut traces = Vec::new();
+		let mut number = from;
+		while number <= to && traces.len() < after + count {
+			let hash = self
+				.client
+				.hash(number)
+				.map_err(|err| internal_err(format!("{:?}", err)))?
+				.ok_or_else(|| internal_err("block not found"))?;
+			let block = FlatContext {
+				block_hash: self
+					.client
+					.runtime_api()
+					.current_block(hash)
+					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+					.map(|block| block.header.hash())
+					.unwrap_or_default(),
+				block_number: number.unique_saturated_into(),
+			};
+
+			for (position, (transaction_hash, trace)) in
+				self.replay(hash, TraceType::Call, None)?.into_iter().enumerate()
+			{
+				if let TransactionTrace::Call(trace) = trace {
+					let mut flat = Vec::new();
+					flatten(&trace, &[], &mut flat);
+					traces.extend(
+						flat.into_iter()
+							.filter(|(trace, _)| {
+								matches(&filter.from_address, &trace.from) &&
+									matches(&filter.to_address, &trace.to)
+							})
+							.map(|(trace, trace_address)| {
+								block.format(trace, trace_address, transaction_hash, position)
+							}),
+					);
+				}
+			}
+			number += One::one();
+		}
+
+		Ok(traces.into_iter().skip(after).take(count).collect())
+	}
+}
+
+/// Frames of a call tree, with their position in the tree.
+fn flatten<'a>(
+	trace: &'a CallTrace,
+	trace_address: &[usize],
+	flat: &mut Vec<(&'a CallTrace, Vec<usize>)>,
+) {
+	flat.push((trace, trace_address.to_vec()));
+	for (index, call) in trace.calls.iter().enumerate() {
+		flatten(call, &[trace_address, &[index]].concat(), flat);
+	}
+}
+
+/// Block a flattened trace belongs to.
+struct FlatContext {
+	block_hash: H256,
+	block_number: u64,
+}
+
+impl FlatContext {
+	/// Format a frame as OpenEthereum does.
+	fn format(
+		&self,
+		trace: &CallTrace,
+		trace_address: Vec<usize>,
+		transaction_hash: H256,
+		transaction_position: usize,
+	) -> Value {
+		let (trace_type, action, result) = match trace.call_type {
+			CallType::Create | CallType::Create2 => (
+				"create",
+				json!({
+					"creationMethod": if trace.call_type == CallType::Create2 { "create2" } else { "create" },
+					"from": trace.from,
+					"gas": trace.gas,
+					"init": Bytes(trace.input.clone()),
+					"value": trace.value,
+				}),
+				json!({
+					"address": trace.to,
+					"code": Bytes(trace.output.clone()),
+					"gasUsed": trace.gas_used,
+				}),
+			),
+			CallType::SelfDestruct => (
+				"suicide",
+				json!({
+					"address": trace.from,
+					"balance": trace.value,
+					"refundAddress": trace.to,
+				}),
+				Value::Null,
+			),
+			call_type => (
+				"call",
+				json!({
+					"callType": match call_type {
+						CallType::StaticCall => "staticcall",
+						CallType::DelegateCall => "delegatecall",
+						CallType::CallCode => "callcode",
+						_ => "call",
+					},
+					"from": trace.from,
+					"gas": trace.gas,
+					"input": Bytes(trace.input.clone()),
+					"to": trace.to,
+					"value": trace.value,
+				}),
+				json!({
+					"gasUsed": trace.gas_used,
+					"output": Bytes(trace.output.clone()),
+				}),
+			),
+		};
+
+		let mut value = json!({
+			"action": action,
+			"blockHash": self.block_hash,
+			"blockNumber": self.block_number,
+			"result": result,
+			"subtraces": trace.calls.len(),
+			"traceAddress": trace_address,
+			"transactionHash": transaction_hash,
+			"transactionPosition": transaction_position,
+			"type": trace_type,
+		});
+		if let Some(error) = &trace.error {
+			value["error"] = String::from_utf8_lossy(error).into();
+			value["result"] = Value::Null;
+		}
+		value
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const ALICE: H160 = H160::repeat_byte(0xAA);
+	const BOB: H160 = H160::repeat_byte(0xBB);
+	const CHARLIE: H160 = H160::repeat_byte(0xCC);
+	const TRANSACTION: H256 = H256::repeat_byte(0x11);
+
+	fn call_trace(call_type: CallType, from: H160, to: H160, calls: Vec<CallTrace>) -> CallTrace {
+		CallTrace {
+			call_type,
+			from,
+			to,
+			value: 1.into(),
+			gas: 21_000.into(),
+			gas_used: 256.into(),
+			input: vec![0x12, 0x34],
+			output: vec![0xab],
+			error: None,
+			calls,
+		}
+	}
+
+	fn trace_type(options: Value) -> RpcResult<TraceType> {
+		serde_json::from_value::<TraceOptions>(options).unwrap().trace_type()
+	}
+
+	#[test]
+	fn options_select_the_tracer() {
+		assert_eq!(
+			TraceOptions::default().trace_type().unwrap(),
+			TraceType::Raw { disable_storage: false, disable_memory: false, disable_stack: false }
+		);
+		assert_eq!(
+			trace_type(json!({ "disableStorage": true, "disableStack": true })).unwrap(),
+			TraceType::Raw { disable_storage: true, disable_memory: false, disable_stack: true }
+		);
+		assert_eq!(trace_type(json!({ "tracer": "callTracer" })).unwrap(), TraceType::Call);
+		assert_eq!(trace_type(json!({ "tracer": "prestateTracer" })).unwrap(), TraceType::Prestate);
+		assert!(trace_type(json!({ "tracer": "4byteTracer" })).is_err());
+	}
+
+	#[test]
+	fn transaction_trace_is_the_call_tree() {
+		let inner = call_trace(CallType::StaticCall, BOB, CHARLIE, vec![]);
+		let trace = TransactionTrace::Call(call_trace(CallType::Call, ALICE, BOB, vec![inner]));
+
+		assert_eq!(
+			serde_json::to_value(trace).unwrap(),
+			json!({
+				"type": "CALL",
+				"from": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+				"to": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+				"value": "0x1",
+				"gas": "0x5208",
+				"gasUsed": "0x100",
+				"input": "0x1234",
+				"output": "0xab",
+				"calls": [{
+					"type": "STATICCALL",
+					"from": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+					"to": "0xcccccccccccccccccccccccccccccccccccccccc",
+					"value": "0x1",
+					"gas": "0x5208",
+					"gasUsed": "0x100",
+					"input": "0x1234",
+					"output": "0xab",
+				}],
+			})
+		);
+	}
+
+	#[test]
+	fn block_traces_are_keyed_by_transaction_hash() {
+		let traces = vec![BlockTransactionTrace {
+			tx_hash: TRANSACTION,
+			result: TransactionTrace::Call(call_trace(CallType::Call, ALICE, BOB, vec![])),
+		}];
+
+		assert_eq!(
+			serde_json::to_value(traces).unwrap(),
+			json!([{
+				"txHash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+				"result": {
+					"type": "CALL",
+					"from": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+					"to": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+					"value": "0x1",
+					"gas": "0x5208",
+					"gasUsed": "0x100",
+					"input": "0x1234",
+					"output": "0xab",
+				},
+			}])
+		);
+	}
+
+	#[test]
+	fn filter_flattens_the_call_tree() {
+		let mut failed = call_trace(CallType::DelegateCall, BOB, CHARLIE, vec![]);
+		failed.error = Some(b"execution reverted".to_vec());
+		let created = call_trace(CallType::Create2, BOB, CHARLIE, vec![failed]);
+		let trace = call_trace(CallType::Call, ALICE, BOB, vec![created]);
+
+		let mut flat = Vec::new();
+		flatten(&trace, &[], &mut flat);
+		let block = FlatContext { block_hash: H256::repeat_byte(0x22), block_number: 7 };
+		let formatted: Vec<_> = flat
+			.into_iter()
+			.map(|(trace, trace_address)| block.format(trace, trace_address, TRANSACTION, 3))
+			.collect();
+
+		let context = json!({
+			"blockHash": "0x2222222222222222222222222222222222222222222222222222222222222222",
+			"blockNumber": 7,
+			"transactionHash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+			"transactionPosition": 3,
+		});
+		let with_context = |mut frame: Value| {
+			for (key, value) in context.as_object().unwrap() {
+				frame[key] = value.clone();
+			}
+			frame
+		};
+		assert_eq!(
+			formatted,
+			vec![
+				with_context(json!({
+					"type": "call",
+					"action": {
+						"callType": "call",
+						"from": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+						"gas": "0x5208",
+						"input": "0x1234",
+						"to": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+						"value": "0x1",
+					},
+					"result": { "gasUsed": "0x100", "output": "0xab" },
+					"subtraces": 1,
+					"traceAddress": [],
+				})),
+				with_context(json!({
+					"type": "create",
+					"action": {
+						"creationMethod": "create2",
+						"from": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+						"gas": "0x5208",
+						"init": "0x1234",
+						"value": "0x1",
+					},
+					"result": {
+						"address": "0xcccccccccccccccccccccccccccccccccccccccc",
+						"code": "0xab",
+						"gasUsed": "0x100",
+					},
+					"subtraces": 1,
+					"traceAddress": [0],
+				})),
+				with_context(json!({
+					"type": "call",
+					"action": {
+						"callType": "delegatecall",
+						"from": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+						"gas": "0x5208",
+						"input": "0x1234",
+						"to": "0xcccccccccccccccccccccccccccccccccccccccc",
+						"value": "0x1",
+					},
+					"error": "execution reverted",
+					"result": null,
+					"subtraces": 0,
+					"traceAddress": [0, 0],
+				})),
+			]
+		);
+	}
+}
@@ -18,6 +18,9 @@
 //!
 use crate::NegativeImbalance;
 use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+use pallet_evm::{EVMCurrencyAdapter, OnChargeEVMTransaction};
+use sp_core::{H160, U256};
+use sp_runtime::traits::UniqueSaturatedInto;
 use sp_std::fmt::Debug;
 
 /// Logic for the author to get a portion of fees.
@@ -59,3 +62,48 @@ where
 		}
 	}
 }
+
+/// Logic for the fees of EVM transactions: the base fee goes to the treasury and the priority fee
+/// to the block author, as with `ToAuthor`.
+pub struct EvmDealWithFees<R>(sp_std::marker::PhantomData<R>);
+impl<R> OnChargeEVMTransaction<R> for EvmDealWithFees<R>
+where
+	R: pallet_balances::Config
+		+ pallet_treasury::Config
+		+ pallet_authorship::Config
+		+ pallet_evm::Config
+		+ Debug,
+	pallet_treasury::Pallet<R>: OnUnbalanced<NegativeImbalance<R>>,
+	<R as frame_system::Config>::AccountId: From<primitives::AccountId>,
+	<R as frame_system::Config>::AccountId: Into<primitives::AccountId>,
+	U256: UniqueSaturatedInto<<R as pallet_balances::Config>::Balance>,
+{
+	type LiquidityInfo = Option<NegativeImbalance<R>>;
+
+	fn withdraw_fee(who: &H160, fee: U256) -> Result<Self::LiquidityInfo, pallet_evm::Error<R>> {
+		<EvmFeeAdapter<R> as OnChargeEVMTransaction<R>>::withdraw_fee(who, fee)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &H160,
+		corrected_fee: U256,
+		base_fee: U256,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Self::LiquidityInfo {
+		// Refunds the unused gas and hands the base fee to the treasury.
+		<EvmFeeAdapter<R> as OnChargeEVMTransaction<R>>::correct_and_deposit_fee(
+			who,
+			corrected_fee,
+			base_fee,
+			already_withdrawn,
+		)
+	}
+
+	fn pay_priority_fee(tip: Self::LiquidityInfo) {
+		if let Some(tip) = tip {
+			<ToAuthor<R> as OnUnbalanced<_>>::on_unbalanced(tip);
+		}
+	}
+}
+
+type EvmFeeAdapter<R> = EVMCurrencyAdapter<pallet_balances::Pallet<R>, pallet_treasury::Pallet<R>>;
@@ -36,6 +36,13 @@ pub mod currency {
 	pub const fn deposit(items: u32, bytes: u32) -> Balance {
 		items as Balance * 1 * CENTS + (bytes as Balance) * 100 * MILLICENTS
 	}
+
+	/// Name of the native token.
+	pub const TOKEN_NAME: &str = "GP";
+	/// Symbol of the native token, advertised as `tokenSymbol` in the chain spec.
+	pub const TOKEN_SYMBOL: &str = "GP";
+	/// Decimals of the native token, advertised as `tokenDecimals` in the chain spec.
+	pub const TOKEN_DECIMALS: u8 = 18;
 }
 
 /// Time and blocks.
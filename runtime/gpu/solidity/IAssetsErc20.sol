@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+// This file is generated from the precompile sources, do not edit it.
+interface IAssetsErc20 {
+    /// @custom:selector ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef
+    event Transfer(address indexed, address indexed, uint256);
+
+    /// @custom:selector 8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925
+    event Approval(address indexed, address indexed, uint256);
+
+    /// @custom:selector e450d38c
+    error ERC20InsufficientBalance(address, uint256, uint256);
+
+    /// @custom:selector fb8f41b2
+    error ERC20InsufficientAllowance(address, uint256, uint256);
+
+    /// @custom:selector 18160ddd
+    function totalSupply() external view returns (uint256);
+
+    /// @custom:selector 70a08231
+    function balanceOf(address) external view returns (uint256);
+
+    /// @custom:selector dd62ed3e
+    function allowance(address, address) external view returns (uint256);
+
+    /// @custom:selector a9059cbb
+    function transfer(address, uint256) external returns (bool);
+
+    /// @custom:selector 095ea7b3
+    function approve(address, uint256) external returns (bool);
+
+    /// @custom:selector 23b872dd
+    function transferFrom(address, address, uint256) external returns (bool);
+
+    /// @custom:selector 06fdde03
+    function name() external view returns (string memory);
+
+    /// @custom:selector 95d89b41
+    function symbol() external view returns (string memory);
+
+    /// @custom:selector 313ce567
+    function decimals() external view returns (uint8);
+
+    /// @custom:selector b9d1d49b
+    function minimumBalance() external view returns (uint256);
+
+    /// @custom:selector 40c10f19
+    function mint(address, uint256) external returns (bool);
+
+    /// @custom:selector 9dc29fac
+    function burn(address, uint256) external returns (bool);
+
+    /// @custom:selector d505accf
+    function permit(address, address, uint256, uint256, uint8, bytes32, bytes32) external;
+
+    /// @custom:selector 7ecebe00
+    function nonces(address) external view returns (uint256);
+
+    /// @custom:selector 3644e515
+    function DOMAIN_SEPARATOR() external view returns (bytes32);
+
+    /// @custom:selector 37d2c2f4
+    function setMetadata(string calldata, string calldata, uint8) external returns (bool);
+
+    /// @custom:selector efb6d432
+    function clearMetadata() external returns (bool);
+
+    /// @custom:selector 8d1fdf2f
+    function freeze(address) external returns (bool);
+
+    /// @custom:selector 5ea20216
+    function thaw(address) external returns (bool);
+
+    /// @custom:selector d4937f51
+    function freezeAsset() external returns (bool);
+
+    /// @custom:selector 51ec2ad7
+    function thawAsset() external returns (bool);
+
+    /// @custom:selector f2fde38b
+    function transferOwnership(address) external returns (bool);
+
+    /// @custom:selector c7d93c59
+    function setTeam(address, address, address) external returns (bool);
+}
@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+// This file is generated from the precompile sources, do not edit it.
+interface IAssetsFactory {
+    /// @custom:selector d0ef1a2f
+    function create(uint128, address, uint256) external returns (address);
+}
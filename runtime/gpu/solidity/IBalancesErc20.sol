@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+// This file is generated from the precompile sources, do not edit it.
+interface IBalancesErc20 {
+    /// @custom:selector ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef
+    event Transfer(address indexed, address indexed, uint256);
+
+    /// @custom:selector 8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925
+    event Approval(address indexed, address indexed, uint256);
+
+    /// @custom:selector 18160ddd
+    function totalSupply() external view returns (uint256);
+
+    /// @custom:selector 70a08231
+    function balanceOf(address) external view returns (uint256);
+
+    /// @custom:selector dd62ed3e
+    function allowance(address, address) external view returns (uint256);
+
+    /// @custom:selector a9059cbb
+    function transfer(address, uint256) external returns (bool);
+
+    /// @custom:selector 095ea7b3
+    function approve(address, uint256) external returns (bool);
+
+    /// @custom:selector 23b872dd
+    function transferFrom(address, address, uint256) external returns (bool);
+
+    /// @custom:selector 06fdde03
+    function name() external view returns (string memory);
+
+    /// @custom:selector 95d89b41
+    function symbol() external view returns (string memory);
+
+    /// @custom:selector 313ce567
+    function decimals() external view returns (uint8);
+}
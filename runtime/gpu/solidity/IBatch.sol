@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+// This file is generated from the precompile sources, do not edit it.
+interface IBatch {
+    /// @custom:selector bf855484633929c3d6688eb3caf8eff910fb4bef030a8d7dbc9390d26759714d
+    event SubcallSucceeded(uint256);
+
+    /// @custom:selector dbc5d06f4f877f959b1ff12d2161cdd693fa8e442ee53f1790b2804b24881f05
+    event SubcallFailed(uint256);
+
+    /// @custom:selector 9095ae9f
+    error BatchSubcallFailed(uint256, bytes);
+
+    /// @custom:selector 7625508a
+    error BatchOutOfGas(uint256);
+
+    /// @custom:selector 79df4b9c
+    function batchSome(address[] calldata, uint256[] calldata, bytes[] calldata, uint64[] calldata) external returns (bool);
+
+    /// @custom:selector cf0491c7
+    function batchSomeUntilFailure(address[] calldata, uint256[] calldata, bytes[] calldata, uint64[] calldata) external returns (bool);
+
+    /// @custom:selector 96e292b8
+    function batchAll(address[] calldata, uint256[] calldata, bytes[] calldata, uint64[] calldata) external returns (bool);
+}
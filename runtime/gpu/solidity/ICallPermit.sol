@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+// This file is generated from the precompile sources, do not edit it.
+interface ICallPermit {
+    /// @custom:selector b5ea0966
+    function dispatch(address, address, uint256, bytes calldata, uint64, uint256, uint8, bytes32, bytes32) external returns (bytes memory);
+
+    /// @custom:selector 7ecebe00
+    function nonces(address) external view returns (uint256);
+
+    /// @custom:selector 3644e515
+    function DOMAIN_SEPARATOR() external view returns (bytes32);
+}
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+// This file is generated from the precompile sources, do not edit it.
+interface IGovernance {
+    /// @custom:selector 3839f7832b2a6263aa1fd5040f37d10fd4f9e9c4a9ef07ec384cb1cef9fb4c0e
+    event Voted(uint32 indexed, address, bool, uint256, uint8);
+
+    /// @custom:selector 49fc1dd929f126e1d88cbb9c135625e30c2deba291adeea4740e446098b9957b
+    event VoteRemoved(uint32 indexed, address);
+
+    /// @custom:selector 6cc151d547592e227b1e85a264ac3699c6f1014112b08bb3832de1f23b9c66db
+    event Delegated(uint16 indexed, address, address, uint256, uint8);
+
+    /// @custom:selector 1053303328f6db14014ccced6297bcad2b3897157ce46070711ab995a05dfa14
+    event Undelegated(uint16 indexed, address);
+
+    /// @custom:selector b7e57d4680cfb2f91ccc9cb43bc9ba37c9f06ccd1d97c416eecffa48d66bc641
+    event Submitted(uint32 indexed, uint16, bytes32);
+
+    /// @custom:selector 222ac3cb2f2e974dcbd2ac3d35e9fefb77e57f5dc4b9243afa9a926b1ff57f75
+    event DecisionDepositPlaced(uint32 indexed, address, uint256);
+
+    /// @custom:selector f594cad4
+    function vote(uint32, bool, uint8, uint256) external returns (bool);
+
+    /// @custom:selector 79cae220
+    function removeVote(uint32) external returns (bool);
+
+    /// @custom:selector 681750e8
+    function delegate(uint16, address, uint8, uint256) external returns (bool);
+
+    /// @custom:selector 98be4094
+    function undelegate(uint16) external returns (bool);
+
+    /// @custom:selector 70fe1ba6
+    function submit(uint16, bytes32, uint32) external returns (uint32);
+
+    /// @custom:selector 245ce18d
+    function placeDecisionDeposit(uint32) external returns (bool);
+
+    /// @custom:selector 8d407c0b
+    function referendumStatus(uint32) external view returns (uint8);
+
+    /// @custom:selector 61b95999
+    function referendumTally(uint32) external view returns (uint256, uint256, uint256);
+
+    /// @custom:selector 34038146
+    function trackInfo(uint16) external view returns (bytes memory, uint32, uint256, uint32, uint32, uint32, uint32);
+}
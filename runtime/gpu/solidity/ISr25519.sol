@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+// This file is generated from the precompile sources, do not edit it.
+interface ISr25519 {
+    /// @custom:selector 5bf48e3a
+    function verify(bytes32, bytes calldata, bytes calldata) external view returns (bool);
+}
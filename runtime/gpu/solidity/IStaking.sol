@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+// This file is generated from the precompile sources, do not edit it.
+interface IStaking {
+    /// @custom:selector 6d76f692
+    function bond(uint256, uint8, bytes32) external returns (bool);
+
+    /// @custom:selector eaca88de
+    function bondExtra(uint256) external returns (bool);
+
+    /// @custom:selector 27de9e32
+    function unbond(uint256) external returns (bool);
+
+    /// @custom:selector 548a6706
+    function withdrawUnbonded(uint32) external returns (bool);
+
+    /// @custom:selector f5330e96
+    function nominate(bytes32[] calldata) external returns (bool);
+
+    /// @custom:selector 2b8a3ae6
+    function chill() external returns (bool);
+
+    /// @custom:selector 6bc56089
+    function payoutStakers(bytes32, uint32) external returns (bool);
+
+    /// @custom:selector ed864099
+    function setPayee(uint8, bytes32) external returns (bool);
+
+    /// @custom:selector fbfa941f
+    function ledger(address) external view returns (uint256, uint256, uint256);
+
+    /// @custom:selector f95baf26
+    function nominations(address) external view returns (bytes32[] memory);
+
+    /// @custom:selector 973628f6
+    function currentEra() external view returns (uint32);
+
+    /// @custom:selector 51026cd4
+    function minNominatorBond() external view returns (uint256);
+}
@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.0;
+
+// This file is generated from the precompile sources, do not edit it.
+interface ISubstrateEcdsa {
+    /// @custom:selector de8f50a1
+    function verify(bytes calldata, bytes calldata, bytes calldata) external view returns (bool);
+}
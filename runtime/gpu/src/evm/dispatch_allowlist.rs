@@ -0,0 +1,199 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Calls that EVM contracts may dispatch through the dispatch precompile.
+
+use frame_support::{
+	dispatch::{DispatchClass, GetDispatchInfo, Pays},
+	traits::GetCallMetadata,
+};
+use pallet_evm::{ExitError, PrecompileFailure};
+use pallet_evm_precompile_dispatch::DispatchValidateT;
+use sp_std::marker::PhantomData;
+
+pub use pallet_dispatch_allowlist::*;
+
+#[frame_support::pallet]
+pub mod pallet_dispatch_allowlist {
+	use frame_support::{
+		pallet_prelude::*, traits::CallMetadata, weights::constants::RocksDbWeight,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	/// Maximum length of a pallet or call name.
+	pub type MaxNameLen = ConstU32<64>;
+
+	/// An allowlist entry: a pallet as named in `construct_runtime`, and optionally one of its
+	/// calls.
+	#[derive(PartialEq, Eq, Clone, MaxEncodedLen, Encode, Decode, TypeInfo, RuntimeDebug)]
+	pub struct AllowedCall {
+		/// Name of the pallet.
+		pub pallet: BoundedVec<u8, MaxNameLen>,
+		/// Name of the call, any call of the pallet if `None`.
+		pub call: Option<BoundedVec<u8, MaxNameLen>>,
+	}
+
+	impl AllowedCall {
+		/// Allows any call of `pallet`.
+		pub fn pallet(pallet: &str) -> Self {
+			Self { pallet: BoundedVec::truncate_from(pallet.as_bytes().to_vec()), call: None }
+		}
+
+		/// Allows `call` of `pallet`.
+		pub fn call(pallet: &str, call: &str) -> Self {
+			Self {
+				pallet: BoundedVec::truncate_from(pallet.as_bytes().to_vec()),
+				call: Some(BoundedVec::truncate_from(call.as_bytes().to_vec())),
+			}
+		}
+
+		/// Whether this entry covers the call described by `metadata`.
+		pub fn matches(&self, metadata: &CallMetadata) -> bool {
+			self.pallet[..] == *metadata.pallet_name.as_bytes() &&
+				self.call
+					.as_ref()
+					.map_or(true, |call| call[..] == *metadata.function_name.as_bytes())
+		}
+	}
+
+	/// Weight functions needed for the dispatch allowlist.
+	pub trait WeightInfo {
+		fn set_allowlist(n: u32) -> Weight;
+	}
+
+	/// Weights of the dispatch allowlist, linear in the number of entries which are encoded into
+	/// storage and into the event.
+	pub struct SubstrateWeight<T>(PhantomData<T>);
+	impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+		fn set_allowlist(n: u32) -> Weight {
+			Weight::from_parts(10_000_000, 0)
+				.saturating_add(Weight::from_parts(250_000, 0).saturating_mul(n.into()))
+				.saturating_add(T::DbWeight::get().writes(1))
+		}
+	}
+
+	impl WeightInfo for () {
+		fn set_allowlist(n: u32) -> Weight {
+			Weight::from_parts(10_000_000, 0)
+				.saturating_add(Weight::from_parts(250_000, 0).saturating_mul(n.into()))
+				.saturating_add(RocksDbWeight::get().writes(1))
+		}
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin allowed to replace the allowlist.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of allowlist entries.
+		#[pallet::constant]
+		type MaxAllowedCalls: Get<u32>;
+
+		/// The allowlist until one is set by [`Pallet::set_allowlist`].
+		type DefaultAllowlist: Get<BoundedVec<AllowedCall, Self::MaxAllowedCalls>>;
+
+		/// Weight information for the extrinsics of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::type_value]
+	pub fn DefaultAllowlistOf<T: Config>() -> BoundedVec<AllowedCall, T::MaxAllowedCalls> {
+		T::DefaultAllowlist::get()
+	}
+
+	/// Calls that may be dispatched through the dispatch precompile.
+	#[pallet::storage]
+	#[pallet::getter(fn allowlist)]
+	pub type Allowlist<T: Config> = StorageValue<
+		_,
+		BoundedVec<AllowedCall, T::MaxAllowedCalls>,
+		ValueQuery,
+		DefaultAllowlistOf<T>,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The allowlist was replaced.
+		AllowlistSet { allowlist: BoundedVec<AllowedCall, T::MaxAllowedCalls> },
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Replace the allowlist of the dispatch precompile.
+		///
+		/// An allowlist longer than `MaxAllowedCalls` doesn't decode, so the weight is bounded.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_allowlist(allowlist.len() as u32))]
+		pub fn set_allowlist(
+			origin: OriginFor<T>,
+			allowlist: BoundedVec<AllowedCall, T::MaxAllowedCalls>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			Allowlist::<T>::put(&allowlist);
+			Self::deposit_event(Event::AllowlistSet { allowlist });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether the call described by `metadata` is allowlisted.
+		pub fn is_allowed(metadata: &CallMetadata) -> bool {
+			Self::allowlist().iter().any(|entry| entry.matches(metadata))
+		}
+	}
+}
+
+/// Validates calls of the dispatch precompile against the [`Allowlist`].
+///
+/// Only `Normal` calls that pay fees are dispatched. Allowlisting a pallet that wraps calls, such
+/// as `Utility` or `Proxy`, also allows whatever they wrap.
+pub struct DispatchAllowlist<R>(PhantomData<R>);
+
+impl<R> DispatchValidateT<R::AccountId, <R as frame_system::Config>::RuntimeCall>
+	for DispatchAllowlist<R>
+where
+	R: Config,
+	<R as frame_system::Config>::RuntimeCall: GetDispatchInfo + GetCallMetadata,
+{
+	fn validate_before_dispatch(
+		_origin: &R::AccountId,
+		call: &<R as frame_system::Config>::RuntimeCall,
+	) -> Option<PrecompileFailure> {
+		let info = call.get_dispatch_info();
+		if !(info.pays_fee == Pays::Yes && info.class == DispatchClass::Normal) {
+			return Some(PrecompileFailure::Error {
+				exit_status: ExitError::Other("invalid call".into()),
+			})
+		}
+
+		if !Pallet::<R>::is_allowed(&call.get_call_metadata()) {
+			return Some(PrecompileFailure::Error {
+				exit_status: ExitError::Other("call not allowed".into()),
+			})
+		}
+
+		None
+	}
+}
@@ -13,19 +13,30 @@
 
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
+pub mod dispatch_allowlist;
 pub mod precompiles;
+#[cfg(test)]
+mod tests;
+#[cfg(feature = "evm-tracing")]
+pub mod tracing;
 
 use crate::{
-	AccountId, Babe, Balance, Balances, BaseFee, ConstU32, Runtime, RuntimeCall, RuntimeEvent,
-	Signature, Timestamp, Weight, MAXIMUM_BLOCK_WEIGHT, NORMAL_DISPATCH_RATIO,
+	weights, AccountId, Babe, Balances, BaseFee, ConstU32, GeneralAdmin, Runtime, RuntimeCall,
+	RuntimeEvent, Signature, Timestamp, UnifiedAccounts, Weight, MAXIMUM_BLOCK_WEIGHT,
+	NORMAL_DISPATCH_RATIO,
 };
 
+use dispatch_allowlist::AllowedCall;
 use frame_support::{
-	parameter_types, traits::FindAuthor, weights::constants::WEIGHT_REF_TIME_PER_SECOND,
-	ConsensusEngineId,
+	parameter_types,
+	traits::{EitherOf, FindAuthor},
+	weights::constants::WEIGHT_REF_TIME_PER_SECOND,
+	BoundedVec, ConsensusEngineId,
 };
+use frame_system::EnsureRoot;
 use pallet_ethereum::PostLogContent;
 use parity_scale_codec::Encode;
+use runtime_common::impls::{DealWithFees, EvmDealWithFees};
 use sp_core::{H160, U256};
 use sp_runtime::{
 	traits::{BlakeTwo256, Verify},
@@ -34,7 +45,9 @@ use sp_runtime::{
 };
 
 pub use precompiles::GpuPrecompiles;
-pub use gpu_runtime_constants::currency::{MILLICENTS, UNITS};
+pub use gpu_runtime_constants::currency::{
+	MILLICENTS, TOKEN_DECIMALS, TOKEN_NAME, TOKEN_SYMBOL, UNITS,
+};
 
 pub type Precompiles = GpuPrecompiles<Runtime>;
 
@@ -58,6 +71,20 @@ impl pallet_base_fee::BaseFeeThreshold for BaseFeeThreshold {
 	}
 }
 
+/// Metadata of the native currency as seen through its ERC20 precompile.
+pub struct NativeErc20Metadata;
+impl pallet_evm_precompile_balances_erc20::Erc20Metadata for NativeErc20Metadata {
+	fn name() -> &'static str {
+		TOKEN_NAME
+	}
+	fn symbol() -> &'static str {
+		TOKEN_SYMBOL
+	}
+	fn decimals() -> u8 {
+		TOKEN_DECIMALS
+	}
+}
+
 impl pallet_base_fee::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Threshold = BaseFeeThreshold;
@@ -128,14 +155,14 @@ impl pallet_evm::Config for Runtime {
 	type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Runtime>;
 	type CallOrigin = pallet_evm::EnsureAddressRoot<AccountId>;
 	type WithdrawOrigin = pallet_evm::EnsureAddressTruncated;
-	type AddressMapping = pallet_evm::HashedAddressMapping<BlakeTwo256>;
+	type AddressMapping = UnifiedAccounts;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type PrecompilesType = Precompiles;
 	type PrecompilesValue = PrecompilesValue;
 	type ChainId = ChainId;
-	type OnChargeTransaction = ();
+	type OnChargeTransaction = EvmDealWithFees<Runtime>;
 	type BlockGasLimit = BlockGasLimit;
 	type Timestamp = Timestamp;
 	type OnCreate = ();
@@ -158,7 +185,6 @@ impl pallet_ethereum::Config for Runtime {
 
 parameter_types! {
 	pub const EcdsaUnsignedPriority: TransactionPriority = TransactionPriority::MAX / 2;
-	pub const CallFee: Balance = UNITS / 10;
 	pub const CallMagicNumber: u16 = 0x0250;
 }
 
@@ -168,8 +194,37 @@ impl pallet_custom_signatures::Config for Runtime {
 	type Signature = pallet_custom_signatures::ethereum::EthereumSignature;
 	type Signer = <Signature as Verify>::Signer;
 	type CallMagicNumber = CallMagicNumber;
+	type ChainId = ChainId;
 	type Currency = Balances;
-	type CallFee = CallFee;
-	type OnChargeTransaction = ();
+	type OnChargeTransaction = DealWithFees<Runtime>;
 	type UnsignedPriority = EcdsaUnsignedPriority;
+	type WeightInfo = weights::pallet_custom_signatures::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	/// Pallets whose calls contracts may dispatch until governance sets the allowlist.
+	pub DefaultDispatchAllowlist: BoundedVec<AllowedCall, ConstU32<64>> =
+		BoundedVec::truncate_from(sp_std::vec![
+			AllowedCall::pallet("Balances"),
+			AllowedCall::pallet("Staking"),
+			AllowedCall::pallet("Assets"),
+			AllowedCall::pallet("ConvictionVoting"),
+		]);
+}
+
+impl dispatch_allowlist::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AdminOrigin = EitherOf<EnsureRoot<AccountId>, GeneralAdmin>;
+	type MaxAllowedCalls = ConstU32<64>;
+	type DefaultAllowlist = DefaultDispatchAllowlist;
+	type WeightInfo = dispatch_allowlist::SubstrateWeight<Runtime>;
+}
+
+impl pallet_unified_accounts::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type DefaultEvmToNative = pallet_evm::HashedAddressMapping<BlakeTwo256>;
+	type DefaultNativeToEvm = pallet_unified_accounts::HashedAccountMapping<BlakeTwo256>;
+	type ChainId = ChainId;
+	type WeightInfo = weights::pallet_unified_accounts::WeightInfo<Runtime>;
 }
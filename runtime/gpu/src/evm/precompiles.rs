@@ -20,24 +20,75 @@ use pallet_evm::{
 	ExitRevert, IsPrecompileResult, Precompile, PrecompileFailure, PrecompileHandle,
 	PrecompileResult, PrecompileSet,
 };
-use pallet_evm_precompile_assets_erc20::{AddressToAssetId, Erc20AssetsPrecompileSet};
+use pallet_evm_precompile_assets_erc20::{
+	AddressToAssetId, Erc20AssetsFactoryPrecompile, Erc20AssetsPrecompileSet,
+};
+use pallet_evm_precompile_balances_erc20::Erc20BalancesPrecompile;
 use pallet_evm_precompile_batch::BatchPrecompile;
 use pallet_evm_precompile_blake2::Blake2F;
+use pallet_evm_precompile_bls12377::{
+	Bls12377G1Add, Bls12377G1Mul, Bls12377G1MultiExp, Bls12377G2Add, Bls12377G2Mul,
+	Bls12377G2MultiExp, Bls12377Pairing,
+};
+use pallet_evm_precompile_bls12381::{
+	Bls12381G1Add, Bls12381G1Msm, Bls12381G2Add, Bls12381G2Msm, Bls12381MapG1, Bls12381MapG2,
+	Bls12381Pairing,
+};
 use pallet_evm_precompile_bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
+use pallet_evm_precompile_bw6761::{
+	Bw6761G1Add, Bw6761G1Mul, Bw6761G1MultiExp, Bw6761G2Add, Bw6761G2Mul, Bw6761G2MultiExp,
+	Bw6761Pairing,
+};
+use pallet_evm_precompile_call_permit::CallPermitPrecompile;
+use pallet_evm_precompile_curve25519::{Curve25519Add, Curve25519ScalarMul};
 use pallet_evm_precompile_dispatch::Dispatch;
 use pallet_evm_precompile_ed25519::Ed25519Verify;
+use pallet_evm_precompile_governance::GovernancePrecompile;
 use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_p256::P256VerifyPrecompile;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
 use pallet_evm_precompile_sr25519::Sr25519Precompile;
+use pallet_evm_precompile_staking::StakingPrecompile;
 use pallet_evm_precompile_substrate_ecdsa::SubstrateEcdsaPrecompile;
 use sp_core::H160;
 use sp_std::{fmt::Debug, marker::PhantomData};
 
+use super::dispatch_allowlist::DispatchAllowlist;
+use crate::weights;
+
+/// The assets precompile set, charging the weights benchmarked for the runtime.
+type AssetsPrecompileSet<R> =
+	Erc20AssetsPrecompileSet<R, (), weights::pallet_evm_precompile_assets_erc20::WeightInfo<R>>;
+
 /// The asset precompile address prefix. Addresses that match against this prefix will be routed
 /// to Erc20AssetsPrecompileSet
 pub const ASSET_PRECOMPILE_ADDRESS_PREFIX: &[u8] = &[255u8; 4];
 
+/// Gas of the EIP-3026 BW6-761 operations. The BW6-761 precompiles do not charge any gas on their
+/// own yet, so it is charged before running them.
+const BW6761_ADD_GAS: u64 = 180;
+const BW6761_MUL_GAS: u64 = 64_000;
+const BW6761_PAIRING_BASE_GAS: u64 = 320_000;
+const BW6761_PAIRING_PER_PAIR_GAS: u64 = 120_000;
+
+/// EIP-3026 discounts of a multiexponentiation of `k` pairs, in thousandths of its multiplications.
+/// The last one applies to any larger `k`.
+const BW6761_MULTIEXP_DISCOUNT_TABLE: [u64; 128] = [
+	1266, 733, 561, 474, 422, 387, 362, 344, 329, 318, 308, 300, 296, 289, 283, 279, 275, 272, 269,
+	266, 265, 260, 259, 256, 255, 254, 252, 251, 250, 249, 248, 247, 246, 245, 243, 242, 241, 240,
+	239, 238, 237, 236, 235, 234, 233, 232, 231, 231, 230, 229, 228, 227, 226, 225, 224, 223, 222,
+	221, 220, 219, 219, 218, 217, 216, 215, 214, 213, 212, 211, 210, 209, 208, 207, 206, 205, 204,
+	203, 202, 201, 200, 199, 198, 197, 196, 195, 194, 193, 192, 191, 190, 189, 188, 187, 186, 185,
+	184, 183, 182, 181, 180, 179, 178, 177, 176, 175, 174, 173, 172, 171, 170, 169, 168, 167, 166,
+	165, 164, 163, 162, 161, 160, 159, 158, 157, 156, 155, 154, 153, 152,
+];
+
+/// Gas of the Curve25519 operations, priced as the `ECADD` and `ECMUL` of EIP-1108 on top of the
+/// small linear cost the precompiles charge on their own.
+const CURVE25519_ADD_GAS: u64 = 150;
+const CURVE25519_MUL_GAS: u64 = 6_000;
+
 /// The PrecompileSet installed in the Astar runtime.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct GpuPrecompiles<R>(PhantomData<R>);
@@ -50,9 +101,13 @@ impl<R> GpuPrecompiles<R> {
 	/// Return all addresses that contain precompiles. This can be used to populate dummy code
 	/// under the precompile.
 	pub fn used_addresses() -> impl Iterator<Item = H160> {
-		sp_std::vec![1, 2, 3, 4, 5, 6, 7, 8, 1024, 1025, 1026, 1027, 20482, 20483, 20486]
-			.into_iter()
-			.map(hash)
+		sp_std::vec![
+			1, 2, 3, 4, 5, 6, 7, 8, 11, 12, 13, 14, 15, 16, 17, 256, 1024, 1025, 1026, 1027, 1028,
+			1029, 1030, 1031, 1032, 1033, 1034, 1035, 1036, 1037, 1038, 1039, 1040, 1041, 1042,
+			1043, 20481, 20482, 20483, 20486, 20487, 20488, 20489, 20490
+		]
+		.into_iter()
+		.map(hash)
 	}
 }
 
@@ -61,9 +116,14 @@ impl<R> GpuPrecompiles<R> {
 /// 1024-2047 Precompiles that are not in Ethereum Mainnet
 impl<R> PrecompileSet for GpuPrecompiles<R>
 where
-	Erc20AssetsPrecompileSet<R>: PrecompileSet,
-	BatchPrecompile<R>: Precompile,
-	Dispatch<R>: Precompile,
+	AssetsPrecompileSet<R>: PrecompileSet,
+	BatchPrecompile<R, weights::pallet_evm_precompile_batch::WeightInfo<R>>: Precompile,
+	Dispatch<R, DispatchAllowlist<R>>: Precompile,
+	StakingPrecompile<R>: Precompile,
+	GovernancePrecompile<R, crate::governance::TracksInfo>: Precompile,
+	Erc20BalancesPrecompile<R, super::NativeErc20Metadata>: Precompile,
+	CallPermitPrecompile<R>: Precompile,
+	Erc20AssetsFactoryPrecompile<R>: Precompile,
 	R: pallet_evm::Config
 		+ pallet_assets::Config
 		+ AddressToAssetId<<R as pallet_assets::Config>::AssetId>,
@@ -73,7 +133,7 @@ where
 		if let IsPrecompileResult::Answer { is_precompile, .. } =
 			self.is_precompile(address, u64::MAX)
 		{
-			if is_precompile && address > hash(9) && handle.context().address != address {
+			if is_precompile && address > hash(1023) && handle.context().address != address {
 				return Some(Err(PrecompileFailure::Revert {
 					exit_status: ExitRevert::Reverted,
 					output: b"cannot be called with DELEGATECALL or CALLCODE".to_vec(),
@@ -91,31 +151,89 @@ where
 			a if a == hash(7) => Some(Bn128Mul::execute(handle)),
 			a if a == hash(8) => Some(Bn128Pairing::execute(handle)),
 			a if a == hash(9) => Some(Blake2F::execute(handle)),
+			// EIP-2537 BLS12-381
+			a if a == hash(11) => Some(Bls12381G1Add::execute(handle)),
+			a if a == hash(12) => Some(Bls12381G1Msm::execute(handle)),
+			a if a == hash(13) => Some(Bls12381G2Add::execute(handle)),
+			a if a == hash(14) => Some(Bls12381G2Msm::execute(handle)),
+			a if a == hash(15) => Some(Bls12381Pairing::execute(handle)),
+			a if a == hash(16) => Some(Bls12381MapG1::execute(handle)),
+			a if a == hash(17) => Some(Bls12381MapG2::execute(handle)),
+			// RIP-7212 P256VERIFY 0x100
+			a if a == hash(256) => Some(P256VerifyPrecompile::<R>::execute(handle)),
 			// nor Ethereum precompiles :
 			a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
-			a if a == hash(1025) => Some(Dispatch::<R>::execute(handle)),
+			a if a == hash(1025) => Some(Dispatch::<R, DispatchAllowlist<R>>::execute(handle)),
 			a if a == hash(1026) => Some(ECRecoverPublicKey::execute(handle)),
 			a if a == hash(1027) => Some(Ed25519Verify::execute(handle)),
+			// Curve25519 0x404-0x405
+			a if a == hash(1028) =>
+				Some(execute_charged::<Curve25519Add>(handle, curve25519_add_gas)),
+			a if a == hash(1029) =>
+				Some(execute_charged::<Curve25519ScalarMul>(handle, |_| CURVE25519_MUL_GAS)),
+			// BLS12-377 0x406-0x40C
+			a if a == hash(1030) => Some(Bls12377G1Add::execute(handle)),
+			a if a == hash(1031) => Some(Bls12377G1Mul::execute(handle)),
+			a if a == hash(1032) => Some(Bls12377G1MultiExp::execute(handle)),
+			a if a == hash(1033) => Some(Bls12377G2Add::execute(handle)),
+			a if a == hash(1034) => Some(Bls12377G2Mul::execute(handle)),
+			a if a == hash(1035) => Some(Bls12377G2MultiExp::execute(handle)),
+			a if a == hash(1036) => Some(Bls12377Pairing::execute(handle)),
+			// BW6-761 0x40D-0x413
+			a if a == hash(1037) =>
+				Some(execute_charged::<Bw6761G1Add>(handle, |_| BW6761_ADD_GAS)),
+			a if a == hash(1038) =>
+				Some(execute_charged::<Bw6761G1Mul>(handle, |_| BW6761_MUL_GAS)),
+			a if a == hash(1039) =>
+				Some(execute_charged::<Bw6761G1MultiExp>(handle, bw6761_multiexp_gas)),
+			a if a == hash(1040) =>
+				Some(execute_charged::<Bw6761G2Add>(handle, |_| BW6761_ADD_GAS)),
+			a if a == hash(1041) =>
+				Some(execute_charged::<Bw6761G2Mul>(handle, |_| BW6761_MUL_GAS)),
+			a if a == hash(1042) =>
+				Some(execute_charged::<Bw6761G2MultiExp>(handle, bw6761_multiexp_gas)),
+			a if a == hash(1043) =>
+				Some(execute_charged::<Bw6761Pairing>(handle, bw6761_pairing_gas)),
+			// Staking 0x5001
+			a if a == hash(20481) => Some(StakingPrecompile::<R>::execute(handle)),
 			// Sr25519     0x5002
-			a if a == hash(20482) => Some(Sr25519Precompile::<R>::execute(handle)),
+			a if a == hash(20482) => Some(Sr25519Precompile::<
+				R,
+				weights::pallet_evm_precompile_sr25519::WeightInfo<R>,
+			>::execute(handle)),
 			// SubstrateEcdsa 0x5003
-			a if a == hash(20483) => Some(SubstrateEcdsaPrecompile::<R>::execute(handle)),
+			a if a == hash(20483) => Some(SubstrateEcdsaPrecompile::<
+				R,
+				weights::pallet_evm_precompile_substrate_ecdsa::WeightInfo<R>,
+			>::execute(handle)),
 			// Batch 0x5006
-			a if a == hash(20486) => Some(BatchPrecompile::<R>::execute(handle)),
+			a if a == hash(20486) => Some(BatchPrecompile::<
+				R,
+				weights::pallet_evm_precompile_batch::WeightInfo<R>,
+			>::execute(handle)),
+			// Governance 0x5007
+			a if a == hash(20487) =>
+				Some(GovernancePrecompile::<R, crate::governance::TracksInfo>::execute(handle)),
+			// Native currency ERC20 0x5008
+			a if a == hash(20488) =>
+				Some(Erc20BalancesPrecompile::<R, super::NativeErc20Metadata>::execute(handle)),
+			// Call permit 0x5009
+			a if a == hash(20489) => Some(CallPermitPrecompile::<R>::execute(handle)),
+			// Assets factory 0x500A
+			a if a == hash(20490) => Some(Erc20AssetsFactoryPrecompile::<R>::execute(handle)),
 			// If the address matches asset prefix, the we route through the asset precompile set
 			a if &a.to_fixed_bytes()[0..4] == ASSET_PRECOMPILE_ADDRESS_PREFIX =>
-				Erc20AssetsPrecompileSet::<R>::new().execute(handle),
+				AssetsPrecompileSet::<R>::new().execute(handle),
 			// Default
 			_ => None,
 		}
 	}
 
 	fn is_precompile(&self, address: H160, gas: u64) -> IsPrecompileResult {
-		let assets_precompile =
-			match Erc20AssetsPrecompileSet::<R>::new().is_precompile(address, gas) {
-				IsPrecompileResult::Answer { is_precompile, .. } => is_precompile,
-				_ => false,
-			};
+		let assets_precompile = match AssetsPrecompileSet::<R>::new().is_precompile(address, gas) {
+			IsPrecompileResult::Answer { is_precompile, .. } => is_precompile,
+			_ => false,
+		};
 
 		IsPrecompileResult::Answer {
 			is_precompile: assets_precompile || Self::used_addresses().any(|x| x == address),
@@ -124,6 +242,36 @@ where
 	}
 }
 
+/// Charges the gas computed by `gas` from the input length, then runs `P`.
+fn execute_charged<P: Precompile>(
+	handle: &mut impl PrecompileHandle,
+	gas: impl FnOnce(usize) -> u64,
+) -> PrecompileResult {
+	handle.record_cost(gas(handle.input().len()))?;
+	P::execute(handle)
+}
+
+/// A multiexponentiation costs its multiplications, each pair taking 256 bytes, discounted by the
+/// EIP-3026 table.
+fn bw6761_multiexp_gas(input_len: usize) -> u64 {
+	let k = input_len / 256;
+	if k == 0 {
+		return 0
+	}
+	let discount = BW6761_MULTIEXP_DISCOUNT_TABLE[k.min(BW6761_MULTIEXP_DISCOUNT_TABLE.len()) - 1];
+	k as u64 * BW6761_MUL_GAS * discount / 1_000
+}
+
+/// An addition costs an `ECADD` for each of its points of 32 bytes.
+fn curve25519_add_gas(input_len: usize) -> u64 {
+	(input_len / 32) as u64 * CURVE25519_ADD_GAS
+}
+
+/// A pairing check costs a base plus a fixed amount for each pair of 384 bytes.
+fn bw6761_pairing_gas(input_len: usize) -> u64 {
+	BW6761_PAIRING_BASE_GAS + (input_len / 384) as u64 * BW6761_PAIRING_PER_PAIR_GAS
+}
+
 fn hash(a: u64) -> H160 {
 	H160::from_low_u64_be(a)
 }
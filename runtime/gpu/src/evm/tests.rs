@@ -0,0 +1,783 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::{
+	pallet_custom_origins, Assets, Authorship, Balance,
+	DispatchAllowlist as DispatchAllowlistPallet, Ethereum, RuntimeOrigin, System, Treasury,
+	BABE_GENESIS_EPOCH_CONFIG, EVM,
+};
+use babe_primitives::{
+	digests::{PreDigest, SecondaryPlainPreDigest},
+	AuthorityId as BabeId, BABE_ENGINE_ID,
+};
+use dispatch_allowlist::DispatchAllowlist;
+use frame_support::{assert_noop, assert_ok, dispatch::GetDispatchInfo, traits::Currency};
+use gpu_evm_tracing::{CallTracer, CallType, PrestateTracer, TransactionTrace};
+use gpu_runtime_constants::currency::EXISTENTIAL_DEPOSIT;
+use pallet_evm::{
+	AddressMapping, ExitError, ExitReason, ExitRevert, ExitSucceed, PrecompileFailure, Runner,
+};
+use pallet_evm_precompile_dispatch::DispatchValidateT;
+use parity_scale_codec::{Decode, Encode};
+use rustc_hex::FromHex;
+use sp_core::{sr25519, H256};
+use sp_runtime::{BuildStorage, Digest, DigestItem, DispatchError};
+
+const BASE_FEE: Balance = MILLICENTS / 1_000_000;
+const TRANSFER_GAS: Balance = 21_000;
+const INITIAL_BALANCE: Balance = 10 * UNITS;
+
+const ALICE: H160 = H160::repeat_byte(0x11);
+const BOB: H160 = H160::repeat_byte(0x22);
+
+fn account(address: H160) -> AccountId {
+	<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+}
+
+/// Block author, the only session validator and so the author of the BABE pre-digest.
+fn author() -> AccountId {
+	AccountId::from([7u8; 32])
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![
+			(account(ALICE), INITIAL_BALANCE),
+			// Keeps the treasury account alive so it can receive fees below the existential
+			// deposit.
+			(Treasury::account_id(), EXISTENTIAL_DEPOSIT),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	pallet_babe::GenesisConfig::<Runtime> {
+		authorities: vec![(BabeId::from(sr25519::Public::from_raw([7u8; 32])), 1)],
+		epoch_config: Some(BABE_GENESIS_EPOCH_CONFIG),
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		pallet_session::Validators::<Runtime>::put(vec![author()]);
+		let pre_digest = PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
+			authority_index: 0,
+			slot: 1.into(),
+		});
+		System::initialize(
+			&1,
+			&Default::default(),
+			&Digest { logs: vec![DigestItem::PreRuntime(BABE_ENGINE_ID, pre_digest.encode())] },
+		);
+		let _ = Balances::deposit_creating(&author(), EXISTENTIAL_DEPOSIT);
+	});
+	ext
+}
+
+fn transfer(value: Balance, priority_fee_per_gas: Balance) -> pallet_ethereum::Transaction {
+	pallet_ethereum::Transaction::EIP1559(ethereum::EIP1559Transaction {
+		chain_id: ChainId::get(),
+		nonce: U256::zero(),
+		max_priority_fee_per_gas: priority_fee_per_gas.into(),
+		max_fee_per_gas: (BASE_FEE + priority_fee_per_gas).into(),
+		gas_limit: 100_000.into(),
+		action: pallet_ethereum::TransactionAction::Call(BOB),
+		value: value.into(),
+		input: vec![],
+		access_list: vec![],
+		// `transact` trusts its origin, the signature is only checked during validation.
+		odd_y_parity: false,
+		r: H256::repeat_byte(1),
+		s: H256::repeat_byte(1),
+	})
+}
+
+#[test]
+fn base_fee_goes_to_treasury_and_tip_to_author() {
+	new_test_ext().execute_with(|| {
+		let tip = 2 * BASE_FEE;
+
+		assert_ok!(Ethereum::transact(
+			RuntimeOrigin::from(pallet_ethereum::RawOrigin::EthereumTransaction(ALICE)),
+			transfer(UNITS, tip),
+		));
+
+		assert_eq!(Authorship::author(), Some(author()));
+		// The EVM view of the author, truncated from the BABE authority key, is not credited.
+		assert_eq!(Balances::free_balance(account(EVM::find_author())), 0);
+		// Unused gas is refunded, the sender only pays for the gas of the transfer.
+		assert_eq!(
+			Balances::free_balance(account(ALICE)),
+			INITIAL_BALANCE - UNITS - TRANSFER_GAS * (BASE_FEE + tip)
+		);
+		assert_eq!(Balances::free_balance(account(BOB)), UNITS);
+		assert_eq!(
+			Balances::free_balance(Treasury::account_id()),
+			EXISTENTIAL_DEPOSIT + TRANSFER_GAS * BASE_FEE
+		);
+		assert_eq!(Balances::free_balance(author()), EXISTENTIAL_DEPOSIT + TRANSFER_GAS * tip);
+	});
+}
+
+#[test]
+fn without_tip_author_gets_nothing() {
+	new_test_ext().execute_with(|| {
+		let issuance = Balances::total_issuance();
+
+		assert_ok!(Ethereum::transact(
+			RuntimeOrigin::from(pallet_ethereum::RawOrigin::EthereumTransaction(ALICE)),
+			transfer(UNITS, 0),
+		));
+
+		assert_eq!(
+			Balances::free_balance(Treasury::account_id()),
+			EXISTENTIAL_DEPOSIT + TRANSFER_GAS * BASE_FEE
+		);
+		assert_eq!(Balances::free_balance(author()), EXISTENTIAL_DEPOSIT);
+		// Fees are no longer burned.
+		assert_eq!(Balances::total_issuance(), issuance);
+	});
+}
+
+#[test]
+fn call_tracer_traces_transfer() {
+	new_test_ext().execute_with(|| {
+		let (tracer, result) = gpu_evm_tracing::using(CallTracer::default(), || {
+			Ethereum::transact(
+				RuntimeOrigin::from(pallet_ethereum::RawOrigin::EthereumTransaction(ALICE)),
+				transfer(UNITS, 0),
+			)
+		});
+		assert_ok!(result);
+
+		let Some(TransactionTrace::Call(trace)) = tracer.into_trace() else {
+			panic!("transfer must produce a call trace");
+		};
+		assert_eq!(trace.call_type, CallType::Call);
+		assert_eq!((trace.from, trace.to), (ALICE, BOB));
+		assert_eq!(trace.value, UNITS.into());
+		assert_eq!(trace.gas_used, TRANSFER_GAS.into());
+		assert_eq!(trace.error, None);
+		assert!(trace.calls.is_empty());
+	});
+}
+
+#[test]
+fn prestate_tracer_collects_sender_and_recipient() {
+	new_test_ext().execute_with(|| {
+		let (tracer, result) = gpu_evm_tracing::using(PrestateTracer::default(), || {
+			Ethereum::transact(
+				RuntimeOrigin::from(pallet_ethereum::RawOrigin::EthereumTransaction(ALICE)),
+				transfer(UNITS, 0),
+			)
+		});
+		assert_ok!(result);
+
+		let touched = tracer.into_touched();
+		assert!(touched.contains_key(&ALICE));
+		assert!(touched.contains_key(&BOB));
+	});
+}
+
+fn call_not_allowed(reason: &'static str) -> Option<PrecompileFailure> {
+	Some(PrecompileFailure::Error { exit_status: ExitError::Other(reason.into()) })
+}
+
+fn validate_dispatch(call: &RuntimeCall) -> Option<PrecompileFailure> {
+	DispatchAllowlist::<Runtime>::validate_before_dispatch(&account(ALICE), call)
+}
+
+#[test]
+fn dispatch_allowlist_defaults_to_user_pallets() {
+	new_test_ext().execute_with(|| {
+		let transfer: RuntimeCall =
+			pallet_balances::Call::transfer_allow_death { dest: account(BOB).into(), value: UNITS }
+				.into();
+		assert_eq!(validate_dispatch(&transfer), None);
+
+		let remark: RuntimeCall = frame_system::Call::remark { remark: vec![] }.into();
+		assert_eq!(validate_dispatch(&remark), call_not_allowed("call not allowed"));
+	});
+}
+
+#[test]
+fn dispatch_allowlist_rejects_operational_calls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(DispatchAllowlistPallet::set_allowlist(
+			RuntimeOrigin::root(),
+			BoundedVec::truncate_from(vec![AllowedCall::pallet("System")]),
+		));
+
+		let remark: RuntimeCall = frame_system::Call::remark { remark: vec![] }.into();
+		assert_eq!(validate_dispatch(&remark), None);
+
+		let set_code: RuntimeCall = frame_system::Call::set_code { code: vec![] }.into();
+		assert_eq!(validate_dispatch(&set_code), call_not_allowed("invalid call"));
+	});
+}
+
+#[test]
+fn dispatch_allowlist_is_set_by_general_admin() {
+	new_test_ext().execute_with(|| {
+		let allowlist = BoundedVec::truncate_from(vec![AllowedCall::call("System", "remark")]);
+		assert_noop!(
+			DispatchAllowlistPallet::set_allowlist(
+				RuntimeOrigin::signed(account(ALICE)),
+				allowlist.clone(),
+			),
+			DispatchError::BadOrigin,
+		);
+
+		assert_ok!(DispatchAllowlistPallet::set_allowlist(
+			pallet_custom_origins::Origin::GeneralAdmin.into(),
+			allowlist.clone(),
+		));
+		assert_eq!(DispatchAllowlistPallet::allowlist(), allowlist);
+
+		let remark: RuntimeCall = frame_system::Call::remark { remark: vec![] }.into();
+		assert_eq!(validate_dispatch(&remark), None);
+		let remark_with_event: RuntimeCall =
+			frame_system::Call::remark_with_event { remark: vec![] }.into();
+		assert_eq!(validate_dispatch(&remark_with_event), call_not_allowed("call not allowed"));
+		let transfer: RuntimeCall =
+			pallet_balances::Call::transfer_allow_death { dest: account(BOB).into(), value: UNITS }
+				.into();
+		assert_eq!(validate_dispatch(&transfer), call_not_allowed("call not allowed"));
+	});
+}
+
+fn set_allowlist_call(entries: Vec<AllowedCall>) -> RuntimeCall {
+	dispatch_allowlist::Call::set_allowlist { allowlist: BoundedVec::truncate_from(entries) }.into()
+}
+
+#[test]
+fn dispatch_allowlist_weight_grows_with_its_length() {
+	let weight = |n| {
+		set_allowlist_call(vec![AllowedCall::pallet("System"); n])
+			.get_dispatch_info()
+			.weight
+	};
+
+	assert!(weight(64).ref_time() > weight(1).ref_time());
+}
+
+#[test]
+fn dispatch_allowlist_longer_than_the_bound_does_not_decode() {
+	let entries = vec![AllowedCall::pallet("System"); 65];
+
+	// The call index of `set_allowlist`, followed by one entry more than `MaxAllowedCalls`.
+	let mut encoded = set_allowlist_call(entries[..64].to_vec()).encode()[..2].to_vec();
+	entries.encode_to(&mut encoded);
+
+	assert!(RuntimeCall::decode(&mut &encoded[..]).is_err());
+}
+
+/// Calls the precompile at `address` from `ALICE`, outside of a transaction.
+fn call_precompile(address: u64, input: Vec<u8>) -> (ExitReason, Vec<u8>) {
+	call_precompile_with_gas_limit(address, input, 15_000_000)
+}
+
+fn call_precompile_with_gas_limit(
+	address: u64,
+	input: Vec<u8>,
+	gas_limit: u64,
+) -> (ExitReason, Vec<u8>) {
+	call(H160::from_low_u64_be(address), input, gas_limit)
+}
+
+/// Calls `to` from `ALICE`, outside of a transaction.
+fn call(to: H160, input: Vec<u8>, gas_limit: u64) -> (ExitReason, Vec<u8>) {
+	let info = <Runtime as pallet_evm::Config>::Runner::call(
+		ALICE,
+		to,
+		input,
+		U256::zero(),
+		gas_limit,
+		None,
+		None,
+		None,
+		vec![],
+		false,
+		false,
+		None,
+		None,
+		<Runtime as pallet_evm::Config>::config(),
+	)
+	.expect("call is not validated");
+	(info.exit_reason, info.value)
+}
+
+/// Runs the precompile test vectors in `json` against the precompile at `address`. Failure vectors
+/// have an `ExpectedError` instead of an `Expected` output.
+fn check_test_vectors(address: u64, json: &str) {
+	let vectors: Vec<serde_json::Value> = serde_json::from_str(json).unwrap();
+	for vector in vectors {
+		let name = &vector["Name"];
+		let input = vector["Input"].as_str().unwrap().from_hex().unwrap();
+		let (exit_reason, output) = call_precompile(address, input);
+		match vector["ExpectedError"].as_str() {
+			Some(error) => assert_eq!(
+				exit_reason,
+				ExitReason::Error(ExitError::Other(error.to_owned().into())),
+				"{name}",
+			),
+			None => {
+				assert_eq!(exit_reason, ExitReason::Succeed(ExitSucceed::Returned), "{name}");
+				let expected: Vec<u8> = vector["Expected"].as_str().unwrap().from_hex().unwrap();
+				assert_eq!(output, expected, "{name}");
+			},
+		}
+	}
+}
+
+macro_rules! testdata {
+	($file:literal) => {
+		include_str!(concat!("../../../../frontier/frame/evm/precompile/testdata/", $file))
+	};
+}
+
+macro_rules! bls12381_testdata {
+	($file:literal) => {
+		include_str!(concat!("../../../../pallets/precompiles/bls12381/testdata/", $file))
+	};
+}
+
+#[test]
+fn precompiles_are_mounted() {
+	for address in (11..=17).chain([256]).chain(1028..=1043) {
+		assert!(
+			GpuPrecompiles::<Runtime>::used_addresses()
+				.any(|a| a == H160::from_low_u64_be(address)),
+			"{address} is not a used address",
+		);
+	}
+}
+
+#[test]
+fn curve25519_precompiles_work() {
+	// Multiples of the ristretto255 generator, from RFC 9496.
+	let b = hex_literal::hex!("e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76");
+	let b2 = hex_literal::hex!("6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919");
+	let b3 = hex_literal::hex!("94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259");
+
+	new_test_ext().execute_with(|| {
+		let returned = ExitReason::Succeed(ExitSucceed::Returned);
+
+		assert_eq!(call_precompile(1028, [b, b2].concat()), (returned.clone(), b3.to_vec()));
+
+		let mut scalar = [0u8; 32];
+		scalar[0] = 3;
+		assert_eq!(call_precompile(1029, [scalar, b].concat()), (returned, b3.to_vec()));
+
+		assert_eq!(
+			call_precompile(1028, vec![0; 33]).0,
+			ExitReason::Error(ExitError::Other("input must contain multiple of 32 bytes".into())),
+		);
+	});
+}
+
+#[test]
+fn p256_verify_precompile_works() {
+	// sha256("hello passkey") signed with a fixed secp256r1 key.
+	let input = [
+		hex_literal::hex!("a78aa2145253267d4ed15e649d6bc32659677b00717e606ec11fcb4d754053b4"),
+		hex_literal::hex!("78f71b28dddec5f4686d7b36ac0c9be4a79f2a5d67c2fd8f9ef670840f72a6ee"),
+		hex_literal::hex!("3ca95606e445c9ab41952f1d8a57e3530cc26b0588df234bef56b5b75dae37dc"),
+		hex_literal::hex!("471c3e758c4904285bba7e53118ed0f524adeb0757d25bd2f8e7b0d76dfa714c"),
+		hex_literal::hex!("dd520f7aca8a8b917acc37f51de8f0c9bbe3ad858382e702dc25a12d09f7a858"),
+	]
+	.concat();
+	new_test_ext().execute_with(|| {
+		let returned = ExitReason::Succeed(ExitSucceed::Returned);
+		let mut one = vec![0u8; 32];
+		one[31] = 1;
+
+		assert_eq!(call_precompile(256, input.clone()), (returned.clone(), one));
+		assert_eq!(call_precompile(256, input[..159].to_vec()), (returned, vec![]));
+	});
+}
+
+#[test]
+fn bls12377_precompiles_pass_test_vectors() {
+	new_test_ext().execute_with(|| {
+		check_test_vectors(1030, testdata!("bls12377G1Add.json"));
+		check_test_vectors(1031, testdata!("bls12377G1Mul.json"));
+		check_test_vectors(1032, testdata!("bls12377G1MultiExp.json"));
+		check_test_vectors(1033, testdata!("bls12377G2Add.json"));
+		check_test_vectors(1034, testdata!("bls12377G2Mul.json"));
+		check_test_vectors(1035, testdata!("bls12377G2MultiExp.json"));
+
+		check_test_vectors(1030, testdata!("fail-bls12377G1Add.json"));
+		check_test_vectors(1031, testdata!("fail-bls12377G1Mul.json"));
+		check_test_vectors(1032, testdata!("fail-bls12377G1MultiExp.json"));
+		check_test_vectors(1033, testdata!("fail-bls12377G2Add.json"));
+		check_test_vectors(1034, testdata!("fail-bls12377G2Mul.json"));
+		check_test_vectors(1035, testdata!("fail-bls12377G2MultiExp.json"));
+		check_test_vectors(1036, testdata!("fail-bls12377Pairing.json"));
+	});
+}
+
+#[test]
+fn bw6761_precompiles_pass_test_vectors() {
+	new_test_ext().execute_with(|| {
+		check_test_vectors(1037, testdata!("bw6761G1Add.json"));
+		check_test_vectors(1038, testdata!("bw6761G1Mul.json"));
+		check_test_vectors(1039, testdata!("bw6761G1MultiExp.json"));
+		check_test_vectors(1040, testdata!("bw6761G2Add.json"));
+		check_test_vectors(1041, testdata!("bw6761G2Mul.json"));
+		check_test_vectors(1042, testdata!("bw6761G2MultiExp.json"));
+
+		check_test_vectors(1037, testdata!("fail-bw6761G1Add.json"));
+		check_test_vectors(1038, testdata!("fail-bw6761G1Mul.json"));
+		check_test_vectors(1039, testdata!("fail-bw6761G1MultiExp.json"));
+		check_test_vectors(1040, testdata!("fail-bw6761G2Add.json"));
+		check_test_vectors(1041, testdata!("fail-bw6761G2Mul.json"));
+		check_test_vectors(1042, testdata!("fail-bw6761G2MultiExp.json"));
+		check_test_vectors(1043, testdata!("fail-bw6761Pairing.json"));
+	});
+}
+
+#[test]
+fn bw6761_precompiles_charge_gas() {
+	new_test_ext().execute_with(|| {
+		let vectors: Vec<serde_json::Value> =
+			serde_json::from_str(testdata!("bw6761G1Mul.json")).unwrap();
+		let input: Vec<u8> = vectors[0]["Input"].as_str().unwrap().from_hex().unwrap();
+
+		// Enough for the call itself but not for the multiplication.
+		let (exit_reason, _) = call_precompile_with_gas_limit(1038, input.clone(), 21_000 + 50_000);
+		assert_eq!(exit_reason, ExitReason::Error(ExitError::OutOfGas));
+
+		let (exit_reason, _) = call_precompile_with_gas_limit(1038, input, 21_000 + 80_000);
+		assert_eq!(exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+	});
+}
+
+#[test]
+fn bw6761_multiexp_gas_is_discounted() {
+	new_test_ext().execute_with(|| {
+		let vectors: Vec<serde_json::Value> =
+			serde_json::from_str(testdata!("bw6761G1Mul.json")).unwrap();
+		let pair: Vec<u8> = vectors[0]["Input"].as_str().unwrap().from_hex().unwrap();
+
+		// A single pair costs more than a multiplication: 64_000 * 1.266.
+		let (exit_reason, _) = call_precompile_with_gas_limit(1039, pair.clone(), 21_000 + 81_000);
+		assert_eq!(exit_reason, ExitReason::Error(ExitError::OutOfGas));
+
+		// Two pairs cost less than two multiplications: 2 * 64_000 * 0.733.
+		let (exit_reason, _) =
+			call_precompile_with_gas_limit(1039, pair.repeat(2), 21_000 + 8_192 + 94_000);
+		assert_eq!(exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+	});
+}
+
+#[test]
+fn curve25519_precompiles_charge_gas() {
+	let b = hex_literal::hex!("e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76");
+
+	new_test_ext().execute_with(|| {
+		let mut scalar = [0u8; 32];
+		scalar[0] = 3;
+		let input = [scalar, b].concat();
+
+		// Enough for the call itself but not for an `ECMUL`.
+		let (exit_reason, _) = call_precompile_with_gas_limit(1029, input.clone(), 21_000 + 5_000);
+		assert_eq!(exit_reason, ExitReason::Error(ExitError::OutOfGas));
+
+		let (exit_reason, _) = call_precompile_with_gas_limit(1029, input, 21_000 + 8_000);
+		assert_eq!(exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+	});
+}
+
+#[test]
+fn bls12381_precompiles_pass_test_vectors() {
+	new_test_ext().execute_with(|| {
+		check_test_vectors(11, bls12381_testdata!("bls12381G1Add.json"));
+		check_test_vectors(12, bls12381_testdata!("bls12381G1MSM.json"));
+		check_test_vectors(13, bls12381_testdata!("bls12381G2Add.json"));
+		check_test_vectors(14, bls12381_testdata!("bls12381G2MSM.json"));
+		check_test_vectors(15, bls12381_testdata!("bls12381Pairing.json"));
+		check_test_vectors(16, bls12381_testdata!("bls12381MapG1.json"));
+		check_test_vectors(17, bls12381_testdata!("bls12381MapG2.json"));
+
+		check_test_vectors(11, bls12381_testdata!("fail-bls12381G1Add.json"));
+		check_test_vectors(12, bls12381_testdata!("fail-bls12381G1MSM.json"));
+		check_test_vectors(13, bls12381_testdata!("fail-bls12381G2Add.json"));
+		check_test_vectors(14, bls12381_testdata!("fail-bls12381G2MSM.json"));
+		check_test_vectors(15, bls12381_testdata!("fail-bls12381Pairing.json"));
+		check_test_vectors(16, bls12381_testdata!("fail-bls12381MapG1.json"));
+		check_test_vectors(17, bls12381_testdata!("fail-bls12381MapG2.json"));
+	});
+}
+
+/// Checks the Solidity interfaces and the ABI JSON in `solidity/` against the ones rendered from
+/// the `Action` enums of the precompiles. `UPDATE_INTERFACES=1` rewrites them instead.
+#[test]
+fn precompile_interfaces_are_up_to_date() {
+	use precompile_utils::solidity::{abi_json, interface, SolidityInterface};
+
+	fn rendered<T: SolidityInterface>(name: &str) -> [(String, String); 2] {
+		[(format!("{name}.sol"), interface::<T>(name)), (format!("{name}.json"), abi_json::<T>())]
+	}
+
+	let interfaces = [
+		rendered::<pallet_evm_precompile_staking::Action>("IStaking"),
+		rendered::<pallet_evm_precompile_sr25519::Action>("ISr25519"),
+		rendered::<pallet_evm_precompile_substrate_ecdsa::Action>("ISubstrateEcdsa"),
+		rendered::<pallet_evm_precompile_batch::Action>("IBatch"),
+		rendered::<pallet_evm_precompile_governance::Action>("IGovernance"),
+		rendered::<pallet_evm_precompile_balances_erc20::Action>("IBalancesErc20"),
+		rendered::<pallet_evm_precompile_call_permit::Action>("ICallPermit"),
+		rendered::<pallet_evm_precompile_assets_erc20::FactoryAction>("IAssetsFactory"),
+		rendered::<pallet_evm_precompile_assets_erc20::Action>("IAssetsErc20"),
+	];
+
+	let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("solidity");
+	if std::env::var("UPDATE_INTERFACES").as_deref() == Ok("1") {
+		std::fs::create_dir_all(&dir).unwrap();
+		for (file, contents) in interfaces.into_iter().flatten() {
+			std::fs::write(dir.join(file), contents).unwrap();
+		}
+		return
+	}
+
+	let outdated: Vec<_> = interfaces
+		.into_iter()
+		.flatten()
+		.filter(|(file, contents)| {
+			std::fs::read_to_string(dir.join(file)).ok().as_ref() != Some(contents)
+		})
+		.map(|(file, _)| file)
+		.collect();
+	assert!(
+		outdated.is_empty(),
+		"solidity/{{{}}} differ from the precompiles, rerun with UPDATE_INTERFACES=1 to update them",
+		outdated.join(","),
+	);
+}
+
+#[test]
+fn precompile_interface_events_match_logs() {
+	use pallet_evm_precompile_governance as governance;
+	use precompile_utils::solidity::SolidityInterface;
+
+	fn topics<T: SolidityInterface>() -> Vec<[u8; 32]> {
+		T::EVENTS.iter().map(|event| event.selector).collect()
+	}
+
+	assert_eq!(
+		topics::<pallet_evm_precompile_assets_erc20::Action>(),
+		vec![
+			pallet_evm_precompile_assets_erc20::SELECTOR_LOG_TRANSFER,
+			pallet_evm_precompile_assets_erc20::SELECTOR_LOG_APPROVAL,
+		],
+	);
+	assert_eq!(
+		topics::<pallet_evm_precompile_balances_erc20::Action>(),
+		vec![
+			pallet_evm_precompile_balances_erc20::SELECTOR_LOG_TRANSFER,
+			pallet_evm_precompile_balances_erc20::SELECTOR_LOG_APPROVAL,
+		],
+	);
+	assert_eq!(
+		topics::<pallet_evm_precompile_batch::Action>(),
+		vec![
+			pallet_evm_precompile_batch::LOG_SUBCALL_SUCCEEDED,
+			pallet_evm_precompile_batch::LOG_SUBCALL_FAILED,
+		],
+	);
+	assert_eq!(
+		topics::<governance::Action>(),
+		vec![
+			governance::SELECTOR_LOG_VOTED,
+			governance::SELECTOR_LOG_VOTE_REMOVED,
+			governance::SELECTOR_LOG_DELEGATED,
+			governance::SELECTOR_LOG_UNDELEGATED,
+			governance::SELECTOR_LOG_SUBMITTED,
+			governance::SELECTOR_LOG_DECISION_DEPOSIT_PLACED,
+		],
+	);
+}
+
+#[test]
+fn precompile_interface_errors_match_reverts() {
+	use pallet_evm_precompile_assets_erc20 as assets_erc20;
+	use pallet_evm_precompile_batch as batch;
+	use precompile_utils::solidity::SolidityInterface;
+
+	let selectors: Vec<_> = assets_erc20::Action::ERRORS
+		.iter()
+		.map(|error| error.selector.to_be_bytes())
+		.collect();
+	assert_eq!(
+		selectors,
+		[
+			assets_erc20::SELECTOR_ERROR_INSUFFICIENT_BALANCE,
+			assets_erc20::SELECTOR_ERROR_INSUFFICIENT_ALLOWANCE,
+		]
+		.map(|hash| [hash[0], hash[1], hash[2], hash[3]]),
+	);
+
+	let selectors: Vec<_> =
+		batch::Action::ERRORS.iter().map(|error| error.selector.to_be_bytes()).collect();
+	assert_eq!(
+		selectors,
+		[batch::ERROR_SUBCALL_FAILED, batch::ERROR_OUT_OF_GAS]
+			.map(|hash| [hash[0], hash[1], hash[2], hash[3]]),
+	);
+}
+
+#[test]
+fn assets_erc20_reverts_with_custom_errors() {
+	use pallet_evm_precompile_assets_erc20::{
+		Action, AddressToAssetId, SELECTOR_ERROR_INSUFFICIENT_ALLOWANCE,
+		SELECTOR_ERROR_INSUFFICIENT_BALANCE,
+	};
+	use precompile_utils::{Address, CustomError, EvmDataWriter, Revert};
+
+	fn reverted(error: CustomError) -> (ExitReason, Vec<u8>) {
+		(ExitReason::Revert(ExitRevert::Reverted), Revert::from(error).to_encoded_bytes())
+	}
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(
+			RuntimeOrigin::root(),
+			1u128.into(),
+			account(ALICE).into(),
+			true,
+			1,
+		));
+		assert_ok!(Assets::mint(
+			RuntimeOrigin::signed(account(ALICE)),
+			1u128.into(),
+			account(ALICE).into(),
+			100,
+		));
+		let asset = Runtime::asset_id_to_address(1);
+
+		let input = EvmDataWriter::new_with_selector(Action::Transfer)
+			.write(Address(BOB))
+			.write(U256::from(101))
+			.build();
+		assert_eq!(
+			call(asset, input, 15_000_000),
+			reverted(
+				CustomError::new(SELECTOR_ERROR_INSUFFICIENT_BALANCE)
+					.write(Address(ALICE))
+					.write(U256::from(100))
+					.write(U256::from(101))
+			),
+		);
+
+		let input = EvmDataWriter::new_with_selector(Action::TransferFrom)
+			.write(Address(BOB))
+			.write(Address(ALICE))
+			.write(U256::from(1))
+			.build();
+		assert_eq!(
+			call(asset, input, 15_000_000),
+			reverted(
+				CustomError::new(SELECTOR_ERROR_INSUFFICIENT_ALLOWANCE)
+					.write(Address(ALICE))
+					.write(U256::zero())
+					.write(U256::from(1))
+			),
+		);
+	});
+}
+
+#[test]
+fn batch_reverts_with_the_path_of_invalid_arguments() {
+	use pallet_evm_precompile_batch::{Action, CALL_DATA_LIMIT};
+	use precompile_utils::{Address, Bytes, EvmDataWriter, InjectBacktrace, Revert};
+
+	new_test_ext().execute_with(|| {
+		let input = EvmDataWriter::new_with_selector(Action::BatchAll)
+			.write(vec![Address(BOB), Address(BOB)])
+			.write(Vec::<U256>::new())
+			.write(vec![Bytes(vec![]), Bytes(vec![1; CALL_DATA_LIMIT as usize + 1])])
+			.write(Vec::<u64>::new())
+			.build();
+
+		let message = format!("bytes/string is longer than {CALL_DATA_LIMIT} bytes");
+		assert_eq!(
+			call_precompile(20486, input),
+			(
+				ExitReason::Revert(ExitRevert::Reverted),
+				Revert::new(message).in_array(1).in_arg(2).to_encoded_bytes(),
+			),
+		);
+	});
+}
+
+#[test]
+fn batch_all_reverts_with_the_index_and_output_of_the_failed_subcall() {
+	use pallet_evm_precompile_assets_erc20::{self as assets_erc20, AddressToAssetId};
+	use pallet_evm_precompile_batch::{Action, ERROR_SUBCALL_FAILED};
+	use precompile_utils::{Address, Bytes, CustomError, EvmDataWriter, Revert};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(
+			RuntimeOrigin::root(),
+			1u128.into(),
+			account(ALICE).into(),
+			true,
+			1,
+		));
+		assert_ok!(Assets::mint(
+			RuntimeOrigin::signed(account(ALICE)),
+			1u128.into(),
+			account(ALICE).into(),
+			100,
+		));
+		let asset = Runtime::asset_id_to_address(1);
+		let transfer = |amount: u32| {
+			Bytes(
+				EvmDataWriter::new_with_selector(assets_erc20::Action::Transfer)
+					.write(Address(BOB))
+					.write(U256::from(amount))
+					.build(),
+			)
+		};
+
+		let input = EvmDataWriter::new_with_selector(Action::BatchAll)
+			.write(vec![Address(asset), Address(asset)])
+			.write(Vec::<U256>::new())
+			.write(vec![transfer(50), transfer(51)])
+			.write(Vec::<u64>::new())
+			.build();
+
+		let insufficient_balance =
+			CustomError::new(assets_erc20::SELECTOR_ERROR_INSUFFICIENT_BALANCE)
+				.write(Address(ALICE))
+				.write(U256::from(50))
+				.write(U256::from(51));
+		let failed = CustomError::new(ERROR_SUBCALL_FAILED)
+			.write(U256::from(1))
+			.write(Bytes(Revert::from(insufficient_balance).to_encoded_bytes()));
+		assert_eq!(
+			call_precompile(20486, input),
+			(ExitReason::Revert(ExitRevert::Reverted), Revert::from(failed).to_encoded_bytes()),
+		);
+		// The first transfer is rolled back with the batch.
+		assert_eq!(Assets::balance(1u128.into(), account(ALICE)), 100);
+	});
+}
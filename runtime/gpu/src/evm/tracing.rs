@@ -0,0 +1,117 @@
+// Copyright (C) 2021-2022 gpu.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Replay of blocks with the EVM tracers of `gpu_evm_tracing`.
+
+use crate::{Executive, Header, Runtime, RuntimeCall, UncheckedExtrinsic};
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use gpu_evm_tracing::{
+	CallTracer, PrestateAccount, PrestateTracer, RawTracer, TraceType, TransactionTrace,
+};
+use sp_core::H256;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+/// Replay a block on top of the state of its parent, tracing its Ethereum transactions.
+///
+/// Substrate extrinsics are applied untraced, so that each Ethereum transaction runs on the
+/// state it saw when the block was built. When `only` is set, the other Ethereum transactions
+/// are applied untraced too and the replay stops after the traced one.
+pub fn replay(
+	header: &Header,
+	extrinsics: Vec<UncheckedExtrinsic>,
+	trace_type: TraceType,
+	only: Option<H256>,
+) -> Vec<(H256, TransactionTrace)> {
+	Executive::initialize_block(header);
+
+	let mut traces = Vec::new();
+	for extrinsic in extrinsics {
+		let traced = match &extrinsic.0.function {
+			RuntimeCall::Ethereum(pallet_ethereum::Call::transact { transaction }) =>
+				Some(transaction.hash()),
+			_ => None,
+		}
+		.filter(|hash| only.map_or(true, |only| only == *hash));
+
+		match traced {
+			Some(hash) => {
+				if let Some(trace) = trace(extrinsic, trace_type) {
+					traces.push((hash, trace));
+				}
+				if only.is_some() {
+					break
+				}
+			},
+			None => {
+				let _ = Executive::apply_extrinsic(extrinsic);
+			},
+		}
+	}
+	traces
+}
+
+/// Apply `extrinsic` with the tracer of `trace_type`.
+fn trace(extrinsic: UncheckedExtrinsic, trace_type: TraceType) -> Option<TransactionTrace> {
+	match trace_type {
+		TraceType::Call => {
+			let (tracer, _) = gpu_evm_tracing::using(CallTracer::default(), || {
+				Executive::apply_extrinsic(extrinsic)
+			});
+			tracer.into_trace()
+		},
+		TraceType::Raw { disable_storage, disable_memory, disable_stack } => {
+			let tracer = RawTracer::new(disable_storage, disable_memory, disable_stack);
+			let (tracer, _) =
+				gpu_evm_tracing::using(tracer, || Executive::apply_extrinsic(extrinsic));
+			Some(tracer.into_trace())
+		},
+		TraceType::Prestate => {
+			// Find out what the transaction touches, then read it before applying it for good.
+			let tracer = with_transaction(|| {
+				let (tracer, _) = gpu_evm_tracing::using(PrestateTracer::default(), || {
+					Executive::apply_extrinsic(extrinsic.clone())
+				});
+				TransactionOutcome::Rollback(Ok::<_, DispatchError>(tracer))
+			})
+			.ok()?;
+
+			let prestate = tracer
+				.into_touched()
+				.into_iter()
+				.map(|(address, slots)| {
+					let (account, _) = pallet_evm::Pallet::<Runtime>::account_basic(&address);
+					let storage = slots
+						.into_iter()
+						.map(|slot| {
+							(slot, pallet_evm::AccountStorages::<Runtime>::get(address, slot))
+						})
+						.collect();
+					let prestate = PrestateAccount {
+						balance: account.balance,
+						nonce: account.nonce,
+						code: pallet_evm::AccountCodes::<Runtime>::get(address),
+						storage,
+					};
+					(address, prestate)
+				})
+				.collect();
+
+			let _ = Executive::apply_extrinsic(extrinsic);
+			Some(TransactionTrace::Prestate(prestate))
+		},
+	}
+}
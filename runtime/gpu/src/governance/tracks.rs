@@ -297,3 +297,29 @@ impl pallet_referenda::TracksInfo<Balance, BlockNumber> for TracksInfo {
 	}
 }
 pallet_referenda::impl_tracksinfo_get!(TracksInfo, Balance, BlockNumber);
+
+/// Inverse of `track_for`, used by the governance precompile to pick the origin of a proposal.
+impl pallet_evm_precompile_governance::TrackOrigin<OriginCaller> for TracksInfo {
+	fn track_origin(track_id: u16) -> Option<OriginCaller> {
+		let origin: OriginCaller = match track_id {
+			0 => frame_system::RawOrigin::Root.into(),
+			1 => origins::Origin::WhitelistedCaller.into(),
+			// General admin
+			10 => origins::Origin::StakingAdmin.into(),
+			11 => origins::Origin::Treasurer.into(),
+			12 => origins::Origin::FellowshipAdmin.into(),
+			13 => origins::Origin::GeneralAdmin.into(),
+			// Referendum admins
+			20 => origins::Origin::ReferendumCanceller.into(),
+			21 => origins::Origin::ReferendumKiller.into(),
+			// Limited treasury spenders
+			30 => origins::Origin::SmallTipper.into(),
+			31 => origins::Origin::BigTipper.into(),
+			32 => origins::Origin::SmallSpender.into(),
+			33 => origins::Origin::MediumSpender.into(),
+			34 => origins::Origin::BigSpender.into(),
+			_ => return None,
+		};
+		Some(origin)
+	}
+}
@@ -106,6 +106,7 @@ pub mod proxy_config;
 use frame_support::traits::{Currency,OnUnbalanced};
 
 use governance::{pallet_custom_origins, GeneralAdmin, StakingAdmin, Treasurer, TreasurySpender};
+use evm::dispatch_allowlist::pallet_dispatch_allowlist;
 
 impl_runtime_weights!(gpu_runtime_constants);
 
@@ -1036,6 +1037,8 @@ construct_runtime! {
 		Ethereum: pallet_ethereum = 71,
 		BaseFee: pallet_base_fee = 72,
 		EthCall: pallet_custom_signatures = 73,
+		UnifiedAccounts: pallet_unified_accounts = 74,
+		DispatchAllowlist: pallet_dispatch_allowlist = 75,
 
 		Contracts: pallet_contracts = 80,
 
@@ -1217,6 +1220,13 @@ mod benches {
 		[pallet_referenda, Referenda]
 		[pallet_whitelist, Whitelist]
 		[pallet_assets, Assets]
+		// Local
+		[pallet_custom_signatures, EthCall]
+		[pallet_evm_precompile_assets_erc20, AssetsErc20Bench::<Runtime>]
+		[pallet_evm_precompile_batch, BatchBench::<Runtime>]
+		[pallet_evm_precompile_sr25519, Sr25519Bench::<Runtime>]
+		[pallet_evm_precompile_substrate_ecdsa, SubstrateEcdsaBench::<Runtime>]
+		[pallet_unified_accounts, UnifiedAccounts]
 	);
 }
 
@@ -1756,6 +1766,61 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl gpu_evm_tracing::EvmTracingApi<Block> for Runtime {
+		fn trace_transaction(
+			header: &<Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction_hash: H256,
+			trace_type: gpu_evm_tracing::TraceType,
+		) -> Result<Option<gpu_evm_tracing::TransactionTrace>, sp_runtime::DispatchError> {
+			#[cfg(feature = "evm-tracing")]
+			{
+				Ok(evm::tracing::replay(header, extrinsics, trace_type, Some(transaction_hash))
+					.pop()
+					.map(|(_, trace)| trace))
+			}
+			#[cfg(not(feature = "evm-tracing"))]
+			{
+				let _ = (header, extrinsics, transaction_hash, trace_type);
+				Err("Missing `evm-tracing` compile time feature flag.".into())
+			}
+		}
+
+		fn trace_block(
+			header: &<Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			trace_type: gpu_evm_tracing::TraceType,
+		) -> Result<Vec<(H256, gpu_evm_tracing::TransactionTrace)>, sp_runtime::DispatchError> {
+			#[cfg(feature = "evm-tracing")]
+			{
+				Ok(evm::tracing::replay(header, extrinsics, trace_type, None))
+			}
+			#[cfg(not(feature = "evm-tracing"))]
+			{
+				let _ = (header, extrinsics, trace_type);
+				Err("Missing `evm-tracing` compile time feature flag.".into())
+			}
+		}
+	}
+
+	impl pallet_unified_accounts_runtime_api::UnifiedAccountsApi<Block, AccountId> for Runtime {
+		fn evm_address(account: AccountId) -> H160 {
+			UnifiedAccounts::evm_address(&account)
+		}
+
+		fn native_account(address: H160) -> AccountId {
+			UnifiedAccounts::native_account(address)
+		}
+
+		fn claimed_evm_address(account: AccountId) -> Option<H160> {
+			UnifiedAccounts::evm_address_of(account)
+		}
+
+		fn claimed_native_account(address: H160) -> Option<AccountId> {
+			UnifiedAccounts::native_account_of(address)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
@@ -1790,6 +1855,10 @@ sp_api::impl_runtime_apis! {
 			use pallet_election_provider_support_benchmarking::Pallet as ElectionProviderBench;
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use frame_benchmarking::baseline::Pallet as Baseline;
+			use pallet_evm_precompile_assets_erc20::benchmarking::Pallet as AssetsErc20Bench;
+			use pallet_evm_precompile_batch::benchmarking::Pallet as BatchBench;
+			use pallet_evm_precompile_sr25519::benchmarking::Pallet as Sr25519Bench;
+			use pallet_evm_precompile_substrate_ecdsa::benchmarking::Pallet as SubstrateEcdsaBench;
 
 			let mut list = Vec::<BenchmarkList>::new();
 			list_benchmarks!(list, extra);
@@ -1813,12 +1882,20 @@ sp_api::impl_runtime_apis! {
 			use pallet_election_provider_support_benchmarking::Pallet as ElectionProviderBench;
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use frame_benchmarking::baseline::Pallet as Baseline;
+			use pallet_evm_precompile_assets_erc20::benchmarking::Pallet as AssetsErc20Bench;
+			use pallet_evm_precompile_batch::benchmarking::Pallet as BatchBench;
+			use pallet_evm_precompile_sr25519::benchmarking::Pallet as Sr25519Bench;
+			use pallet_evm_precompile_substrate_ecdsa::benchmarking::Pallet as SubstrateEcdsaBench;
 
 			impl pallet_session_benchmarking::Config for Runtime {}
 			impl pallet_offences_benchmarking::Config for Runtime {}
 			impl pallet_election_provider_support_benchmarking::Config for Runtime {}
 			impl frame_system_benchmarking::Config for Runtime {}
 			impl frame_benchmarking::baseline::Config for Runtime {}
+			impl pallet_evm_precompile_assets_erc20::benchmarking::Config for Runtime {}
+			impl pallet_evm_precompile_batch::benchmarking::Config for Runtime {}
+			impl pallet_evm_precompile_sr25519::benchmarking::Config for Runtime {}
+			impl pallet_evm_precompile_substrate_ecdsa::benchmarking::Config for Runtime {}
 
 			let mut whitelist: Vec<TrackedStorageKey> = AllPalletsWithSystem::whitelisted_storage_keys();
 			// let treasury_key = frame_system::Account::<Runtime>::hashed_key_for(Treasury::account_id());
@@ -23,7 +23,12 @@ pub mod pallet_bounties;
 pub mod pallet_child_bounties;
 pub mod pallet_contracts;
 pub mod pallet_conviction_voting;
+pub mod pallet_custom_signatures;
 pub mod pallet_election_provider_multi_phase;
+pub mod pallet_evm_precompile_assets_erc20;
+pub mod pallet_evm_precompile_batch;
+pub mod pallet_evm_precompile_sr25519;
+pub mod pallet_evm_precompile_substrate_ecdsa;
 pub mod pallet_fast_unstake;
 pub mod pallet_identity;
 pub mod pallet_im_online;
@@ -38,6 +43,7 @@ pub mod pallet_session;
 pub mod pallet_staking;
 pub mod pallet_timestamp;
 pub mod pallet_treasury;
+pub mod pallet_unified_accounts;
 pub mod pallet_utility;
 pub mod pallet_vesting;
 pub mod pallet_whitelist;
@@ -0,0 +1,33 @@
+//! Weights for `pallet_custom_signatures`
+//!
+//! Estimates, not the output of `benchmark pallet`: they are to be replaced by running
+//! `scripts/run_benches_for_runtime.sh gpu` with `--pallet=pallet_custom_signatures` on the
+//! reference machine.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_custom_signatures`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_custom_signatures::WeightInfo for WeightInfo<T> {
+	fn call() -> Weight {
+		Weight::from_parts(52_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn typed_call() -> Weight {
+		Weight::from_parts(56_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn call_sponsored() -> Weight {
+		Weight::from_parts(94_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+}
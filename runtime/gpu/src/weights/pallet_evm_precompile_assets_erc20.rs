@@ -0,0 +1,55 @@
+//! Weights for `pallet_evm_precompile_assets_erc20`
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_evm_precompile_assets_erc20`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_evm_precompile_assets_erc20::WeightInfo for WeightInfo<T> {
+	fn total_supply() -> Weight {
+		Weight::from_parts(10_000_000, 3687)
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+	fn balance_of() -> Weight {
+		Weight::from_parts(13_000_000, 6308)
+			.saturating_add(T::DbWeight::get().reads(2))
+	}
+	fn allowance() -> Weight {
+		Weight::from_parts(14_000_000, 6322)
+			.saturating_add(T::DbWeight::get().reads(2))
+	}
+	fn name() -> Weight {
+		Weight::from_parts(13_000_000, 6314)
+			.saturating_add(T::DbWeight::get().reads(2))
+	}
+	fn symbol() -> Weight {
+		Weight::from_parts(12_000_000, 6314)
+			.saturating_add(T::DbWeight::get().reads(2))
+	}
+	fn decimals() -> Weight {
+		Weight::from_parts(12_000_000, 6314)
+			.saturating_add(T::DbWeight::get().reads(2))
+	}
+	fn minimum_balance() -> Weight {
+		Weight::from_parts(10_000_000, 3687)
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+	fn nonces() -> Weight {
+		Weight::from_parts(12_000_000, 4236)
+			.saturating_add(T::DbWeight::get().reads(2))
+	}
+	fn domain_separator() -> Weight {
+		Weight::from_parts(17_000_000, 6314)
+			.saturating_add(T::DbWeight::get().reads(2))
+	}
+	fn permit() -> Weight {
+		Weight::from_parts(86_000_000, 14859)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+}
@@ -0,0 +1,29 @@
+//! Weights for `pallet_evm_precompile_batch`
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_evm_precompile_batch`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_evm_precompile_batch::WeightInfo for WeightInfo<T> {
+	/// The range of component `x` is `[0, 512]`.
+	fn batch_some(x: u32, ) -> Weight {
+		Weight::from_parts(6_000_000, 0)
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(x.into()))
+	}
+	/// The range of component `x` is `[0, 512]`.
+	fn batch_some_until_failure(x: u32, ) -> Weight {
+		Weight::from_parts(6_000_000, 0)
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(x.into()))
+	}
+	/// The range of component `x` is `[0, 512]`.
+	fn batch_all(x: u32, ) -> Weight {
+		Weight::from_parts(6_000_000, 0)
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(x.into()))
+	}
+}
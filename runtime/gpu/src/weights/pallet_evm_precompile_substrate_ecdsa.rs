@@ -0,0 +1,19 @@
+//! Weights for `pallet_evm_precompile_substrate_ecdsa`
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_evm_precompile_substrate_ecdsa`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_evm_precompile_substrate_ecdsa::WeightInfo for WeightInfo<T> {
+	/// The range of component `x` is `[0, 65536]`.
+	fn verify(x: u32, ) -> Weight {
+		Weight::from_parts(49_000_000, 0)
+			.saturating_add(Weight::from_parts(1_500, 0).saturating_mul(x.into()))
+	}
+}
@@ -0,0 +1,31 @@
+//! Weights for `pallet_unified_accounts`
+//!
+//! Estimates, not the output of `benchmark pallet`: they are to be replaced by running
+//! `scripts/run_benches_for_runtime.sh gpu` with `--pallet=pallet_unified_accounts` on the
+//! reference machine.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_unified_accounts`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_unified_accounts::WeightInfo for WeightInfo<T> {
+	/// Storage: UnifiedAccounts NativeToEvm (r:1 w:1)
+	/// Proof: UnifiedAccounts NativeToEvm (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: UnifiedAccounts EvmToNative (r:1 w:1)
+	/// Proof: UnifiedAccounts EvmToNative (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Proof: System BlockHash (max_values: None, max_size: Some(44), added: 2519, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn claim_evm_address() -> Weight {
+		Weight::from_parts(74_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+}